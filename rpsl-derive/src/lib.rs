@@ -0,0 +1,212 @@
+//! Derive macros for converting between typed models and [rpsl-rs](https://docs.rs/rpsl-rs)
+//! objects.
+//!
+//! This crate is not meant to be used directly, instead enable the **derive** feature of
+//! rpsl-rs and use the macros re-exported in its `convert` module.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Type,
+};
+
+/// Derive `FromObject` for a struct with named fields.
+///
+/// See the `convert` module of rpsl-rs for the supported `#[rpsl(...)]` attributes.
+#[proc_macro_derive(FromObject, attributes(rpsl))]
+pub fn derive_from_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_object(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `IntoObject` for a struct with named fields.
+///
+/// See the `convert` module of rpsl-rs for the supported `#[rpsl(...)]` attributes.
+#[proc_macro_derive(IntoObject, attributes(rpsl))]
+pub fn derive_into_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_into_object(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_from_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = mapped_fields(input)?;
+    let initializers = fields.iter().map(|field| {
+        let ident = field.ident;
+        let name = &field.name;
+        let function = match (field.cardinality.required, field.cardinality.multiple) {
+            (true, true) => quote!(required_multiple),
+            (true, false) => quote!(required),
+            (false, true) => quote!(multiple),
+            (false, false) => quote!(optional),
+        };
+        quote_spanned! {field.span=>
+            #ident: ::rpsl::convert::#function(object, #name)?
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::rpsl::convert::FromObject for #ident #ty_generics #where_clause {
+            fn from_object<Spec: ::rpsl::spec::Specification>(
+                object: &::rpsl::Object<'_, Spec>,
+            ) -> ::std::result::Result<Self, ::rpsl::convert::FromObjectError> {
+                ::std::result::Result::Ok(Self {
+                    #(#initializers,)*
+                })
+            }
+        }
+    })
+}
+
+fn expand_into_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = mapped_fields(input)?;
+    let pushes = fields.iter().map(|field| {
+        let ident = field.ident;
+        let name = &field.name;
+        if field.cardinality.multiple {
+            quote_spanned! {field.span=>
+                for value in &self.#ident {
+                    attributes.push(::rpsl::convert::attribute(#name, value));
+                }
+            }
+        } else if !field.cardinality.required {
+            quote_spanned! {field.span=>
+                if let ::std::option::Option::Some(value) = &self.#ident {
+                    attributes.push(::rpsl::convert::attribute(#name, value));
+                }
+            }
+        } else {
+            quote_spanned! {field.span=>
+                attributes.push(::rpsl::convert::attribute(#name, &self.#ident));
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::rpsl::convert::IntoObject for #ident #ty_generics #where_clause {
+            fn into_object(self) -> ::rpsl::Object<'static> {
+                let mut attributes = ::std::vec::Vec::new();
+                #(#pushes)*
+                ::rpsl::Object::new(attributes)
+            }
+        }
+    })
+}
+
+/// A struct field mapped onto an attribute.
+struct MappedField<'i> {
+    ident: &'i Ident,
+    name: LitStr,
+    cardinality: Cardinality,
+    span: proc_macro2::Span,
+}
+
+#[derive(Default)]
+struct Cardinality {
+    required: bool,
+    multiple: bool,
+}
+
+fn mapped_fields(input: &DeriveInput) -> syn::Result<Vec<MappedField<'_>>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "rpsl conversions can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "rpsl conversions can only be derived for structs with named fields",
+        ));
+    };
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().ok_or_else(|| {
+                syn::Error::new_spanned(field, "rpsl conversions require named fields")
+            })?;
+            let mut name = None;
+            let mut cardinality = Cardinality::default();
+
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("rpsl")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        name = Some(meta.value()?.parse::<LitStr>()?);
+                    } else if meta.path.is_ident("required") {
+                        cardinality.required = true;
+                    } else if meta.path.is_ident("multiple") {
+                        cardinality.multiple = true;
+                    } else {
+                        return Err(meta.error("expected `name`, `required` or `multiple`"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            let expected = if cardinality.multiple {
+                Some(("Vec", "`multiple` attributes must be of type `Vec<T>`"))
+            } else if cardinality.required {
+                None
+            } else {
+                Some((
+                    "Option",
+                    "attributes that are not `required` must be of type `Option<T>`",
+                ))
+            };
+            if let Some((container, message)) = expected {
+                if !is_container(&field.ty, container) {
+                    return Err(syn::Error::new_spanned(&field.ty, message));
+                }
+            }
+
+            let name = name.unwrap_or_else(|| {
+                LitStr::new(
+                    &ident.to_string().trim_start_matches("r#").replace('_', "-"),
+                    ident.span(),
+                )
+            });
+
+            Ok(MappedField {
+                ident,
+                name,
+                cardinality,
+                span: field.span(),
+            })
+        })
+        .collect()
+}
+
+/// Whether the type is the given single argument generic container, e.g. `Option<T>`.
+fn is_container(ty: &Type, container: &str) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != container {
+        return false;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => {
+            args.args.len() == 1 && matches!(args.args[0], GenericArgument::Type(_))
+        }
+        _ => false,
+    }
+}
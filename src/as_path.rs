@@ -0,0 +1,852 @@
+//! AS path regular expressions as defined by RFC 2622.
+//!
+//! AS path regular expressions, used in filters such as `<^AS3257 AS-FOO+ .* $>`, match the
+//! AS path of a route. Their symbols are AS numbers, `as-set` names, `PeerAS`, `.` and sets of
+//! these enclosed in brackets, which are combined using the usual regular expression operators.
+//! The `~*`, `~+` and `~{m,n}` operators repeat a symbol, requiring all repetitions to match the
+//! same AS, e.g. `AS-FOO~+` matches an AS of `AS-FOO` prepended any number of times.
+//!
+//! A [`Regex`] is compiled into an automaton when parsed, which tests AS paths given as
+//! sequences of AS numbers. The first AS of a path is the peer AS, the last one the origin AS.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{as_path::Regex, types::AsSetName};
+//!
+//! let regex: Regex = "^AS1 AS-CUSTOMERS~+ .* $".parse()?;
+//!
+//! let customers = |set: &AsSetName, asn: u32| set.as_str() == "AS-CUSTOMERS" && asn >= 64512;
+//! assert!(regex.is_match(&[1, 64512, 64512, 3], &customers));
+//! assert!(!regex.is_match(&[1, 2, 64512], &customers));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Resolving sets
+//!
+//! Membership of AS numbers in the `as-set`s used as symbols is determined by an [`AsSets`]
+//! implementation, which is implemented for closures as shown above. To use a
+//! [`Resolver`], the sets can be expanded using [`ResolvedAsSets`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     as_path::{Regex, ResolvedAsSets},
+//!     parse_object,
+//!     resolve::ObjectResolver,
+//! };
+//!
+//! let object = parse_object("
+//! as-set:         AS-CUSTOMERS
+//! members:        AS65536, AS65537
+//!
+//! ")?;
+//! let resolver: ObjectResolver = [&object].into_iter().collect();
+//!
+//! let regex: Regex = "AS-CUSTOMERS$".parse()?;
+//! let sets = ResolvedAsSets::new(&regex, &resolver);
+//! assert!(sets.unresolved().is_empty());
+//! assert!(regex.is_match(&[1, 65537], &sets));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use winnow::{
+    ascii::{dec_uint, multispace0},
+    combinator::{alt, cut_err, delimited, fail, opt, preceded, repeat, separated},
+    error::{StrContext, StrContextValue},
+    stream::Stream,
+    token::take_while,
+    ModalResult, Parser,
+};
+
+use crate::{
+    resolve::{self, Resolver},
+    types::{complete, AsNumber, AsSetName, TypeError},
+};
+
+/// Determines whether an AS is a member of an `as-set`.
+pub trait AsSets {
+    /// Whether the AS is a member of the set.
+    fn contains(&self, set: &AsSetName, asn: u32) -> bool;
+}
+
+impl<F: Fn(&AsSetName, u32) -> bool> AsSets for F {
+    fn contains(&self, set: &AsSetName, asn: u32) -> bool {
+        self(set, asn)
+    }
+}
+
+/// The members of the `as-set`s used by a regular expression, expanded using a [`Resolver`].
+#[derive(Debug, Clone)]
+pub struct ResolvedAsSets {
+    sets: HashMap<String, HashSet<AsNumber>>,
+    unresolved: Vec<AsSetName>,
+}
+
+impl ResolvedAsSets {
+    /// Expand the sets used by the regular expression, including their nested sets.
+    #[must_use]
+    pub fn new<R: Resolver + ?Sized>(regex: &Regex, resolver: &R) -> Self {
+        let mut unresolved = Vec::new();
+        let sets = regex
+            .as_sets()
+            .into_iter()
+            .map(|name| {
+                let members = resolve::as_set_numbers(resolver, name, &mut unresolved);
+                (name.as_str().to_ascii_uppercase(), members)
+            })
+            .collect();
+        Self { sets, unresolved }
+    }
+
+    /// The sets that are not defined, which are treated as empty.
+    #[must_use]
+    pub fn unresolved(&self) -> &[AsSetName] {
+        &self.unresolved
+    }
+}
+
+impl AsSets for ResolvedAsSets {
+    fn contains(&self, set: &AsSetName, asn: u32) -> bool {
+        self.sets
+            .get(&set.as_str().to_ascii_uppercase())
+            .is_some_and(|members| members.contains(&AsNumber::new(asn)))
+    }
+}
+
+/// A compiled AS path regular expression, e.g. `^AS1 AS-FOO* [AS2 AS3]?$`.
+///
+/// Bounded repetitions are compiled by repeating their operand, expressions compiling to more
+/// than 10 000 instructions, such as deeply nested repetitions, are rejected, as are expressions
+/// nested in more than 64 `()`.
+#[derive(Debug, Clone)]
+pub struct Regex {
+    source: String,
+    node: Node,
+    program: Vec<Instruction>,
+    registers: usize,
+}
+
+impl Regex {
+    /// The text the expression was parsed from, without surrounding whitespace.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// The names of the `as-set`s used as symbols, in order of their first use.
+    #[must_use]
+    pub fn as_sets(&self) -> Vec<&AsSetName> {
+        fn collect<'r>(node: &'r Node, names: &mut Vec<&'r AsSetName>) {
+            match node {
+                Node::Symbol(symbol) | Node::Same { symbol, .. } => match symbol {
+                    Symbol::AsSet(name) => names.push(name),
+                    Symbol::Set { items, .. } => {
+                        names.extend(items.iter().filter_map(|item| match item {
+                            SetItem::AsSet(name) => Some(name),
+                            _ => None,
+                        }));
+                    }
+                    Symbol::Any | Symbol::AsNumber(_) | Symbol::PeerAs => {}
+                },
+                Node::Concatenation(nodes) | Node::Alternation(nodes) => {
+                    for node in nodes {
+                        collect(node, names);
+                    }
+                }
+                Node::Repetition { node, .. } => collect(node, names),
+                Node::Start | Node::End => {}
+            }
+        }
+
+        let mut names = Vec::new();
+        collect(&self.node, &mut names);
+        let mut seen = HashSet::new();
+        names.retain(|name| seen.insert(name.as_str().to_ascii_uppercase()));
+        names
+    }
+
+    /// Whether the expression matches the AS path, of which the first AS is the peer AS.
+    ///
+    /// Unless anchored by `^` and `$`, the expression may match any part of the path.
+    #[must_use]
+    pub fn is_match(&self, path: &[u32], sets: &(impl AsSets + ?Sized)) -> bool {
+        let mut threads = Threads::default();
+        for (position, asn) in path.iter().map(Some).chain([None]).enumerate() {
+            let state = State {
+                position,
+                length: path.len(),
+                peer: path.first().copied(),
+            };
+            // Starting a thread at every position leaves the start of the match unanchored.
+            if threads.add(&self.program, &state, 0, vec![None; self.registers]) {
+                return true;
+            }
+            let Some(&asn) = asn else {
+                break;
+            };
+
+            let mut next = Threads::default();
+            let state = State {
+                position: position + 1,
+                ..state
+            };
+            for (pc, mut registers) in threads.take() {
+                let matches = match &self.program[pc] {
+                    Instruction::Symbol(symbol) => symbol.matches(asn, &state, sets),
+                    Instruction::Capture(symbol, register) => {
+                        registers[*register] = Some(asn);
+                        symbol.matches(asn, &state, sets)
+                    }
+                    Instruction::Same(register) => registers[*register] == Some(asn),
+                    _ => false,
+                };
+                if matches && next.add(&self.program, &state, pc + 1, registers) {
+                    return true;
+                }
+            }
+            threads = next;
+        }
+        false
+    }
+}
+
+impl FromStr for Regex {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let node = complete(
+            delimited(
+                multispace0,
+                |input: &mut &str| alternation(input, 0),
+                multispace0,
+            ),
+            "AS path regular expression",
+            s,
+        )?;
+        let mut compiler = Compiler::default();
+        compiler.node(&node);
+        if compiler.is_full() {
+            return Err(TypeError::new(
+                "AS path regular expression",
+                s,
+                0,
+                format!(
+                    "expression is too large, expected at most {} instructions",
+                    Compiler::MAX_INSTRUCTIONS
+                ),
+            ));
+        }
+        compiler.emit(Instruction::Match);
+        Ok(Self {
+            source: s.trim().to_string(),
+            node,
+            program: compiler.program,
+            registers: compiler.registers,
+        })
+    }
+}
+
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl PartialEq for Regex {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl Eq for Regex {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    /// `^`, the start of the path.
+    Start,
+    /// `$`, the end of the path.
+    End,
+    Symbol(Symbol),
+    Concatenation(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repetition {
+        node: Box<Node>,
+        min: u8,
+        max: Option<u8>,
+    },
+    /// A symbol repeated using `~`, of which all repetitions match the same AS.
+    Same {
+        symbol: Symbol,
+        min: u8,
+        max: Option<u8>,
+    },
+}
+
+/// A symbol matching a single AS in the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Symbol {
+    /// `.`, any AS.
+    Any,
+    AsNumber(AsNumber),
+    AsSet(AsSetName),
+    PeerAs,
+    /// `[...]` or `[^...]`, any AS matching one of the items or none of them.
+    Set {
+        negated: bool,
+        items: Vec<SetItem>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SetItem {
+    Any,
+    AsNumber(AsNumber),
+    /// An inclusive range of AS numbers, e.g. `AS1 - AS5`.
+    Range(AsNumber, AsNumber),
+    AsSet(AsSetName),
+    PeerAs,
+}
+
+impl Symbol {
+    fn matches(&self, asn: u32, state: &State, sets: &(impl AsSets + ?Sized)) -> bool {
+        match self {
+            Self::Any => true,
+            Self::AsNumber(number) => number.get() == asn,
+            Self::AsSet(name) => sets.contains(name, asn),
+            Self::PeerAs => state.peer == Some(asn),
+            Self::Set { negated, items } => {
+                items.iter().any(|item| item.matches(asn, state, sets)) != *negated
+            }
+        }
+    }
+}
+
+impl SetItem {
+    fn matches(&self, asn: u32, state: &State, sets: &(impl AsSets + ?Sized)) -> bool {
+        match self {
+            Self::Any => true,
+            Self::AsNumber(number) => number.get() == asn,
+            Self::Range(start, end) => (start.get()..=end.get()).contains(&asn),
+            Self::AsSet(name) => sets.contains(name, asn),
+            Self::PeerAs => state.peer == Some(asn),
+        }
+    }
+}
+
+/// An instruction of the automaton, which is simulated by running a thread for each possible
+/// path through the program in lockstep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Instruction {
+    /// Consume an AS matching the symbol.
+    Symbol(Symbol),
+    /// Consume an AS matching the symbol, storing it in a register.
+    Capture(Symbol, usize),
+    /// Consume the AS stored in a register.
+    Same(usize),
+    /// Continue only at the start of the path.
+    Start,
+    /// Continue only at the end of the path.
+    End,
+    /// Continue at both instructions, preferring the first.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Debug, Default)]
+struct Compiler {
+    program: Vec<Instruction>,
+    registers: usize,
+}
+
+impl Compiler {
+    /// The maximum number of instructions of a program.
+    const MAX_INSTRUCTIONS: usize = 10_000;
+
+    /// Whether the program exceeds the maximum number of instructions.
+    ///
+    /// Nodes are no longer compiled once it does, such that the size of nested repetitions
+    /// cannot grow unbounded.
+    fn is_full(&self) -> bool {
+        self.program.len() > Self::MAX_INSTRUCTIONS
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.program.push(instruction);
+        self.program.len() - 1
+    }
+
+    fn node(&mut self, node: &Node) {
+        if self.is_full() {
+            return;
+        }
+        match node {
+            Node::Start => {
+                self.emit(Instruction::Start);
+            }
+            Node::End => {
+                self.emit(Instruction::End);
+            }
+            Node::Symbol(symbol) => {
+                self.emit(Instruction::Symbol(symbol.clone()));
+            }
+            Node::Concatenation(nodes) => {
+                for node in nodes {
+                    self.node(node);
+                }
+            }
+            Node::Alternation(nodes) => self.alternation(nodes),
+            Node::Repetition { node, min, max } => {
+                self.repetition(*min, *max, |compiler| compiler.node(node));
+            }
+            Node::Same { symbol, min, max } => {
+                let register = self.registers;
+                self.registers += 1;
+                // The first repetition captures the AS, which the others must match.
+                let repetitions = |compiler: &mut Self| {
+                    compiler.emit(Instruction::Capture(symbol.clone(), register));
+                    compiler.repetition(
+                        min.saturating_sub(1),
+                        max.map(|max| max - 1),
+                        |compiler| {
+                            compiler.emit(Instruction::Same(register));
+                        },
+                    );
+                };
+                match max {
+                    Some(0) => {}
+                    _ if *min == 0 => self.optional(repetitions),
+                    _ => repetitions(self),
+                }
+            }
+        }
+    }
+
+    fn alternation(&mut self, nodes: &[Node]) {
+        let mut jumps = Vec::new();
+        for (index, node) in nodes.iter().enumerate() {
+            if index + 1 == nodes.len() {
+                self.node(node);
+            } else {
+                let split = self.emit(Instruction::Split(0, 0));
+                self.node(node);
+                jumps.push(self.emit(Instruction::Jump(0)));
+                self.program[split] = Instruction::Split(split + 1, self.program.len());
+            }
+        }
+        let end = self.program.len();
+        for jump in jumps {
+            self.program[jump] = Instruction::Jump(end);
+        }
+    }
+
+    /// Compile a node between `min` and `max` times, or any number of times if there is no `max`.
+    fn repetition(&mut self, min: u8, max: Option<u8>, mut node: impl FnMut(&mut Self)) {
+        for _ in 0..min {
+            node(self);
+        }
+        match max {
+            None => {
+                let split = self.emit(Instruction::Split(0, 0));
+                node(self);
+                self.emit(Instruction::Jump(split));
+                self.program[split] = Instruction::Split(split + 1, self.program.len());
+            }
+            Some(max) => {
+                for _ in min..max {
+                    self.optional(&mut node);
+                }
+            }
+        }
+    }
+
+    fn optional(&mut self, node: impl FnOnce(&mut Self)) {
+        let split = self.emit(Instruction::Split(0, 0));
+        node(self);
+        self.program[split] = Instruction::Split(split + 1, self.program.len());
+    }
+}
+
+/// The position of the automaton in the path.
+#[derive(Debug, Clone, Copy)]
+struct State {
+    position: usize,
+    length: usize,
+    peer: Option<u32>,
+}
+
+/// The threads of the automaton waiting to consume the next AS.
+#[derive(Debug, Default)]
+struct Threads {
+    threads: Vec<(usize, Vec<Option<u32>>)>,
+    seen: HashSet<(usize, Vec<Option<u32>>)>,
+}
+
+impl Threads {
+    /// Add a thread at the instruction, following all instructions not consuming an AS.
+    ///
+    /// Returns whether a thread reached the end of the program.
+    fn add(
+        &mut self,
+        program: &[Instruction],
+        state: &State,
+        pc: usize,
+        registers: Vec<Option<u32>>,
+    ) -> bool {
+        if !self.seen.insert((pc, registers.clone())) {
+            return false;
+        }
+        match program[pc] {
+            Instruction::Match => true,
+            Instruction::Start if state.position == 0 => {
+                self.add(program, state, pc + 1, registers)
+            }
+            Instruction::End if state.position == state.length => {
+                self.add(program, state, pc + 1, registers)
+            }
+            Instruction::Start | Instruction::End => false,
+            Instruction::Jump(target) => self.add(program, state, target, registers),
+            Instruction::Split(first, second) => {
+                self.add(program, state, first, registers.clone())
+                    || self.add(program, state, second, registers)
+            }
+            Instruction::Symbol(_) | Instruction::Capture(..) | Instruction::Same(_) => {
+                self.threads.push((pc, registers));
+                false
+            }
+        }
+    }
+
+    fn take(self) -> Vec<(usize, Vec<Option<u32>>)> {
+        self.threads
+    }
+}
+
+/// The maximum number of expressions in `()` nested in each other.
+const MAX_DEPTH: usize = 64;
+
+/// Parse an alternation nested in `depth` expressions in `()`.
+fn alternation(input: &mut &str, depth: usize) -> ModalResult<Node> {
+    separated(
+        1..,
+        |input: &mut &str| concatenation(input, depth),
+        (multispace0, '|'),
+    )
+    .map(|mut nodes: Vec<Node>| {
+        if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Node::Alternation(nodes)
+        }
+    })
+    .parse_next(input)
+}
+
+fn concatenation(input: &mut &str, depth: usize) -> ModalResult<Node> {
+    repeat(
+        0..,
+        preceded(multispace0, |input: &mut &str| repetition(input, depth)),
+    )
+    .map(|mut nodes: Vec<Node>| {
+        if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Node::Concatenation(nodes)
+        }
+    })
+    .parse_next(input)
+}
+
+fn repetition(input: &mut &str, depth: usize) -> ModalResult<Node> {
+    let mut node = primary(input, depth)?;
+    loop {
+        let checkpoint = input.checkpoint();
+        let Some((same, (min, max))) =
+            opt(preceded(multispace0, (opt('~'), operator))).parse_next(input)?
+        else {
+            return Ok(node);
+        };
+        node = match (same, node) {
+            (None, node) => Node::Repetition {
+                node: Box::new(node),
+                min,
+                max,
+            },
+            (Some(_), Node::Symbol(symbol)) => Node::Same { symbol, min, max },
+            (Some(_), _) => {
+                input.reset(&checkpoint);
+                return preceded(multispace0, cut_err(fail))
+                    .context(StrContext::Label("same pattern operator"))
+                    .context(StrContext::Expected(StrContextValue::Description(
+                        "a single symbol before ~*, ~+ or ~{m,n}",
+                    )))
+                    .parse_next(input);
+            }
+        };
+    }
+}
+
+/// Parse a postfix operator into the minimum and maximum number of repetitions.
+fn operator(input: &mut &str) -> ModalResult<(u8, Option<u8>)> {
+    alt((
+        '*'.value((0, None)),
+        '+'.value((1, None)),
+        '?'.value((0, Some(1))),
+        delimited(
+            ('{', multispace0),
+            cut_err(
+                (
+                    dec_uint,
+                    opt(preceded((multispace0, ',', multispace0), opt(dec_uint))),
+                )
+                    .verify(|(min, max)| !matches!(max, Some(Some(max)) if max < min))
+                    .map(|(min, max)| (min, max.unwrap_or(Some(min)))),
+            ),
+            cut_err((multispace0, '}')),
+        )
+        .context(StrContext::Label("repetition"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "{m}, {m,n} or {m,} with m not exceeding n and n not exceeding 255",
+        ))),
+    ))
+    .parse_next(input)
+}
+
+fn primary(input: &mut &str, depth: usize) -> ModalResult<Node> {
+    alt((
+        '^'.value(Node::Start),
+        '$'.value(Node::End),
+        '.'.value(Node::Symbol(Symbol::Any)),
+        delimited(
+            '(',
+            cut_err(|input: &mut &str| group(input, depth + 1)),
+            cut_err((multispace0, ')')),
+        ),
+        delimited('[', cut_err(set), cut_err((multispace0, ']'))).map(Node::Symbol),
+        symbol,
+    ))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "an AS number, as-set name, PeerAS, ., ^, $, a set in [] or an expression in ()",
+    )))
+    .parse_next(input)
+}
+
+/// Parse the alternation of an expression in `()`, unless nested too deeply.
+fn group(input: &mut &str, depth: usize) -> ModalResult<Node> {
+    if depth > MAX_DEPTH {
+        return fail
+            .context(StrContext::Label("expression"))
+            .context(StrContext::Expected(StrContextValue::Description(
+                "at most 64 nested expressions in ()",
+            )))
+            .parse_next(input);
+    }
+    alternation(input, depth)
+}
+
+/// Parse an AS number, as-set name or `PeerAS` outside of a set.
+fn symbol(input: &mut &str) -> ModalResult<Node> {
+    let checkpoint = input.checkpoint();
+    match word.parse_next(input)? {
+        SetItem::AsNumber(number) => Ok(Node::Symbol(Symbol::AsNumber(number))),
+        SetItem::AsSet(name) => Ok(Node::Symbol(Symbol::AsSet(name))),
+        SetItem::PeerAs => Ok(Node::Symbol(Symbol::PeerAs)),
+        SetItem::Range(..) | SetItem::Any => {
+            input.reset(&checkpoint);
+            cut_err(fail)
+                .context(StrContext::Label("range"))
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "a range of AS numbers only within []",
+                )))
+                .parse_next(input)
+        }
+    }
+}
+
+fn set(input: &mut &str) -> ModalResult<Symbol> {
+    let item = alt((
+        '.'.value(SetItem::Any),
+        (
+            word,
+            opt(preceded(
+                (multispace0, '-', multispace0),
+                cut_err(as_number),
+            )),
+        )
+            .verify_map(|(item, end)| match (item, end) {
+                (SetItem::AsNumber(start), Some(end)) if start <= end => {
+                    Some(SetItem::Range(start, end))
+                }
+                (item, None) => Some(item),
+                _ => None,
+            }),
+    ));
+    (
+        opt('^').map(|negated| negated.is_some()),
+        repeat(0.., preceded(multispace0, item)),
+    )
+        .map(|(negated, items)| Symbol::Set { negated, items })
+        .parse_next(input)
+}
+
+/// Parse an AS number, as-set name or `PeerAS`.
+fn word(input: &mut &str) -> ModalResult<SetItem> {
+    take_while(1.., |c: char| {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':'
+    })
+    .verify_map(|word: &str| {
+        if word.eq_ignore_ascii_case("PeerAS") {
+            Some(SetItem::PeerAs)
+        } else if let Ok(number) = word.parse::<AsNumber>() {
+            Some(SetItem::AsNumber(number))
+        } else if let Ok(name) = word.parse::<AsSetName>() {
+            Some(SetItem::AsSet(name))
+        } else {
+            // Ranges may be written without spaces, e.g. `AS1-AS5`.
+            let (start, end) = word.split_once('-')?;
+            match (start.parse(), end.parse()) {
+                (Ok(start), Ok(end)) if start <= end => Some(SetItem::Range(start, end)),
+                _ => None,
+            }
+        }
+    })
+    .parse_next(input)
+}
+
+fn as_number(input: &mut &str) -> ModalResult<AsNumber> {
+    word.verify_map(|item| match item {
+        SetItem::AsNumber(number) => Some(number),
+        _ => None,
+    })
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_whois_response, resolve::ObjectResolver};
+
+    fn path(path: &str) -> Vec<u32> {
+        path.split_whitespace()
+            .map(|asn| asn.parse::<AsNumber>().unwrap().get())
+            .collect()
+    }
+
+    fn customers(name: &AsSetName, asn: u32) -> bool {
+        name.as_str() == "AS-CUSTOMERS" && [10, 11].contains(&asn)
+    }
+
+    #[rstest]
+    #[case("AS1", "AS2 AS1 AS3", true)]
+    #[case("^AS1", "AS2 AS1", false)]
+    #[case("AS1$", "AS2 AS1", true)]
+    #[case("^AS1 AS2*$", "AS1", true)]
+    #[case("^AS1 AS2*$", "AS1 AS2 AS2 AS2", true)]
+    #[case("^AS1 AS2*$", "AS1 AS2 AS3", false)]
+    #[case("^AS1 AS2+$", "AS1", false)]
+    #[case("^AS1 AS2?$", "AS1 AS2 AS2", false)]
+    #[case("^AS1 .* AS3$", "AS1 AS2 AS4 AS3", true)]
+    #[case("^AS1 AS2{2}$", "AS1 AS2 AS2", true)]
+    #[case("^AS1 AS2{2,}$", "AS1 AS2", false)]
+    #[case("^AS1 AS2{1,2}$", "AS1 AS2 AS2 AS2", false)]
+    #[case("^(AS1 | AS2) AS3$", "AS2 AS3", true)]
+    #[case("^(AS1 | AS2 | AS3)$", "AS3", true)]
+    #[case("^AS1 (AS2 AS3)*$", "AS1 AS2 AS3 AS2 AS3", true)]
+    #[case("^AS1 (AS2 AS3)*$", "AS1 AS2 AS3 AS2", false)]
+    #[case("^[AS1 AS2] [AS5 - AS7]$", "AS2 AS6", true)]
+    #[case("^[AS1 AS2] [AS5-AS7]$", "AS2 AS8", false)]
+    #[case("^[^AS1 AS2]", "AS3", true)]
+    #[case("^[^AS1 AS2]", "AS1", false)]
+    #[case("^PeerAS AS-CUSTOMERS+$", "AS1 AS10 AS11", true)]
+    #[case("^AS1 [AS-CUSTOMERS AS12]$", "AS1 AS12", true)]
+    #[case("^AS1 AS-CUSTOMERS$", "AS1 AS12", false)]
+    #[case("(AS1?)*", "", true)]
+    #[case("^$", "", true)]
+    #[case("^$", "AS1", false)]
+    #[case("^AS1 AS-CUSTOMERS~*$", "AS1", true)]
+    #[case("^AS1 AS-CUSTOMERS~*$", "AS1 AS10 AS10 AS10", true)]
+    #[case("^AS1 AS-CUSTOMERS~*$", "AS1 AS10 AS11", false)]
+    #[case("^AS1 AS-CUSTOMERS~+$", "AS1", false)]
+    #[case("^AS1 .~+ AS-CUSTOMERS~+$", "AS1 AS2 AS2 AS10 AS10", true)]
+    #[case("^AS1 .~+ AS-CUSTOMERS~+$", "AS1 AS2 AS3 AS10", false)]
+    #[case("^AS1 [AS2 AS3]~{2,3}$", "AS1 AS3 AS3 AS3", true)]
+    #[case("^AS1 [AS2 AS3]~{2,3}$", "AS1 AS3 AS3 AS3 AS3", false)]
+    #[case("^AS1 [AS2 AS3]~{2,3}$", "AS1 AS3", false)]
+    #[case("^AS1 (AS2)~{0}$", "AS1", true)]
+    #[case("^.~* .~*$", "AS1 AS1 AS2 AS2", true)]
+    #[case("^.~* .~*$", "AS1 AS2 AS1", false)]
+    fn is_match(#[case] regex: Regex, #[case] path_: &str, #[case] expected: bool) {
+        assert_eq!(regex.is_match(&path(path_), &customers), expected);
+    }
+
+    #[rstest]
+    #[case("AS1 (AS2", 8)]
+    #[case("AS1 [AS2", 8)]
+    #[case("AS1 AS2{3,2}", 8)]
+    #[case("AS1 AS2{256}", 8)]
+    #[case("AS1 FOO", 4)]
+    #[case("AS1 [AS5 - AS3]", 5)]
+    #[case("(AS1 AS2)~*", 9)]
+    #[case("((.{200}){200}){20}", 0)]
+    #[case("(((.{255}){255}){255}){255}", 0)]
+    #[case("^AS1-AS5$", 1)]
+    #[case("^AS1 AS2-AS5", 5)]
+    fn invalid_is_expected_err(#[case] regex: &str, #[case] offset: usize) {
+        assert_eq!(regex.parse::<Regex>().unwrap_err().offset(), offset);
+    }
+
+    #[test]
+    fn deeply_nested_is_err() {
+        let nested = |depth| format!("{}AS1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(nested(64).parse::<Regex>().is_ok());
+        assert_eq!(nested(65).parse::<Regex>().unwrap_err().offset(), 65);
+        assert!(nested(10_000).parse::<Regex>().is_err());
+    }
+
+    #[test]
+    fn as_sets() {
+        let regex: Regex = "^AS-FOO [AS1 AS-BAR]* AS2:AS-BAZ~+ as-foo$"
+            .parse()
+            .unwrap();
+        let names: Vec<_> = regex.as_sets().iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, vec!["AS-FOO", "AS-BAR", "AS2:AS-BAZ"]);
+    }
+
+    #[test]
+    fn display() {
+        let regex: Regex = "  ^AS1  AS2* $ ".parse().unwrap();
+        assert_eq!(regex.to_string(), "^AS1  AS2* $");
+        assert_eq!(regex, "^AS1 AS2*$".parse().unwrap());
+    }
+
+    #[test]
+    fn resolved_as_sets() {
+        let objects = parse_whois_response(
+            "
+as-set:         AS-FOO
+members:        AS1, AS-BAR, AS-MISSING
+
+as-set:         AS-BAR
+members:        AS2, AS-FOO
+
+",
+        )
+        .unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        let regex: Regex = "^as-foo+$".parse().unwrap();
+        let sets = ResolvedAsSets::new(&regex, &resolver);
+        assert_eq!(sets.unresolved(), ["AS-MISSING".parse().unwrap()]);
+        assert!(regex.is_match(&[1, 2, 2], &sets));
+        assert!(!regex.is_match(&[1, 3], &sets));
+    }
+}
@@ -1,9 +1,20 @@
-use std::{borrow::Cow, convert::Infallible, fmt, marker::PhantomData, ops::Deref, str::FromStr};
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Deref,
+    str::FromStr,
+};
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::spec::{AttributeError, Raw, Specification};
+use crate::{
+    format::Formatter,
+    spec::{AttributeError, Raw, Specification},
+};
 
 /// An attribute of an [`Object`](crate::Object).
 ///
@@ -18,7 +29,7 @@ use crate::spec::{AttributeError, Raw, Specification};
 /// assert_eq!(object[0], attribute);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize), serde(bound = ""))]
 pub struct Attribute<'a, Spec: Specification = Raw> {
     /// The name of the attribute.
@@ -47,11 +58,7 @@ impl<'a, Spec: Specification> Attribute<'a, Spec> {
     /// # Errors
     /// Returns an [`AttributeError`] if the attribute does not conform to the target specification.
     pub fn validate<TargetSpec: Specification>(&self) -> Result<(), AttributeError> {
-        let candidate = Attribute {
-            name: self.name.clone().into_specification(),
-            value: self.value.clone().into_specification(),
-        };
-        TargetSpec::validate_attribute(&candidate)
+        TargetSpec::validate_attribute(&self.as_specification())
     }
 
     /// Convert the attribute into a target specification.
@@ -61,14 +68,27 @@ impl<'a, Spec: Specification> Attribute<'a, Spec> {
     pub fn into_spec<TargetSpec: Specification>(
         self,
     ) -> Result<Attribute<'a, TargetSpec>, AttributeError> {
-        let candidate = Attribute {
-            name: self.name.into_specification(),
-            value: self.value.into_specification(),
-        };
+        let candidate = self.into_specification();
         TargetSpec::validate_attribute(&candidate)?;
         Ok(candidate)
     }
 
+    /// Convert the attribute into a target specification without validating it.
+    pub(crate) fn into_specification<TargetSpec: Specification>(self) -> Attribute<'a, TargetSpec> {
+        Attribute {
+            name: self.name.into_specification(),
+            value: self.value.into_specification(),
+        }
+    }
+
+    /// Borrow the attribute as a target specification without validating or copying it.
+    pub(crate) fn as_specification<TargetSpec: Specification>(&self) -> Attribute<'_, TargetSpec> {
+        Attribute {
+            name: self.name.as_specification(),
+            value: self.value.as_specification(),
+        }
+    }
+
     /// Convert this attribute into an owned (`'static`) variant.
     #[must_use]
     pub fn into_owned(self) -> Attribute<'static, Spec> {
@@ -113,36 +133,55 @@ impl<'a> Attribute<'a, Raw> {
     }
 }
 
-impl<Spec: Specification> fmt::Display for Attribute<'_, Spec> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let values = self.value.values();
+impl<Spec: Specification, OtherSpec: Specification> PartialEq<Attribute<'_, OtherSpec>>
+    for Attribute<'_, Spec>
+{
+    /// Compare two attributes by name and value, regardless of their specification.
+    fn eq(&self, other: &Attribute<'_, OtherSpec>) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
 
-        let first_value = values.first().expect("must contain at least one value");
-        match first_value {
-            Some(value) => {
-                writeln!(f, "{:16}{}", format!("{}:", self.name), value)?;
-            }
-            None => writeln!(f, "{}:", self.name)?,
-        }
+impl<Spec: Specification> Eq for Attribute<'_, Spec> {}
 
-        let remaining_values = &values[1..];
-        for value in remaining_values {
-            match value {
-                Some(value) => {
-                    writeln!(f, "{:16}{}", " ", value)?;
-                }
-                None => {
-                    writeln!(f, " ")?;
-                }
-            }
+impl<Spec: Specification> Hash for Attribute<'_, Spec> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.value.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de: 'a, 'a> Deserialize<'de> for Attribute<'a, Raw> {
+    /// Deserialize an attribute, borrowing its name and value from the input where possible.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Attribute")]
+        struct Repr<'a> {
+            #[serde(borrow)]
+            name: Name<'a>,
+            #[serde(borrow, rename = "values")]
+            value: Value<'a>,
         }
 
-        Ok(())
+        let Repr { name, value } = Repr::deserialize(deserializer)?;
+        Ok(Self { name, value })
+    }
+}
+
+impl<Spec: Specification> fmt::Display for Attribute<'_, Spec> {
+    /// Display the attribute as RPSL using the default [`FormatOptions`](crate::format::FormatOptions).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Formatter::default().write_attribute(f, self)
     }
 }
 
 /// The name of an [`Attribute`].
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
 pub struct Name<'a, Spec: Specification = Raw> {
     inner: Cow<'a, str>,
@@ -182,6 +221,14 @@ impl<'a, Spec: Specification> Name<'a, Spec> {
         }
     }
 
+    /// Borrow this name as a different specification.
+    fn as_specification<TargetSpec: Specification>(&self) -> Name<'_, TargetSpec> {
+        Name {
+            inner: Cow::Borrowed(&self.inner),
+            _spec: PhantomData,
+        }
+    }
+
     /// Convert this name into the [`Raw`] specification.
     pub(crate) fn into_raw(self) -> Name<'a> {
         self.into_specification::<Raw>()
@@ -218,6 +265,22 @@ impl From<String> for Name<'static, Raw> {
     }
 }
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de: 'a, 'a> Deserialize<'de> for Name<'a, Raw> {
+    /// Deserialize a name, borrowing from the input where possible.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let inner = deserializer.deserialize_str(CowStrVisitor)?;
+        Ok(Self {
+            inner,
+            _spec: PhantomData,
+        })
+    }
+}
+
 impl<Spec: Specification> Deref for Name<'_, Spec> {
     type Target = str;
 
@@ -226,6 +289,22 @@ impl<Spec: Specification> Deref for Name<'_, Spec> {
     }
 }
 
+impl<Spec: Specification, OtherSpec: Specification> PartialEq<Name<'_, OtherSpec>>
+    for Name<'_, Spec>
+{
+    fn eq(&self, other: &Name<'_, OtherSpec>) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<Spec: Specification> Eq for Name<'_, Spec> {}
+
+impl<Spec: Specification> Hash for Name<'_, Spec> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
 impl<Spec: Specification> PartialEq<&str> for Name<'_, Spec> {
     fn eq(&self, other: &&str) -> bool {
         self.inner == *other
@@ -241,7 +320,7 @@ impl<Spec: Specification> fmt::Display for Name<'_, Spec> {
 /// The value of an [`Attribute`].
 /// Since only some values contain multiple lines and single line values do not require
 /// additional heap allocation, an Enum is used to represent both variants.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Clone)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize),
@@ -318,7 +397,8 @@ impl<'a, Spec: Specification> Value<'a, Spec> {
         }
     }
 
-    fn values(&'a self) -> Vec<Option<&'a str>> {
+    /// All lines of the value, where empty lines are [`None`].
+    pub(crate) fn values(&self) -> Vec<Option<&str>> {
         match self {
             Value::SingleLine { inner, .. } => {
                 vec![inner.as_ref().map(std::convert::AsRef::as_ref)]
@@ -392,6 +472,23 @@ impl<'a, Spec: Specification> Value<'a, Spec> {
         }
     }
 
+    /// Borrow this value as a different specification.
+    fn as_specification<TargetSpec: Specification>(&self) -> Value<'_, TargetSpec> {
+        match self {
+            Value::SingleLine { inner, .. } => Value::SingleLine {
+                inner: inner.as_deref().map(Cow::Borrowed),
+                _spec: PhantomData,
+            },
+            Value::MultiLine { inner, .. } => Value::MultiLine {
+                inner: inner
+                    .iter()
+                    .map(|line| line.as_deref().map(Cow::Borrowed))
+                    .collect(),
+                _spec: PhantomData,
+            },
+        }
+    }
+
     /// Convert this value into the [`Raw`] specification.
     pub(crate) fn into_raw(self) -> Value<'a> {
         self.into_specification::<Raw>()
@@ -534,6 +631,105 @@ impl<'a, Spec: Specification> From<Value<'a, Spec>> for Vec<Option<String>> {
     }
 }
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a, Raw> {
+    /// Deserialize a value from a sequence of lines, borrowing from the input where possible.
+    ///
+    /// A sequence containing a single line results in a single line value, while empty lines
+    /// are coerced to [`None`] just like when creating a value.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Line<'a>(
+            #[serde(borrow, deserialize_with = "deserialize_line")] Option<Cow<'a, str>>,
+        );
+
+        fn deserialize_line<'de: 'a, 'a, D>(
+            deserializer: D,
+        ) -> Result<Option<Cow<'a, str>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct LineVisitor;
+
+            impl<'de> de::Visitor<'de> for LineVisitor {
+                type Value = Option<Cow<'de, str>>;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("an optional string")
+                }
+
+                fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                    Ok(None)
+                }
+
+                fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                    Ok(None)
+                }
+
+                fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+                    d.deserialize_str(CowStrVisitor).map(coerce_empty_value)
+                }
+            }
+
+            deserializer.deserialize_option(LineVisitor)
+        }
+
+        let mut lines: Vec<Option<Cow<'a, str>>> = Vec::<Line<'a>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|line| line.0)
+            .collect();
+
+        match lines.len() {
+            0 => Ok(Self::SingleLine {
+                inner: None,
+                _spec: PhantomData,
+            }),
+            1 => Ok(Self::SingleLine {
+                inner: lines.pop().expect("contains a single line"),
+                _spec: PhantomData,
+            }),
+            _ => Ok(Self::MultiLine {
+                inner: lines,
+                _spec: PhantomData,
+            }),
+        }
+    }
+}
+
+impl<Spec: Specification, OtherSpec: Specification> PartialEq<Value<'_, OtherSpec>>
+    for Value<'_, Spec>
+{
+    fn eq(&self, other: &Value<'_, OtherSpec>) -> bool {
+        match (self, other) {
+            (Self::SingleLine { inner: s, .. }, Value::SingleLine { inner: o, .. }) => s == o,
+            (Self::MultiLine { inner: s, .. }, Value::MultiLine { inner: o, .. }) => s == o,
+            _ => false,
+        }
+    }
+}
+
+impl<Spec: Specification> Eq for Value<'_, Spec> {}
+
+impl<Spec: Specification> Hash for Value<'_, Spec> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::SingleLine { inner, .. } => {
+                0u8.hash(state);
+                inner.hash(state);
+            }
+            Self::MultiLine { inner, .. } => {
+                1u8.hash(state);
+                inner.hash(state);
+            }
+        }
+    }
+}
+
 impl<Spec: Specification> PartialEq<&str> for Value<'_, Spec> {
     fn eq(&self, other: &&str) -> bool {
         match &self {
@@ -588,6 +784,31 @@ impl<Spec: Specification> PartialEq<Vec<Option<&str>>> for Value<'_, Spec> {
     }
 }
 
+/// Visits a string, borrowing it from the input where possible.
+#[cfg(feature = "serde")]
+struct CowStrVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for CowStrVisitor {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(v))
+    }
+}
+
 /// Coerce an empty value to `None`.
 fn coerce_empty_value<S>(value: S) -> Option<S>
 where
@@ -604,7 +825,7 @@ where
 mod tests {
     use rstest::*;
     #[cfg(feature = "serde")]
-    use serde_test::{assert_ser_tokens, Token};
+    use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
 
     use super::*;
 
@@ -712,6 +933,47 @@ mod tests {
         assert_ser_tokens(&attribute, expected);
     }
 
+    #[rstest]
+    #[case(
+        Attribute::unchecked_single("ASNumber", "32934"),
+        &[
+            Token::Struct { name: "Attribute", len: 2 },
+            Token::Str("name"),
+            Token::Str("ASNumber"),
+            Token::Str("values"),
+            Token::Seq { len: Some(1) },
+            Token::Some,
+            Token::Str("32934"),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    )]
+    #[case(
+        Attribute::unchecked_multi(
+            "address",
+            [Some("Packet Street 6"), None, Some("Internet")]
+        ),
+        &[
+            Token::Struct { name: "Attribute", len: 2 },
+            Token::Str("name"),
+            Token::Str("address"),
+            Token::Str("values"),
+            Token::Seq { len: Some(3) },
+            Token::Some,
+            Token::Str("Packet Street 6"),
+            Token::None,
+            Token::Some,
+            Token::Str("Internet"),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    )]
+    #[cfg(feature = "serde")]
+    /// Attributes deserialize from the same shape they are serialized to.
+    fn attribute_serde_roundtrip(#[case] attribute: Attribute, #[case] tokens: &[Token]) {
+        assert_tokens(&attribute, tokens);
+    }
+
     #[rstest]
     #[case("Ref", Name { inner: Cow::Owned("Ref".to_string()), _spec: PhantomData })]
     fn name_new<S: Into<String>>(#[case] s: S, #[case] expected: Name<Raw>) {
@@ -747,6 +1009,22 @@ mod tests {
         assert_ser_tokens(&name, &[expected]);
     }
 
+    #[test]
+    #[cfg(feature = "json")]
+    /// Names and values deserialized from borrowed input borrow from it instead of allocating.
+    fn attribute_deserialize_borrows() {
+        let json = r#"{"name": "ASNumber", "values": ["32934"]}"#;
+        let attribute: Attribute = serde_json::from_str(json).unwrap();
+        assert!(matches!(attribute.name.inner, Cow::Borrowed("ASNumber")));
+        assert!(matches!(
+            attribute.value,
+            Value::SingleLine {
+                inner: Some(Cow::Borrowed("32934")),
+                ..
+            }
+        ));
+    }
+
     #[rstest]
     #[case(
         "This is a valid attribute value",
@@ -789,7 +1067,7 @@ mod tests {
 
     #[rstest]
     fn value_from_empty_str(#[values("", "   ")] s: &str) {
-        let expected = Value::SingleLine {
+        let expected: Value = Value::SingleLine {
             inner: None,
             _spec: PhantomData,
         };
@@ -832,6 +1110,49 @@ mod tests {
         assert_ser_tokens(&value, expected);
     }
 
+    #[rstest]
+    #[case(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Some,
+            Token::Str("32934"),
+            Token::SeqEnd,
+        ],
+        Value::new_single("32934"),
+    )]
+    #[case(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Some,
+            Token::Str("   "),
+            Token::SeqEnd,
+        ],
+        Value::new_single(""),
+    )]
+    #[case(
+        &[
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+        ],
+        Value::new_single(""),
+    )]
+    #[case(
+        &[
+            Token::Seq { len: Some(3) },
+            Token::Some,
+            Token::BorrowedStr("Packet Street 6"),
+            Token::None,
+            Token::Some,
+            Token::String("Internet"),
+            Token::SeqEnd,
+        ],
+        Value::new_multi(["Packet Street 6", "", "Internet"]),
+    )]
+    #[cfg(feature = "serde")]
+    fn value_deserialize(#[case] tokens: &[Token], #[case] expected: Value) {
+        assert_de_tokens(&expected, tokens);
+    }
+
     #[rstest]
     #[case(
         Value::new_single(""),
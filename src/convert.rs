@@ -0,0 +1,363 @@
+//! Conversions between typed models and [`Object`]s.
+//!
+//! Types implementing [`FromObject`] can be created from an object, while types implementing
+//! [`IntoObject`] can be turned back into one. Rather than implementing these traits by hand, they
+//! can be derived when the **derive** feature is enabled.
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{convert::{FromObject, IntoObject}, parse_object};
+//!
+//! #[derive(FromObject, IntoObject)]
+//! struct Route {
+//!     #[rpsl(required)]
+//!     route: String,
+//!     #[rpsl(required)]
+//!     origin: u32,
+//!     #[rpsl(name = "mnt-by", multiple, required)]
+//!     maintainers: Vec<String>,
+//!     descr: Option<String>,
+//! }
+//!
+//! let object = parse_object("
+//! route:          193.254.30.0/24
+//! origin:         12312
+//! mnt-by:         MAINT-AS3257
+//! mnt-by:         MAINT-AS12312
+//!
+//! ")?;
+//! let route = Route::from_object(&object)?;
+//! assert_eq!(route.origin, 12312);
+//! assert_eq!(route.maintainers, vec!["MAINT-AS3257", "MAINT-AS12312"]);
+//! assert_eq!(route.into_object(), object);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+//!
+//! ## Derive attributes
+//!
+//! Each field maps onto the attribute named after it, with underscores replaced by hyphens.
+//! The mapping can be customized using the `#[rpsl(...)]` attribute.
+//!
+//! - `name = "..."`: The name of the attribute the field maps onto.
+//! - `required`: The attribute must be present. Without `multiple`, the field is of type `T`,
+//!   otherwise at least one attribute must be present.
+//! - `multiple`: The attribute may be present more than once and the field is of type `Vec<T>`.
+//!
+//! A field that is neither `required` nor `multiple` is of type `Option<T>` and the attribute
+//! must not be present more than once.
+//!
+//! Values are parsed using [`FromStr`], joining values spanning multiple lines by spaces and
+//! ignoring comments, and written using [`Display`](fmt::Display), where newlines start a new line
+//! of the value.
+
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use rpsl_derive::{FromObject, IntoObject};
+
+use crate::{
+    spec::{AttributeError, InvalidValueError, Specification},
+    types::content,
+    Attribute, Name, Object, Value,
+};
+
+/// Create a type from an [`Object`].
+pub trait FromObject: Sized {
+    /// Create an instance of this type from the attributes of an object.
+    ///
+    /// # Errors
+    /// Returns a [`FromObjectError`] if the object does not contain the attributes required,
+    /// or their values are invalid.
+    fn from_object<Spec: Specification>(object: &Object<'_, Spec>)
+        -> Result<Self, FromObjectError>;
+}
+
+/// Turn a type into an [`Object`].
+pub trait IntoObject {
+    /// Create an object containing the attributes represented by this type.
+    fn into_object(self) -> Object<'static>;
+}
+
+/// An error that can occur when creating a type from an [`Object`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FromObjectError {
+    /// The object is of a different class than expected.
+    #[error("expected an object of class `{expected}`, found `{found}`")]
+    UnexpectedClass {
+        /// The expected class.
+        expected: Name<'static>,
+        /// The class of the object, the name of its first attribute.
+        found: Name<'static>,
+    },
+    /// A required attribute is not present.
+    #[error("missing required attribute `{0}`")]
+    MissingAttribute(Name<'static>),
+    /// An attribute that may only be present once is repeated.
+    #[error("attribute `{name}` at index {index} must not be repeated")]
+    RepeatedAttribute {
+        /// The name of the repeated attribute.
+        name: Name<'static>,
+        /// The index of the repeated attribute.
+        index: usize,
+    },
+    /// An attribute is invalid.
+    #[error("attribute at index {index} is invalid: {error}")]
+    InvalidAttribute {
+        /// The index of the invalid attribute.
+        index: usize,
+        /// Why the attribute is invalid.
+        error: AttributeError,
+    },
+}
+
+/// Get the parsed value of an attribute that must be present exactly once.
+///
+/// # Errors
+/// Returns a [`FromObjectError`] if the attribute is missing, repeated or cannot be parsed.
+pub fn required<T, Spec>(object: &Object<'_, Spec>, name: &str) -> Result<T, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    optional(object, name)?.ok_or_else(|| FromObjectError::MissingAttribute(Name::new(name)))
+}
+
+/// Get the parsed value of an attribute that may be present at most once.
+///
+/// # Errors
+/// Returns a [`FromObjectError`] if the attribute is repeated or cannot be parsed.
+pub fn optional<T, Spec>(
+    object: &Object<'_, Spec>,
+    name: &str,
+) -> Result<Option<T>, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    let mut found = named(object, name);
+    let first = found.next();
+    if let Some((index, _)) = found.next() {
+        return Err(FromObjectError::RepeatedAttribute {
+            name: Name::new(name),
+            index,
+        });
+    }
+    first.map(|(index, value)| parse(index, value)).transpose()
+}
+
+/// Get the parsed values of an attribute that may be present any number of times.
+///
+/// # Errors
+/// Returns a [`FromObjectError`] if any of the values cannot be parsed.
+pub fn multiple<T, Spec>(object: &Object<'_, Spec>, name: &str) -> Result<Vec<T>, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    named(object, name)
+        .map(|(index, value)| parse(index, value))
+        .collect()
+}
+
+/// Get the parsed items of an attribute containing a comma separated list.
+///
+/// The attribute may be present any number of times, the items of all attributes are returned
+/// in order.
+///
+/// # Errors
+/// Returns a [`FromObjectError`] if any of the items cannot be parsed.
+pub fn list<T, Spec>(object: &Object<'_, Spec>, name: &str) -> Result<Vec<T>, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    let mut items = Vec::new();
+    for (index, value) in named(object, name) {
+        for item in content(value).split(',').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            items.push(item.parse().map_err(|e: T::Err| invalid(index, value, e))?);
+        }
+    }
+    Ok(items)
+}
+
+/// Get the parsed values of an attribute that must be present at least once.
+///
+/// # Errors
+/// Returns a [`FromObjectError`] if the attribute is missing or any of the values cannot be parsed.
+pub fn required_multiple<T, Spec>(
+    object: &Object<'_, Spec>,
+    name: &str,
+) -> Result<Vec<T>, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    let values = multiple(object, name)?;
+    if values.is_empty() {
+        return Err(FromObjectError::MissingAttribute(Name::new(name)));
+    }
+    Ok(values)
+}
+
+/// Create an attribute from a value implementing [`Display`](fmt::Display).
+///
+/// Newlines within the displayed value start a new line of the attribute value.
+pub fn attribute<T>(name: &str, value: &T) -> Attribute<'static>
+where
+    T: fmt::Display + ?Sized,
+{
+    let value: Value = value.to_string().split('\n').collect::<Vec<_>>().into();
+    Attribute::new(Name::new(name), value)
+}
+
+/// The values of all attributes with the given name, together with their index.
+fn named<'o, 'a, Spec: Specification>(
+    object: &'o Object<'a, Spec>,
+    name: &'o str,
+) -> impl Iterator<Item = (usize, &'o Value<'a, Spec>)> {
+    object
+        .iter()
+        .enumerate()
+        .filter(move |(_, attribute)| attribute.name == name)
+        .map(|(index, attribute)| (index, &attribute.value))
+}
+
+fn parse<T, Spec>(index: usize, value: &Value<'_, Spec>) -> Result<T, FromObjectError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    Spec: Specification,
+{
+    content(value)
+        .parse()
+        .map_err(|e: T::Err| invalid(index, value, e))
+}
+
+fn invalid<Spec: Specification>(
+    index: usize,
+    value: &Value<'_, Spec>,
+    error: impl fmt::Display,
+) -> FromObjectError {
+    FromObjectError::InvalidAttribute {
+        index,
+        error: InvalidValueError::new(value, error.to_string()).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    #[test]
+    fn required_present_is_parsed() {
+        let object = object! {
+            "inet-rtr": "rtr.example.net";
+            "ifaddr": "192.0.2.1";
+        };
+        let ifaddr: IpAddr = required(&object, "ifaddr").unwrap();
+        assert_eq!(ifaddr, "192.0.2.1".parse::<IpAddr>().unwrap());
+    }
+
+    #[rstest]
+    #[case(
+        object! { "inet-rtr": "rtr.example.net"; },
+        FromObjectError::MissingAttribute(Name::new("ifaddr"))
+    )]
+    #[case(
+        object! {
+            "ifaddr": "192.0.2.1";
+            "inet-rtr": "rtr.example.net";
+            "ifaddr": "192.0.2.2";
+        },
+        FromObjectError::RepeatedAttribute { name: Name::new("ifaddr"), index: 2 }
+    )]
+    #[case(
+        object! {
+            "inet-rtr": "rtr.example.net";
+            "ifaddr": "192.0.2";
+        },
+        FromObjectError::InvalidAttribute {
+            index: 1,
+            error: InvalidValueError::new(
+                &Value::new_single("192.0.2"),
+                "invalid IP address syntax"
+            ).into()
+        }
+    )]
+    fn required_invalid_is_expected_err(#[case] object: Object, #[case] expected: FromObjectError) {
+        let err = required::<IpAddr, _>(&object, "ifaddr").unwrap_err();
+        assert_eq!(err, expected);
+    }
+
+    #[test]
+    fn optional_missing_is_none() {
+        let object = object! { "inet-rtr": "rtr.example.net"; };
+        assert_eq!(optional::<String, _>(&object, "descr").unwrap(), None);
+    }
+
+    #[test]
+    fn multiple_returns_values_in_order() {
+        let object = object! {
+            "mnt-by": "MAINT-AS3257";
+            "source": "RIPE";
+            "mnt-by": "MAINT-AS12312";
+        };
+        let maintainers: Vec<String> = multiple(&object, "mnt-by").unwrap();
+        assert_eq!(maintainers, vec!["MAINT-AS3257", "MAINT-AS12312"]);
+        assert_eq!(
+            required_multiple::<String, _>(&object, "admin-c").unwrap_err(),
+            FromObjectError::MissingAttribute(Name::new("admin-c"))
+        );
+    }
+
+    #[test]
+    fn comments_are_ignored() {
+        let object = object! {
+            "route": "192.0.2.0/24";
+            "descr": "ACME # Company", "# Comment only", "Packet Street 6";
+            "mnt-by": "MAINT-AS3257, MAINT-AS12312 # Maintainers";
+            "mnt-by": "MAINT-ACME";
+        };
+        assert_eq!(
+            required::<String, _>(&object, "descr").unwrap(),
+            "ACME Packet Street 6"
+        );
+        assert_eq!(
+            list::<String, _>(&object, "mnt-by").unwrap(),
+            vec!["MAINT-AS3257", "MAINT-AS12312", "MAINT-ACME"]
+        );
+    }
+
+    #[rstest]
+    #[case("descr", &"Single line", Attribute::new("descr", "Single line"))]
+    #[case(
+        "remarks",
+        &"First line\nSecond line",
+        Attribute::new("remarks", vec!["First line", "Second line"])
+    )]
+    fn attribute_from_display(
+        #[case] name: &str,
+        #[case] value: &dyn fmt::Display,
+        #[case] expected: Attribute,
+    ) {
+        assert_eq!(attribute(name, value), expected);
+    }
+}
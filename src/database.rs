@@ -0,0 +1,707 @@
+//! An in-memory database of objects, indexed like an Internet Routing Registry.
+//!
+//! An [`IrrDatabase`] stores objects by their class and primary key, see [`ObjectKey`], and
+//! maintains inverse indexes of the attributes referring to other objects, see [`Inverse`].
+//!
+//! Objects of the same class and primary key may be stored for multiple sources, identified by
+//! their `source` attribute. Lookups return the object of the source with the highest
+//! precedence, which is configured using [`IrrDatabase::set_source_precedence`].
+//!
+//! The address space of `route`, `route6`, `inetnum` and `inet6num` objects is indexed using a
+//! [`PrefixTrie`], such that objects can be looked up by prefix or range using
+//! [`IrrDatabase::lookup`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     database::{Inverse, IrrDatabase},
+//!     trie::Match,
+//!     parse_whois_response,
+//! };
+//!
+//! let objects = parse_whois_response(
+//!     "
+//! route:          192.0.2.0/24
+//! origin:         AS65536
+//! mnt-by:         MAINT-EXAMPLE
+//! source:         RADB
+//!
+//! route:          192.0.2.0/24
+//! origin:         AS65536
+//! mnt-by:         MAINT-AS65536
+//! source:         RIPE
+//!
+//! ",
+//! )?;
+//! let mut database: IrrDatabase = objects.into_iter().collect();
+//! database.set_source_precedence(["RIPE", "RADB"]);
+//!
+//! let route = database.get("route", "192.0.2.0/24AS65536").unwrap();
+//! assert_eq!(route.get("source"), ["RIPE"]);
+//! assert_eq!(database.inverse(Inverse::MntBy, "MAINT-AS65536").len(), 1);
+//! assert!(database.inverse(Inverse::MntBy, "MAINT-EXAMPLE").is_empty());
+//!
+//! let routes = database.lookup("route", &"192.0.2.0/25".parse()?, Match::LessSpecific);
+//! assert_eq!(routes, [route]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+use crate::{
+    resolve::ObjectResolver,
+    spec::{Raw, Specification},
+    trie::{AddressRange, Match, PrefixTrie},
+    types, Object,
+};
+
+/// The class and primary key identifying an object.
+///
+/// The class is the name of the first attribute of the object, the primary key is its value
+/// except for the following classes:
+/// - `route` and `route6` objects are identified by their prefix followed by their origin, e.g.
+///   `192.0.2.0/24AS65536`.
+/// - `person` and `role` objects are identified by their `nic-hdl`.
+///
+/// Both are case insensitive, the class is stored in lowercase and the key in uppercase.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectKey {
+    class: String,
+    key: String,
+}
+
+impl ObjectKey {
+    /// Create the key of an object of the class with the primary key.
+    #[must_use]
+    pub fn new(class: &str, key: &str) -> Self {
+        Self {
+            class: class.trim().to_ascii_lowercase(),
+            key: key
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_ascii_uppercase(),
+        }
+    }
+
+    /// The key of the object, or `None` if it has no attributes.
+    #[must_use]
+    pub fn of<Spec: Specification>(object: &Object<'_, Spec>) -> Option<Self> {
+        let first = object.first()?;
+        let class = first.name.to_ascii_lowercase();
+        let value = |name: &str| {
+            object
+                .iter()
+                .find(|attribute| attribute.name.eq_ignore_ascii_case(name))
+                .map(|attribute| types::content(&attribute.value))
+        };
+        let key = match class.as_str() {
+            "route" | "route6" => {
+                types::content(&first.value) + &value("origin").unwrap_or_default()
+            }
+            "person" | "role" => value("nic-hdl").unwrap_or_else(|| types::content(&first.value)),
+            _ => types::content(&first.value),
+        };
+        Some(Self::new(&class, &key))
+    }
+
+    /// The class of the object in lowercase.
+    #[must_use]
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    /// The primary key of the object in uppercase.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl fmt::Display for ObjectKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.class, self.key)
+    }
+}
+
+/// An attribute referring to other objects, for which an inverse index is maintained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Inverse {
+    /// The maintainers of an object.
+    MntBy,
+    /// The administrative contacts of an object.
+    AdminC,
+    /// The technical contacts of an object.
+    TechC,
+    /// The AS originating a `route` or `route6` object.
+    Origin,
+    /// The sets an object claims membership of.
+    MemberOf,
+}
+
+impl Inverse {
+    /// All attributes with an inverse index.
+    pub const ALL: [Self; 5] = [
+        Self::MntBy,
+        Self::AdminC,
+        Self::TechC,
+        Self::Origin,
+        Self::MemberOf,
+    ];
+
+    /// The name of the attribute.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::MntBy => "mnt-by",
+            Self::AdminC => "admin-c",
+            Self::TechC => "tech-c",
+            Self::Origin => "origin",
+            Self::MemberOf => "member-of",
+        }
+    }
+}
+
+impl fmt::Display for Inverse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An in-memory database of objects, indexed by class, primary key and source.
+///
+/// Sources are case insensitive and stored in uppercase, objects without a `source` attribute
+/// are stored for the empty source. Sources listed in the precedence order take precedence over
+/// the sources not listed, which are ordered by name.
+///
+/// Lookups only return the object of the source with the highest precedence for each key, such
+/// that objects of other sources are shadowed rather than merged. Use
+/// [`get_all`](IrrDatabase::get_all) to access the objects of all sources.
+#[derive(Debug, Clone)]
+pub struct IrrDatabase<Spec: Specification = Raw> {
+    objects: BTreeMap<ObjectKey, HashMap<String, Object<'static, Spec>>>,
+    inverse: HashMap<(Inverse, String), BTreeSet<(ObjectKey, String)>>,
+    /// The keys of objects of classes describing address space, by class.
+    ranges: HashMap<String, PrefixTrie<ObjectKey>>,
+    precedence: Vec<String>,
+}
+
+impl<Spec: Specification> Default for IrrDatabase<Spec> {
+    fn default() -> Self {
+        Self {
+            objects: BTreeMap::new(),
+            inverse: HashMap::new(),
+            ranges: HashMap::new(),
+            precedence: Vec::new(),
+        }
+    }
+}
+
+impl<Spec: Specification> IrrDatabase<Spec> {
+    /// Create an empty database.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the sources in order of precedence, starting with the source of the highest
+    /// precedence.
+    pub fn set_source_precedence<I, S>(&mut self, sources: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.precedence = sources
+            .into_iter()
+            .map(|source| source.as_ref().trim().to_ascii_uppercase())
+            .collect();
+    }
+
+    /// The sources in order of precedence.
+    #[must_use]
+    pub fn source_precedence(&self) -> &[String] {
+        &self.precedence
+    }
+
+    /// Insert an object, returning the object of the same key and source it replaces.
+    ///
+    /// Objects without attributes are ignored.
+    pub fn insert(&mut self, object: Object<'_, Spec>) -> Option<Object<'static, Spec>> {
+        let key = ObjectKey::of(&object)?;
+        let source = source(&object);
+        let object = object.into_owned();
+        let replaced = self.remove_from(&key, &source);
+        for (inverse, value) in references(&object) {
+            self.inverse
+                .entry((inverse, value))
+                .or_default()
+                .insert((key.clone(), source.clone()));
+        }
+        if !self.objects.contains_key(&key) {
+            if let Some(range) = address_range(&object) {
+                self.ranges
+                    .entry(key.class.clone())
+                    .or_default()
+                    .insert(range, key.clone());
+            }
+        }
+        self.objects.entry(key).or_default().insert(source, object);
+        replaced
+    }
+
+    /// Remove the object of the key and source, returning it if it was stored.
+    pub fn remove_from(&mut self, key: &ObjectKey, source: &str) -> Option<Object<'static, Spec>> {
+        let source = source.trim().to_ascii_uppercase();
+        let sources = self.objects.get_mut(key)?;
+        let object = sources.remove(&source)?;
+        if sources.is_empty() {
+            self.objects.remove(key);
+            if let (Some(range), Some(trie)) =
+                (address_range(&object), self.ranges.get_mut(&key.class))
+            {
+                trie.remove(&range, key);
+            }
+        }
+        let entry = (key.clone(), source);
+        for (inverse, value) in references(&object) {
+            let index = (inverse, value);
+            if let Some(entries) = self.inverse.get_mut(&index) {
+                entries.remove(&entry);
+                if entries.is_empty() {
+                    self.inverse.remove(&index);
+                }
+            }
+        }
+        Some(object)
+    }
+
+    /// The object of the class and primary key from the source with the highest precedence.
+    #[must_use]
+    pub fn get(&self, class: &str, key: &str) -> Option<&Object<'static, Spec>> {
+        self.get_key(&ObjectKey::new(class, key))
+    }
+
+    /// The object of the key from the source with the highest precedence.
+    #[must_use]
+    pub fn get_key(&self, key: &ObjectKey) -> Option<&Object<'static, Spec>> {
+        let sources = self.objects.get(key)?;
+        let source = self.preferred(sources)?;
+        sources.get(source)
+    }
+
+    /// The objects of the key from all sources, in order of precedence.
+    #[must_use]
+    pub fn get_all(&self, key: &ObjectKey) -> Vec<(&str, &Object<'static, Spec>)> {
+        let Some(sources) = self.objects.get(key) else {
+            return Vec::new();
+        };
+        let mut objects: Vec<_> = sources
+            .iter()
+            .map(|(source, object)| (source.as_str(), object))
+            .collect();
+        objects.sort_by_key(|(source, _)| self.rank(source));
+        objects
+    }
+
+    /// The objects referring to the value using the attribute, ordered by key.
+    ///
+    /// Values are compared case insensitively, e.g. `maint-example` finds the objects
+    /// maintained by `MAINT-EXAMPLE`.
+    #[must_use]
+    pub fn inverse(&self, inverse: Inverse, value: &str) -> Vec<&Object<'static, Spec>> {
+        let Some(entries) = self
+            .inverse
+            .get(&(inverse, value.trim().to_ascii_uppercase()))
+        else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .filter_map(|(key, source)| {
+                let sources = self.objects.get(key)?;
+                (self.preferred(sources)? == source).then(|| &sources[source])
+            })
+            .collect()
+    }
+
+    /// The objects of the class whose address space matches the range in the query mode.
+    ///
+    /// Only `route`, `route6`, `inetnum` and `inet6num` objects are indexed by their address
+    /// space, see [`PrefixTrie::lookup`] for the order of the objects.
+    #[must_use]
+    pub fn lookup(
+        &self,
+        class: &str,
+        range: &AddressRange,
+        mode: Match,
+    ) -> Vec<&Object<'static, Spec>> {
+        let Some(trie) = self.ranges.get(&class.trim().to_ascii_lowercase()) else {
+            return Vec::new();
+        };
+        trie.lookup(range, mode)
+            .into_iter()
+            .filter_map(|(_, key)| self.get_key(key))
+            .collect()
+    }
+
+    /// The objects of the class, ordered by key.
+    pub fn class<'d>(
+        &'d self,
+        class: &str,
+    ) -> impl Iterator<Item = &'d Object<'static, Spec>> + 'd {
+        let start = ObjectKey::new(class, "");
+        self.objects
+            .range(start.clone()..)
+            .take_while(move |(key, _)| key.class == start.class)
+            .filter_map(|(_, sources)| sources.get(self.preferred(sources)?))
+    }
+
+    /// All objects, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = &Object<'static, Spec>> + '_ {
+        self.objects
+            .values()
+            .filter_map(|sources| sources.get(self.preferred(sources)?))
+    }
+
+    /// The number of distinct keys, regardless of the number of sources storing them.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Whether the database contains no objects.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// A resolver for the references of filters using the objects of the database.
+    #[must_use]
+    pub fn resolver(&self) -> ObjectResolver {
+        self.iter().collect()
+    }
+
+    /// The source of the highest precedence of those storing an object.
+    fn preferred<'s>(
+        &self,
+        sources: &'s HashMap<String, Object<'static, Spec>>,
+    ) -> Option<&'s str> {
+        sources
+            .keys()
+            .min_by_key(|source| self.rank(source))
+            .map(String::as_str)
+    }
+
+    /// The order of a source, sorting listed sources by precedence before the others by name.
+    fn rank<'s>(&self, source: &'s str) -> (usize, &'s str) {
+        let position = self.precedence.iter().position(|listed| listed == source);
+        (position.unwrap_or(self.precedence.len()), source)
+    }
+}
+
+impl<'a, Spec: Specification> FromIterator<Object<'a, Spec>> for IrrDatabase<Spec> {
+    fn from_iter<I: IntoIterator<Item = Object<'a, Spec>>>(objects: I) -> Self {
+        let mut database = Self::new();
+        database.extend(objects);
+        database
+    }
+}
+
+impl<'a, Spec: Specification> Extend<Object<'a, Spec>> for IrrDatabase<Spec> {
+    fn extend<I: IntoIterator<Item = Object<'a, Spec>>>(&mut self, objects: I) {
+        for object in objects {
+            self.insert(object);
+        }
+    }
+}
+
+/// The source of the object in uppercase, or an empty string if it has none.
+fn source<Spec: Specification>(object: &Object<'_, Spec>) -> String {
+    object
+        .iter()
+        .find(|attribute| attribute.name.eq_ignore_ascii_case("source"))
+        .map(|attribute| types::content(&attribute.value).to_ascii_uppercase())
+        .unwrap_or_default()
+}
+
+/// The address space of `route`, `route6`, `inetnum` and `inet6num` objects.
+fn address_range<Spec: Specification>(object: &Object<'_, Spec>) -> Option<AddressRange> {
+    let first = object.first()?;
+    match first.name.to_ascii_lowercase().as_str() {
+        "route" | "route6" | "inetnum" | "inet6num" => types::content(&first.value).parse().ok(),
+        _ => None,
+    }
+}
+
+/// The values of the attributes with an inverse index, splitting lists into their items.
+fn references<Spec: Specification>(object: &Object<'_, Spec>) -> Vec<(Inverse, String)> {
+    let mut references = Vec::new();
+    for inverse in Inverse::ALL {
+        for attribute in object
+            .iter()
+            .filter(|attribute| attribute.name.eq_ignore_ascii_case(inverse.name()))
+        {
+            for item in types::content(&attribute.value).split(',') {
+                let reference = (inverse, item.trim().to_ascii_uppercase());
+                if !reference.1.is_empty() && !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_object, parse_whois_response};
+
+    const OBJECTS: &str = "
+person:         John Doe
+nic-hdl:        JD1-RIPE
+mnt-by:         MAINT-FOO
+source:         RIPE
+
+aut-num:        AS65536
+as-name:        EXAMPLE
+member-of:      AS-FOO
+admin-c:        JD1-RIPE
+tech-c:         JD1-RIPE, JD2-RIPE
+mnt-by:         MAINT-FOO
+source:         RIPE
+
+route:          192.0.2.0/24
+origin:         AS65536
+mnt-by:         MAINT-FOO
+source:         RIPE
+
+route:          192.0.2.0/24
+origin:         AS65536
+mnt-by:         MAINT-BAR
+source:         RADB
+
+route:          198.51.100.0/24
+origin:         AS65536
+mnt-by:         MAINT-BAR
+source:         RADB
+
+route6:         2001:db8::/32
+origin:         AS65537
+source:         RADB
+
+";
+
+    fn database() -> IrrDatabase {
+        parse_whois_response(OBJECTS).unwrap().into_iter().collect()
+    }
+
+    fn keys(objects: &[&Object<'static>]) -> Vec<String> {
+        objects
+            .iter()
+            .map(|object| ObjectKey::of(object).unwrap().to_string())
+            .collect()
+    }
+
+    #[rstest]
+    #[case(
+        "route:          192.0.2.0/24\norigin: AS65536\n\n",
+        "route: 192.0.2.0/24AS65536"
+    )]
+    #[case("person: John Doe\nnic-hdl: jd1-ripe\n\n", "person: JD1-RIPE")]
+    #[case(
+        "inetnum: 192.0.2.0  -   192.0.2.255 # Comment\n\n",
+        "inetnum: 192.0.2.0 - 192.0.2.255"
+    )]
+    #[case("mntner: maint-foo\n\n", "mntner: MAINT-FOO")]
+    #[case("ROUTE: 192.0.2.0/24\nORIGIN: AS1\n\n", "route: 192.0.2.0/24AS1")]
+    #[case("Person: John Doe\nNIC-HDL: jd1-ripe\n\n", "person: JD1-RIPE")]
+    fn object_key_of(#[case] object: &str, #[case] expected: &str) {
+        let object = parse_object(object).unwrap();
+        assert_eq!(ObjectKey::of(&object).unwrap().to_string(), expected);
+    }
+
+    #[test]
+    fn database_looks_up_objects() {
+        let database = database();
+
+        assert_eq!(database.len(), 5);
+        assert_eq!(
+            database.get("PERSON", "jd1-ripe").unwrap().get("person"),
+            ["John Doe"]
+        );
+        assert!(database.get("person", "JD2-RIPE").is_none());
+        assert_eq!(
+            database
+                .class("route")
+                .map(|object| object.get("route")[0])
+                .collect::<Vec<_>>(),
+            ["192.0.2.0/24", "198.51.100.0/24"]
+        );
+        assert_eq!(database.iter().count(), 5);
+    }
+
+    #[test]
+    fn database_applies_source_precedence() {
+        let mut database = database();
+        let key = ObjectKey::new("route", "192.0.2.0/24AS65536");
+
+        assert_eq!(database.get_key(&key).unwrap().get("source"), ["RADB"]);
+        assert_eq!(
+            keys(&database.inverse(Inverse::MntBy, "MAINT-FOO")).len(),
+            2
+        );
+
+        database.set_source_precedence(["ripe"]);
+        assert_eq!(database.get_key(&key).unwrap().get("source"), ["RIPE"]);
+        assert_eq!(
+            database
+                .get_all(&key)
+                .into_iter()
+                .map(|(source, _)| source)
+                .collect::<Vec<_>>(),
+            ["RIPE", "RADB"]
+        );
+        assert_eq!(
+            keys(&database.inverse(Inverse::MntBy, "maint-bar")),
+            ["route: 198.51.100.0/24AS65536"]
+        );
+        assert_eq!(
+            keys(&database.inverse(Inverse::MntBy, "MAINT-FOO")).len(),
+            3
+        );
+    }
+
+    #[rstest]
+    #[case(Inverse::AdminC, "JD1-RIPE", &["aut-num: AS65536"])]
+    #[case(Inverse::TechC, "jd2-ripe", &["aut-num: AS65536"])]
+    #[case(Inverse::MemberOf, "AS-FOO", &["aut-num: AS65536"])]
+    #[case(
+        Inverse::Origin,
+        "AS65536",
+        &["route: 192.0.2.0/24AS65536", "route: 198.51.100.0/24AS65536"]
+    )]
+    #[case(Inverse::Origin, "AS65537", &["route6: 2001:DB8::/32AS65537"])]
+    #[case(Inverse::MntBy, "MAINT-BAZ", &[])]
+    fn database_inverse_lookups(
+        #[case] inverse: Inverse,
+        #[case] value: &str,
+        #[case] expected: &[&str],
+    ) {
+        assert_eq!(keys(&database().inverse(inverse, value)), expected);
+    }
+
+    #[test]
+    fn database_ignores_case_of_attribute_names() {
+        let object = parse_object("AUT-NUM: AS65536\nMNT-BY: MAINT-FOO\nSOURCE: RIPE\n\n").unwrap();
+        let database: IrrDatabase = [object].into_iter().collect();
+        assert_eq!(
+            keys(&database.inverse(Inverse::MntBy, "maint-foo")),
+            ["aut-num: AS65536"]
+        );
+        assert_eq!(
+            database
+                .get_all(&ObjectKey::new("aut-num", "AS65536"))
+                .into_iter()
+                .map(|(source, _)| source)
+                .collect::<Vec<_>>(),
+            ["RIPE"]
+        );
+    }
+
+    #[test]
+    fn database_looks_up_address_space() {
+        let mut database = database();
+        let objects = parse_whois_response(
+            "
+inetnum:        192.0.2.0 - 192.0.2.255
+source:         RIPE
+
+INETNUM:        192.0.2.0 - 192.0.2.99
+source:         RIPE
+
+ROUTE:          192.0.2.128/25
+ORIGIN:         AS65538
+source:         RIPE
+
+inet6num:       2001:db8::/32
+source:         RIPE
+
+",
+        )
+        .unwrap();
+        database.extend(objects);
+        let lookup = |database: &IrrDatabase, class, range: &str, mode| {
+            keys(&database.lookup(class, &range.parse().unwrap(), mode))
+        };
+
+        assert_eq!(
+            lookup(&database, "route", "192.0.2.0/25", Match::LessSpecific),
+            ["route: 192.0.2.0/24AS65536"]
+        );
+        assert_eq!(
+            lookup(&database, "route", "192.0.2.128/25", Match::Exact),
+            ["route: 192.0.2.128/25AS65538"]
+        );
+        assert_eq!(
+            lookup(&database, "inetnum", "192.0.2.0/24", Match::MoreSpecific),
+            ["inetnum: 192.0.2.0 - 192.0.2.99"]
+        );
+        assert_eq!(
+            lookup(&database, "INETNUM", "192.0.2.0/24", Match::Exact),
+            ["inetnum: 192.0.2.0 - 192.0.2.255"]
+        );
+        assert_eq!(
+            lookup(
+                &database,
+                "inet6num",
+                "2001:db8::/48",
+                Match::AllLessSpecific
+            ),
+            ["inet6num: 2001:DB8::/32"]
+        );
+        assert!(lookup(&database, "aut-num", "0.0.0.0/0", Match::AllMoreSpecific).is_empty());
+
+        let key = ObjectKey::new("route", "192.0.2.0/24AS65536");
+        database.remove_from(&key, "RIPE");
+        assert_eq!(
+            lookup(&database, "route", "192.0.2.0/24", Match::Exact),
+            ["route: 192.0.2.0/24AS65536"]
+        );
+        database.remove_from(&key, "RADB");
+        assert!(lookup(&database, "route", "192.0.2.0/24", Match::Exact).is_empty());
+    }
+
+    #[test]
+    fn database_replaces_and_removes_objects() {
+        let mut database = database();
+        let object = parse_object(
+            "
+aut-num:        AS65536
+as-name:        EXAMPLE
+mnt-by:         MAINT-BAZ
+source:         RIPE
+
+",
+        )
+        .unwrap();
+
+        let replaced = database.insert(object).unwrap();
+        assert_eq!(replaced.get("member-of"), ["AS-FOO"]);
+        assert!(database.inverse(Inverse::MemberOf, "AS-FOO").is_empty());
+        assert_eq!(database.inverse(Inverse::MntBy, "MAINT-BAZ").len(), 1);
+
+        let key = ObjectKey::new("aut-num", "AS65536");
+        assert!(database.remove_from(&key, "radb").is_none());
+        assert!(database.remove_from(&key, "ripe").is_some());
+        assert!(database.get_key(&key).is_none());
+        assert!(database.inverse(Inverse::MntBy, "MAINT-BAZ").is_empty());
+        assert_eq!(database.len(), 4);
+    }
+}
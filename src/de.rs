@@ -0,0 +1,624 @@
+//! Deserialize user defined types from an [`Object`] using [Serde](https://serde.rs).
+//!
+//! Most applications only care about a handful of attributes per object class. Instead of
+//! looking up each attribute by name, a type implementing [`Deserialize`] can be created
+//! directly from an [`Object`] using [`from_object`].
+//!
+//! ```rust
+//! # use rpsl::{de::from_object, parse_object};
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Route {
+//!     route: String,
+//!     origin: String,
+//!     descr: Option<String>,
+//!     #[serde(rename = "mnt-by")]
+//!     mnt_by: Vec<String>,
+//! }
+//!
+//! let object = parse_object("
+//! route:          193.254.30.0/24
+//! origin:         AS12312
+//! mnt-by:         MAINT-AS3257
+//! mnt-by:         MAINT-AS12312
+//! source:         RIPE
+//!
+//! ")?;
+//! let route: Route = from_object(&object)?;
+//! assert_eq!(route.route, "193.254.30.0/24");
+//! assert_eq!(route.origin, "AS12312");
+//! assert_eq!(route.descr, None);
+//! assert_eq!(route.mnt_by, vec!["MAINT-AS3257", "MAINT-AS12312"]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Mapping
+//!
+//! Each field is looked up by the attribute name it is deserialized from, which is either the
+//! field name or the name given using `#[serde(rename = "...")]`. Attributes that are not part of
+//! the type are ignored, unless the type uses `#[serde(deny_unknown_fields)]`.
+//!
+//! - A scalar, like a [`String`], an integer or any type deserialized from a string, requires its
+//!   attribute to be present exactly once. A value spanning multiple lines is joined into a single
+//!   line, separating each line by a space.
+//! - A sequence, like a [`Vec`], contains the lines of all attributes with a matching name,
+//!   in the same order as they would be returned by [`Object::get`].
+//! - An [`Option`] is [`None`] if the attribute is missing or empty.
+//!
+//! Since a missing attribute is treated like a missing field, sequences of attributes that may
+//! not be present should be annotated using `#[serde(default)]`.
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use serde::de::{
+    self,
+    value::{BorrowedStrDeserializer, SeqDeserializer, StringDeserializer},
+    Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+};
+
+use crate::{spec::Specification, Attribute, Object};
+
+/// Deserialize an instance of type `T` from an [`Object`].
+///
+/// # Errors
+/// Returns an [`Error`] if the attributes of the object cannot be mapped onto `T`.
+pub fn from_object<'de, T, Spec>(object: &'de Object<'_, Spec>) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+    Spec: Specification,
+{
+    T::deserialize(Deserializer::new(object))
+}
+
+/// A Serde [`Deserializer`](serde::Deserializer) that maps the attributes of an [`Object`] onto
+/// the fields of a struct or the entries of a map.
+#[derive(Debug)]
+pub struct Deserializer<'de, 'a, Spec: Specification> {
+    object: &'de Object<'a, Spec>,
+}
+
+impl<'de, 'a, Spec: Specification> Deserializer<'de, 'a, Spec> {
+    /// Create a deserializer for the given object.
+    #[must_use]
+    pub fn new(object: &'de Object<'a, Spec>) -> Self {
+        Self { object }
+    }
+}
+
+impl<'de, Spec: Specification> de::Deserializer<'de> for Deserializer<'de, '_, Spec> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut groups: Vec<(&'de str, Vec<&'de Attribute<'_, Spec>>)> = Vec::new();
+        for attribute in self.object.iter() {
+            match groups
+                .iter_mut()
+                .find(|(name, _)| *name == &*attribute.name)
+            {
+                Some((_, attributes)) => attributes.push(attribute),
+                None => groups.push((&attribute.name, vec![attribute])),
+            }
+        }
+
+        visitor.visit_map(AttributeMap {
+            groups: groups.into_iter(),
+            next: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+/// Provides the attributes of an object grouped by name.
+struct AttributeMap<'de, I> {
+    groups: I,
+    next: Option<AttributeGroup<'de>>,
+}
+
+impl<'de, 'a, I, Spec> MapAccess<'de> for AttributeMap<'de, I>
+where
+    I: Iterator<Item = (&'de str, Vec<&'de Attribute<'a, Spec>>)>,
+    Spec: Specification + 'a,
+    'a: 'de,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.groups.next() {
+            Some((name, attributes)) => {
+                self.next = Some(AttributeGroup {
+                    name,
+                    lines: attributes
+                        .iter()
+                        .map(|attribute| attribute.value.with_content())
+                        .collect(),
+                });
+                seed.deserialize(BorrowedStrDeserializer::new(name))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let group = self.next.take().ok_or(Error::ValueBeforeKey)?;
+        let name = group.name;
+        seed.deserialize(group).map_err(|error| Error::Attribute {
+            name: name.to_string(),
+            error: Box::new(error),
+        })
+    }
+}
+
+/// All attributes of an object with the same name, represented by the lines of their values.
+struct AttributeGroup<'de> {
+    name: &'de str,
+    /// The lines containing content, for each attribute.
+    lines: Vec<Vec<&'de str>>,
+}
+
+impl<'de> AttributeGroup<'de> {
+    /// The value of a single attribute, joining multiple lines using spaces.
+    fn scalar(self) -> Result<Scalar<'de>, Error> {
+        let [lines] = <[Vec<&str>; 1]>::try_from(self.lines)
+            .map_err(|lines| Error::ExpectedSingle(lines.len()))?;
+
+        let value = match lines.as_slice() {
+            [] => Cow::Borrowed(""),
+            [line] => Cow::Borrowed(*line),
+            lines => Cow::Owned(lines.join(" ")),
+        };
+        Ok(Scalar(value))
+    }
+
+    /// The lines of all attributes.
+    fn flatten(self) -> Vec<&'de str> {
+        self.lines.into_iter().flatten().collect()
+    }
+}
+
+macro_rules! forward_to_scalar {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.scalar()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for AttributeGroup<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.lines.len() == 1 && self.lines[0].len() <= 1 {
+            self.deserialize_str(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    forward_to_scalar! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.lines.iter().all(Vec::is_empty) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let lines = self.flatten().into_iter().map(|l| Scalar(Cow::Borrowed(l)));
+        let mut seq = SeqDeserializer::new(lines);
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::Map)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.scalar()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// A single line of text, parsed into the type requested.
+struct Scalar<'de>(Cow<'de, str>);
+
+impl Scalar<'_> {
+    fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.0.parse().map_err(|error: T::Err| Error::InvalidValue {
+            value: self.0.to_string(),
+            message: error.to_string(),
+        })
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Scalar<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Scalar<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Cow::Owned(s) => visitor.visit_string(s),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Cow::Borrowed(s) => {
+                BorrowedStrDeserializer::<Error>::new(s).deserialize_enum(name, variants, visitor)
+            }
+            Cow::Owned(s) => {
+                StringDeserializer::<Error>::new(s).deserialize_enum(name, variants, visitor)
+            }
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// An error that can occur when deserializing from an [`Object`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A custom error reported by the type being deserialized, e.g. a missing field.
+    #[error("{0}")]
+    Custom(String),
+    /// The attributes of a field cannot be deserialized.
+    #[error("`{name}`: {error}")]
+    Attribute {
+        /// The name of the attributes.
+        name: String,
+        /// Why the attributes cannot be deserialized.
+        error: Box<Error>,
+    },
+    /// A single attribute was expected, but the object contains a different number of them.
+    #[error("expected a single attribute, found {0}")]
+    ExpectedSingle(usize),
+    /// An attribute was requested to be deserialized into a map.
+    #[error("an attribute cannot be deserialized into a map")]
+    Map,
+    /// A value cannot be parsed into the type requested.
+    #[error("invalid value `{value}`: {message}")]
+    InvalidValue {
+        /// The value that cannot be parsed.
+        value: String,
+        /// Why the value cannot be parsed.
+        message: String,
+    },
+    /// A value was requested before its key.
+    #[error("value requested before key")]
+    ValueBeforeKey,
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, net::IpAddr};
+
+    use rstest::*;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{object, parse_object};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ipv4Route {
+        route: String,
+        origin: String,
+        #[serde(rename = "mnt-by")]
+        mnt_by: Vec<String>,
+        remarks: Option<String>,
+        #[serde(default, rename = "member-of")]
+        member_of: Vec<String>,
+    }
+
+    #[test]
+    fn deserialize_struct() {
+        let object = object! {
+            "route": "193.254.30.0/24";
+            "descr": "Not part of the struct";
+            "origin": "AS12312";
+            "mnt-by": "MAINT-AS3257";
+            "mnt-by": "MAINT-AS12312";
+            "source": "RIPE";
+        };
+        let route: Ipv4Route = from_object(&object).unwrap();
+        assert_eq!(
+            route,
+            Ipv4Route {
+                route: "193.254.30.0/24".to_string(),
+                origin: "AS12312".to_string(),
+                mnt_by: vec!["MAINT-AS3257".to_string(), "MAINT-AS12312".to_string()],
+                remarks: None,
+                member_of: vec![],
+            }
+        );
+    }
+
+    #[test]
+    /// Single line values are borrowed from the object.
+    fn deserialize_borrowed_str() {
+        #[derive(Deserialize)]
+        struct Role<'a> {
+            role: &'a str,
+        }
+
+        let object = parse_object("role:           ACME Company\n\n").unwrap();
+        let role: Role = from_object(&object).unwrap();
+        assert_eq!(role.role, "ACME Company");
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "remarks": "Locations", "LA1 - CoreSite One Wilshire", "NY1 - Equinix New York";
+        },
+        "Locations LA1 - CoreSite One Wilshire NY1 - Equinix New York"
+    )]
+    #[case(object! { "remarks": "Peering Policy"; }, "Peering Policy")]
+    /// Multi line values deserialize into a single line joined by spaces.
+    fn deserialize_multi_line_into_string(#[case] object: Object, #[case] expected: &str) {
+        #[derive(Deserialize)]
+        struct Remarks {
+            remarks: String,
+        }
+        let remarks: Remarks = from_object(&object).unwrap();
+        assert_eq!(remarks.remarks, expected);
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "address": "Packet Street 6", "", "128 Series of Tubes";
+            "address": "Internet";
+        },
+        vec!["Packet Street 6", "128 Series of Tubes", "Internet"]
+    )]
+    /// The lines of multi line values deserialize into a sequence.
+    fn deserialize_multi_line_into_vec(#[case] object: Object, #[case] expected: Vec<&str>) {
+        #[derive(Deserialize)]
+        struct Address {
+            address: Vec<String>,
+        }
+        let address: Address = from_object(&object).unwrap();
+        assert_eq!(address.address, expected);
+    }
+
+    #[test]
+    fn deserialize_parsed_types() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Status {
+            Assigned,
+            Allocated,
+        }
+
+        #[derive(Deserialize)]
+        struct Parsed {
+            #[serde(rename = "local-as")]
+            local_as: u32,
+            ifaddr: IpAddr,
+            status: Status,
+            #[serde(rename = "rpki-ov")]
+            rpki_ov: bool,
+        }
+
+        let object = object! {
+            "local-as": "3257";
+            "ifaddr": "192.0.2.1";
+            "status": "assigned";
+            "rpki-ov": "true";
+        };
+        let parsed: Parsed = from_object(&object).unwrap();
+        assert_eq!(parsed.local_as, 3257);
+        assert_eq!(parsed.ifaddr, "192.0.2.1".parse::<IpAddr>().unwrap());
+        assert_eq!(parsed.status, Status::Assigned);
+        assert!(parsed.rpki_ov);
+    }
+
+    #[test]
+    fn deserialize_map() {
+        let object = object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6";
+            "address": "Internet";
+        };
+        let map: HashMap<String, Vec<String>> = from_object(&object).unwrap();
+        assert_eq!(map["role"], vec!["ACME Company"]);
+        assert_eq!(map["address"], vec!["Packet Street 6", "Internet"]);
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "193.254.30.0/24";
+            "origin": "AS12312";
+            "origin": "AS3257";
+            "mnt-by": "MAINT-AS3257";
+        },
+        "`origin`: expected a single attribute, found 2"
+    )]
+    #[case(
+        object! {
+            "route": "193.254.30.0/24";
+            "mnt-by": "MAINT-AS3257";
+        },
+        "missing field `origin`"
+    )]
+    fn deserialize_struct_invalid_is_expected_err(#[case] object: Object, #[case] expected: &str) {
+        let err = from_object::<Ipv4Route, _>(&object).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn deserialize_unparsable_value_is_err() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct AutNum {
+            #[serde(rename = "local-as")]
+            local_as: u32,
+        }
+
+        let object = object! { "local-as": "AS3257"; };
+        let err = from_object::<AutNum, _>(&object).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`local-as`: invalid value `AS3257`: invalid digit found in string"
+        );
+    }
+}
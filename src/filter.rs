@@ -0,0 +1,495 @@
+//! Evaluation of routing policy filters.
+//!
+//! An [`Evaluator`] decides whether a [`Route`] is matched by a [`Filter`], looking up the sets
+//! and routes referenced by the filter using a [`Resolver`]. References which cannot be resolved
+//! are treated as empty sets and reported in the resulting [`Evaluation`], as are parts of the
+//! filter which cannot be evaluated.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     filter::{Evaluator, Route, Unresolved},
+//!     parse_whois_response,
+//!     policy::Filter,
+//!     resolve::ObjectResolver,
+//! };
+//!
+//! let objects = parse_whois_response(
+//!     "
+//! as-set:         AS-FOO
+//! members:        AS65536, AS-BAR
+//!
+//! route:          192.0.2.0/24
+//! origin:         AS65536
+//!
+//! ",
+//! )?;
+//! let resolver: ObjectResolver = objects.iter().collect();
+//! let evaluator = Evaluator::new(&resolver);
+//!
+//! let filter = Filter::parse("AS-FOO AND NOT {0.0.0.0/0^0-7}")?;
+//! let evaluation = evaluator.evaluate(&filter, &Route::new("192.0.2.0/24".parse()?));
+//! assert!(evaluation.matches);
+//! assert_eq!(evaluation.unresolved, vec![Unresolved::AsSet("AS-BAR".parse()?)]);
+//!
+//! let mut route = Route::new("198.51.100.0/24".parse()?);
+//! route.as_path = vec![1.into(), 2.into(), 2.into()];
+//! let filter = Filter::parse("<^AS1 AS2*$>")?;
+//! assert!(evaluator.evaluate(&filter, &route).matches);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Semantics
+//!
+//! As defined by RFC 2622, an AS number in a filter matches the routes it originates. These are
+//! the prefixes of the `route` and `route6` objects with the AS as their origin, to which range
+//! operators are applied. `PeerAS` refers to the first AS of the route's AS path, which is
+//! matched by AS path filters using the [`as_path`](crate::as_path) module.
+//!
+//! Community filters such as `community(65535:10)` and `community.contains(...)` match routes
+//! with all listed communities, `community == {...}` matches routes with exactly the listed
+//! communities.
+
+use std::collections::HashSet;
+
+use crate::{
+    as_path::{Regex, ResolvedAsSets},
+    policy::{Filter, FilterKind, Span},
+    resolve::{self, Member, Resolver},
+    types::{
+        AsNumber, AsSetName, Community, FilterSetName, Prefix, RangeOperator, RouteSetName,
+        TypeError,
+    },
+};
+
+/// A route to be matched by a filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// The prefix of the route.
+    pub prefix: Prefix,
+    /// The AS path of the route, starting with the peer AS and ending with the origin AS.
+    pub as_path: Vec<AsNumber>,
+    /// The communities of the route.
+    pub communities: Vec<Community>,
+}
+
+impl Route {
+    /// Create a route with an empty AS path and no communities.
+    #[must_use]
+    pub fn new(prefix: Prefix) -> Self {
+        Self {
+            prefix,
+            as_path: Vec::new(),
+            communities: Vec::new(),
+        }
+    }
+
+    /// The AS the route was received from, which is the first AS of its path.
+    #[must_use]
+    pub fn peer_as(&self) -> Option<AsNumber> {
+        self.as_path.first().copied()
+    }
+}
+
+/// The result of evaluating a filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Whether the route is matched by the filter.
+    pub matches: bool,
+    /// The references and parts of the filter that could not be resolved, in the order they
+    /// were encountered.
+    pub unresolved: Vec<Unresolved>,
+}
+
+impl Evaluation {
+    /// Whether the filter was evaluated without any unresolved references.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// A reference or part of a filter that could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unresolved {
+    /// An `as-set` that is not defined.
+    AsSet(AsSetName),
+    /// A `route-set` that is not defined.
+    RouteSet(RouteSetName),
+    /// A `filter-set` that is not defined.
+    FilterSet(FilterSetName),
+    /// A part of the filter that cannot be evaluated, e.g. an invalid AS path regular expression.
+    Filter {
+        /// The span of the part of the filter.
+        span: Span,
+        /// Why it cannot be evaluated.
+        error: TypeError,
+    },
+}
+
+/// Evaluates filters using a [`Resolver`].
+#[derive(Debug, Clone, Copy)]
+pub struct Evaluator<'r, R: ?Sized> {
+    resolver: &'r R,
+}
+
+impl<'r, R: Resolver + ?Sized> Evaluator<'r, R> {
+    /// Create an evaluator resolving references using the resolver.
+    #[must_use]
+    pub fn new(resolver: &'r R) -> Self {
+        Self { resolver }
+    }
+
+    /// Evaluate whether the route is matched by the filter.
+    #[must_use]
+    pub fn evaluate(&self, filter: &Filter, route: &Route) -> Evaluation {
+        let mut evaluation = Evaluation {
+            matches: false,
+            unresolved: Vec::new(),
+        };
+        let mut state = State {
+            resolver: self.resolver,
+            route,
+            unresolved: &mut evaluation.unresolved,
+            filter_sets: Vec::new(),
+        };
+        evaluation.matches = state.filter(filter);
+        evaluation
+    }
+}
+
+/// The state of a single evaluation.
+struct State<'s, R: ?Sized> {
+    resolver: &'s R,
+    route: &'s Route,
+    unresolved: &'s mut Vec<Unresolved>,
+    /// The filter-sets being evaluated, to prevent endless recursion.
+    filter_sets: Vec<String>,
+}
+
+impl<R: Resolver + ?Sized> State<'_, R> {
+    fn unresolved(&mut self, unresolved: Unresolved) {
+        if !self.unresolved.contains(&unresolved) {
+            self.unresolved.push(unresolved);
+        }
+    }
+
+    /// Evaluate the filter. Both operands of `AND` and `OR` are always evaluated, such that all
+    /// unresolved references are reported.
+    fn filter(&mut self, filter: &Filter) -> bool {
+        let prefix = self.route.prefix;
+        match &filter.kind {
+            FilterKind::Any => true,
+            FilterKind::PeerAs => self
+                .route
+                .peer_as()
+                .is_some_and(|peer| self.originates(peer, RangeOperator::None)),
+            FilterKind::AsNumber(number, operator) => self.originates(*number, *operator),
+            FilterKind::AsSet(name, operator) => self
+                .as_set(name)
+                .into_iter()
+                .any(|number| self.originates(number, *operator)),
+            FilterKind::RouteSet(name, operator) => {
+                self.route_set(name, *operator, &mut Vec::new())
+            }
+            FilterKind::FilterSet(name) => self.filter_set(name),
+            FilterKind::Prefixes(ranges, operator) => ranges
+                .iter()
+                .any(|range| within(range.prefix(), operator.apply_to(range.operator()), &prefix)),
+            FilterKind::AsPath(regex) => self.as_path(regex, filter.span.clone()),
+            FilterKind::Community { method, arguments } => {
+                self.community(method.as_deref(), arguments, filter.span.clone())
+            }
+            FilterKind::Not(filter) => !self.filter(filter),
+            FilterKind::And(left, right) => {
+                let left = self.filter(left);
+                self.filter(right) && left
+            }
+            FilterKind::Or(left, right) => {
+                let left = self.filter(left);
+                self.filter(right) || left
+            }
+        }
+    }
+
+    /// Whether the AS originates a route object matching the route with the operator applied.
+    fn originates(&self, origin: AsNumber, operator: RangeOperator) -> bool {
+        self.resolver
+            .routes(origin)
+            .iter()
+            .any(|route| within(*route, operator, &self.route.prefix))
+    }
+
+    /// The AS numbers contained in the as-set, including those of its nested sets.
+    fn as_set(&mut self, name: &AsSetName) -> HashSet<AsNumber> {
+        let mut unresolved = Vec::new();
+        let numbers = resolve::as_set_numbers(self.resolver, name, &mut unresolved);
+        for name in unresolved {
+            self.unresolved(Unresolved::AsSet(name));
+        }
+        numbers
+    }
+
+    /// Whether the route-set contains the route with the operator applied to its members.
+    ///
+    /// The names of the route-sets being evaluated are kept in `path` to stop at cycles.
+    fn route_set(
+        &mut self,
+        name: &RouteSetName,
+        operator: RangeOperator,
+        path: &mut Vec<String>,
+    ) -> bool {
+        let key = name.as_str().to_ascii_uppercase();
+        if path.contains(&key) {
+            return false;
+        }
+        let Some(members) = self.resolver.route_set(name) else {
+            self.unresolved(Unresolved::RouteSet(name.clone()));
+            return false;
+        };
+        path.push(key);
+        let mut matches = false;
+        for member in members.iter() {
+            matches |= match member {
+                Member::Prefix(range) => within(
+                    range.prefix(),
+                    operator.apply_to(range.operator()),
+                    &self.route.prefix,
+                ),
+                Member::AsNumber(number, inner) => {
+                    self.originates(*number, operator.apply_to(*inner))
+                }
+                Member::AsSet(name, inner) => self
+                    .as_set(name)
+                    .into_iter()
+                    .any(|number| self.originates(number, operator.apply_to(*inner))),
+                Member::RouteSet(name, inner) => {
+                    self.route_set(name, operator.apply_to(*inner), path)
+                }
+            };
+        }
+        path.pop();
+        matches
+    }
+
+    fn filter_set(&mut self, name: &FilterSetName) -> bool {
+        let key = name.as_str().to_ascii_uppercase();
+        if self.filter_sets.contains(&key) {
+            return false;
+        }
+        let Some(filter) = self.resolver.filter_set(name) else {
+            self.unresolved(Unresolved::FilterSet(name.clone()));
+            return false;
+        };
+        self.filter_sets.push(key);
+        let matches = self.filter(&filter);
+        self.filter_sets.pop();
+        matches
+    }
+
+    fn as_path(&mut self, regex: &str, span: Span) -> bool {
+        let regex: Regex = match regex.parse() {
+            Ok(regex) => regex,
+            Err(error) => {
+                self.unresolved(Unresolved::Filter { span, error });
+                return false;
+            }
+        };
+        let sets = ResolvedAsSets::new(&regex, self.resolver);
+        for name in sets.unresolved() {
+            self.unresolved(Unresolved::AsSet(name.clone()));
+        }
+        let path: Vec<_> = self.route.as_path.iter().map(|asn| asn.get()).collect();
+        regex.is_match(&path, &sets)
+    }
+
+    fn community(&mut self, method: Option<&str>, arguments: &[String], span: Span) -> bool {
+        let communities: Result<HashSet<Community>, _> =
+            arguments.iter().map(|argument| argument.parse()).collect();
+        let communities = match communities {
+            Ok(communities) => communities,
+            Err(error) => {
+                self.unresolved(Unresolved::Filter { span, error });
+                return false;
+            }
+        };
+        let route: HashSet<_> = self.route.communities.iter().copied().collect();
+        match method {
+            None => communities.is_subset(&route),
+            Some(method) if method.eq_ignore_ascii_case("contains") => {
+                communities.is_subset(&route)
+            }
+            Some("==") => communities == route,
+            Some(method) => {
+                let error = TypeError::new(
+                    "community filter",
+                    method,
+                    0,
+                    "expected community(...), community.contains(...) or community == {...}",
+                );
+                self.unresolved(Unresolved::Filter { span, error });
+                false
+            }
+        }
+    }
+}
+
+/// Whether the prefix is matched by the base prefix with the range operator applied.
+///
+/// Unlike [`AddressPrefixRange`](crate::types::AddressPrefixRange), operators with lengths
+/// shorter than the base prefix are allowed, as they result from applying operators to sets.
+fn within(base: Prefix, operator: RangeOperator, prefix: &Prefix) -> bool {
+    let length = base.length();
+    let (min, max) = match operator {
+        RangeOperator::None => (length, length),
+        RangeOperator::LessExcl => (length.saturating_add(1), base.max_length()),
+        RangeOperator::LessIncl => (length, base.max_length()),
+        RangeOperator::Exact(n) => (n, n),
+        RangeOperator::Range(n, m) => (n, m),
+    };
+    base.contains(prefix) && (min..=max).contains(&prefix.length())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_whois_response, resolve::ObjectResolver};
+
+    const OBJECTS: &str = "
+as-set:         AS-FOO
+members:        AS1, AS-BAR
+
+as-set:         AS-BAR
+members:        AS2, AS-FOO
+
+as-set:         AS-MISSING
+members:        AS-UNDEFINED
+
+route-set:      RS-FOO
+members:        198.51.100.0/24^+, AS2^25, RS-BAR
+
+route-set:      RS-BAR
+members:        203.0.113.0/24, RS-FOO
+
+filter-set:     FLTR-FOO
+filter:         AS1 OR FLTR-FOO
+
+route:          192.0.2.0/24
+origin:         AS1
+
+route:          10.0.0.0/16
+origin:         AS2
+
+";
+
+    fn resolver() -> ObjectResolver {
+        parse_whois_response(OBJECTS).unwrap().iter().collect()
+    }
+
+    fn route(prefix: &str, as_path: &[u32], communities: &[&str]) -> Route {
+        Route {
+            prefix: prefix.parse().unwrap(),
+            as_path: as_path.iter().copied().map(AsNumber::new).collect(),
+            communities: communities.iter().map(|c| c.parse().unwrap()).collect(),
+        }
+    }
+
+    fn evaluate(filter: &str, route: &Route) -> Evaluation {
+        Evaluator::new(&resolver()).evaluate(&Filter::parse(filter).unwrap(), route)
+    }
+
+    #[rstest]
+    #[case("ANY", "192.0.2.0/24", true)]
+    #[case("AS1", "192.0.2.0/24", true)]
+    #[case("AS1", "192.0.2.0/25", false)]
+    #[case("AS1^+", "192.0.2.0/25", true)]
+    #[case("AS1^-", "192.0.2.0/24", false)]
+    #[case("AS2", "192.0.2.0/24", false)]
+    #[case("PeerAS", "192.0.2.0/24", true)]
+    #[case("AS-FOO", "10.0.0.0/16", true)]
+    #[case("AS-FOO^16-24", "10.0.1.0/24", true)]
+    #[case("RS-FOO", "198.51.100.128/25", true)]
+    #[case("RS-FOO", "10.0.1.0/25", true)]
+    #[case("RS-FOO", "10.0.1.0/24", false)]
+    #[case("RS-FOO", "203.0.113.0/24", true)]
+    #[case("RS-FOO^-", "203.0.113.0/24", false)]
+    #[case("RS-FOO^26", "198.51.100.64/26", true)]
+    #[case("RS-FOO^26", "198.51.100.0/24", false)]
+    #[case("FLTR-FOO", "192.0.2.0/24", true)]
+    #[case("{ 192.0.2.0/24^+, 198.51.100.0/24 }", "192.0.2.0/28", true)]
+    #[case("{ 192.0.2.0/24^+, 198.51.100.0/24 }^-", "198.51.100.0/24", false)]
+    #[case("{ 192.0.2.0/24^+, 198.51.100.0/24 }^25", "198.51.100.0/25", true)]
+    #[case("{}", "192.0.2.0/24", false)]
+    #[case("AS-FOO AND NOT {0.0.0.0/0^0-7}", "192.0.2.0/24", true)]
+    #[case("AS-FOO AND NOT {0.0.0.0/0^0-24}", "192.0.2.0/24", false)]
+    #[case("NOT AS1 OR AS2", "10.0.0.0/16", true)]
+    fn prefix_filters(#[case] filter: &str, #[case] prefix: &str, #[case] expected: bool) {
+        let evaluation = evaluate(filter, &route(prefix, &[1], &[]));
+        assert_eq!(evaluation.matches, expected);
+        assert!(evaluation.is_complete(), "{:?}", evaluation.unresolved);
+    }
+
+    #[rstest]
+    #[case("<^AS1 AS2*$>", &[1], true)]
+    #[case("<^AS1 AS2*$>", &[1, 2, 2], true)]
+    #[case("<^AS1 AS2*$>", &[1, 2, 3], false)]
+    #[case("<AS-FOO+$>", &[3, 2, 1], true)]
+    #[case("<^AS-FOO>", &[3, 2, 1], false)]
+    #[case("<^PeerAS .* AS2$>", &[1, 3, 2], true)]
+    fn as_path_filters(#[case] filter: &str, #[case] as_path: &[u32], #[case] expected: bool) {
+        let evaluation = evaluate(filter, &route("192.0.2.0/24", as_path, &[]));
+        assert_eq!(evaluation.matches, expected);
+        assert!(evaluation.is_complete(), "{:?}", evaluation.unresolved);
+    }
+
+    #[rstest]
+    #[case("community(65535:10)", true)]
+    #[case("community(65535:10, no_export)", true)]
+    #[case("community.contains(65535:10, 65535:20)", false)]
+    #[case("community == {65535:10, no_export}", true)]
+    #[case("community == {65535:10}", false)]
+    fn community_filters(#[case] filter: &str, #[case] expected: bool) {
+        let route = route("192.0.2.0/24", &[1], &["65535:10", "no_export"]);
+        let evaluation = evaluate(filter, &route);
+        assert_eq!(evaluation.matches, expected);
+        assert!(evaluation.is_complete(), "{:?}", evaluation.unresolved);
+    }
+
+    #[test]
+    fn unresolved_references_are_reported() {
+        let filter = "AS-MISSING OR RS-MISSING OR FLTR-MISSING OR <AS1:AS-MISSING> OR AS-MISSING";
+        let evaluation = evaluate(filter, &route("192.0.2.0/24", &[1], &[]));
+        assert!(!evaluation.matches);
+        assert_eq!(
+            evaluation.unresolved,
+            vec![
+                Unresolved::AsSet("AS-UNDEFINED".parse().unwrap()),
+                Unresolved::RouteSet("RS-MISSING".parse().unwrap()),
+                Unresolved::FilterSet("FLTR-MISSING".parse().unwrap()),
+                Unresolved::AsSet("AS1:AS-MISSING".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_references_in_negations_match() {
+        let evaluation = evaluate("NOT AS-UNDEFINED", &route("192.0.2.0/24", &[1], &[]));
+        assert!(evaluation.matches);
+        assert!(!evaluation.is_complete());
+    }
+
+    #[rstest]
+    #[case("<AS1 (AS2>", 0..10)]
+    #[case("AS1 OR community(65536:1)", 7..25)]
+    #[case("community.delete(65535:1)", 0..25)]
+    fn invalid_filters_are_reported(#[case] filter: &str, #[case] span: Span) {
+        let evaluation = evaluate(filter, &route("192.0.2.0/24", &[1], &[]));
+        assert!(matches!(
+            evaluation.unresolved.as_slice(),
+            [Unresolved::Filter { span: unresolved, .. }] if *unresolved == span
+        ));
+    }
+}
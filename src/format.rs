@@ -0,0 +1,356 @@
+//! Configurable formatting of RPSL objects.
+//!
+//! The [`Display`](std::fmt::Display) implementations of [`Object`] and [`Attribute`] produce RPSL
+//! using the same defaults most registries use: values aligned to column 16, continuation lines
+//! indented with spaces and a trailing blank line terminating each object. Since not every
+//! registry's tooling expects the same layout, a [`Formatter`] can be configured with
+//! [`FormatOptions`] to produce RPSL in a different style.
+//!
+//! ```rust
+//! # use rpsl::{object, format::{Alignment, Continuation, FormatOptions, Formatter, NameCase}};
+//! let role_acme = object! {
+//!     "Role": "ACME Company";
+//!     "Address": "Packet Street 6", "128 Series of Tubes", "Internet";
+//! };
+//!
+//! let formatter = Formatter::new(
+//!     FormatOptions::new()
+//!         .alignment(Alignment::Column(12))
+//!         .continuation(Continuation::Plus)
+//!         .name_case(NameCase::Lowercase)
+//!         .trailing_blank_line(false),
+//! );
+//! assert_eq!(
+//!     formatter.format_object(&role_acme),
+//!     concat!(
+//!         "role:       ACME Company\n",
+//!         "address:    Packet Street 6\n",
+//!         "+           128 Series of Tubes\n",
+//!         "+           Internet\n",
+//!     )
+//! );
+//! ```
+//!
+//! Objects are always formatted from their attributes, even if they were parsed from RPSL and
+//! would display as their original source otherwise.
+
+use std::fmt::{self, Write};
+
+use crate::{spec::Specification, Attribute, Object};
+
+/// Where attribute values are placed relative to their name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// Align values to the given column, e.g. `16` for `mnt-by:         MAINT-AS3257`.
+    ///
+    /// Names longer than the column are directly followed by their value.
+    Column(usize),
+    /// Separate values from their names by a single space, e.g. `mnt-by: MAINT-AS3257`.
+    None,
+}
+
+/// The character that starts a continuation line of a multi line value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Continuation {
+    /// A space (`' '`).
+    Space,
+    /// A tab (`'\t'`).
+    Tab,
+    /// A plus sign (`'+'`).
+    Plus,
+}
+
+impl Continuation {
+    /// The character represented.
+    #[must_use]
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Space => ' ',
+            Self::Tab => '\t',
+            Self::Plus => '+',
+        }
+    }
+}
+
+/// How the case of attribute names is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameCase {
+    /// Keep names as they are.
+    Preserve,
+    /// Convert names to ASCII lowercase.
+    Lowercase,
+    /// Convert names to ASCII uppercase.
+    Uppercase,
+}
+
+/// Options used by a [`Formatter`].
+///
+/// The default options produce the same output as the [`Display`](std::fmt::Display)
+/// implementation of an [`Attribute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    alignment: Alignment,
+    continuation: Continuation,
+    name_case: NameCase,
+    trailing_blank_line: bool,
+}
+
+impl FormatOptions {
+    /// Create the default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            alignment: Alignment::Column(16),
+            continuation: Continuation::Space,
+            name_case: NameCase::Preserve,
+            trailing_blank_line: true,
+        }
+    }
+
+    /// Set where values are placed relative to their name.
+    #[must_use]
+    pub const fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set the character that starts continuation lines.
+    #[must_use]
+    pub const fn continuation(mut self, continuation: Continuation) -> Self {
+        self.continuation = continuation;
+        self
+    }
+
+    /// Set how the case of attribute names is written.
+    #[must_use]
+    pub const fn name_case(mut self, name_case: NameCase) -> Self {
+        self.name_case = name_case;
+        self
+    }
+
+    /// Set whether objects are terminated by a blank line.
+    #[must_use]
+    pub const fn trailing_blank_line(mut self, trailing_blank_line: bool) -> Self {
+        self.trailing_blank_line = trailing_blank_line;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats objects and attributes as RPSL according to its [`FormatOptions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Formatter {
+    options: FormatOptions,
+}
+
+impl Formatter {
+    /// Create a new formatter using the given options.
+    #[must_use]
+    pub const fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// The options used by this formatter.
+    #[must_use]
+    pub const fn options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// Format an object as RPSL.
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn format_object<Spec: Specification>(&self, object: &Object<'_, Spec>) -> String {
+        let mut s = String::new();
+        self.write_object(&mut s, object)
+            .expect("writing to a string does not fail");
+        s
+    }
+
+    /// Format an attribute as RPSL.
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn format_attribute<Spec: Specification>(&self, attribute: &Attribute<'_, Spec>) -> String {
+        let mut s = String::new();
+        self.write_attribute(&mut s, attribute)
+            .expect("writing to a string does not fail");
+        s
+    }
+
+    /// Write an object as RPSL.
+    ///
+    /// # Errors
+    /// Returns an error if writing to the given writer fails.
+    pub fn write_object<W, Spec>(&self, w: &mut W, object: &Object<'_, Spec>) -> fmt::Result
+    where
+        W: Write + ?Sized,
+        Spec: Specification,
+    {
+        for attribute in object.iter() {
+            self.write_attribute(w, attribute)?;
+        }
+        if self.options.trailing_blank_line {
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Write an attribute as RPSL.
+    ///
+    /// # Errors
+    /// Returns an error if writing to the given writer fails.
+    pub fn write_attribute<W, Spec>(
+        &self,
+        w: &mut W,
+        attribute: &Attribute<'_, Spec>,
+    ) -> fmt::Result
+    where
+        W: Write + ?Sized,
+        Spec: Specification,
+    {
+        let mut lines = attribute.value.values().into_iter();
+
+        let name = match self.options.name_case {
+            NameCase::Preserve => attribute.name.to_string(),
+            NameCase::Lowercase => attribute.name.to_ascii_lowercase(),
+            NameCase::Uppercase => attribute.name.to_ascii_uppercase(),
+        };
+        let prefix = format!("{name}:");
+        match lines.next().flatten() {
+            Some(value) => {
+                self.write_padded(w, &prefix)?;
+                writeln!(w, "{value}")?;
+            }
+            None => writeln!(w, "{prefix}")?,
+        }
+
+        let continuation = self.options.continuation.as_char();
+        for line in lines {
+            match line {
+                Some(value) => {
+                    self.write_padded(w, continuation.encode_utf8(&mut [0; 4]))?;
+                    writeln!(w, "{value}")?;
+                }
+                None => writeln!(w, "{continuation}")?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a line prefix followed by the padding required to reach the value column.
+    fn write_padded<W: Write + ?Sized>(&self, w: &mut W, prefix: &str) -> fmt::Result {
+        match self.options.alignment {
+            Alignment::Column(width) => write!(w, "{prefix:width$}"),
+            Alignment::None => write!(w, "{prefix} "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    #[rstest]
+    #[case(FormatOptions::new(), concat!(
+        "role:           ACME Company\n",
+        "remarks:\n",
+        "address:        Packet Street 6\n",
+        " \n",
+        "                Internet\n",
+        "\n",
+    ))]
+    #[case(FormatOptions::new().alignment(Alignment::None), concat!(
+        "role: ACME Company\n",
+        "remarks:\n",
+        "address: Packet Street 6\n",
+        " \n",
+        "  Internet\n",
+        "\n",
+    ))]
+    #[case(FormatOptions::new().alignment(Alignment::Column(4)), concat!(
+        "role:ACME Company\n",
+        "remarks:\n",
+        "address:Packet Street 6\n",
+        " \n",
+        "    Internet\n",
+        "\n",
+    ))]
+    #[case(FormatOptions::new().continuation(Continuation::Plus), concat!(
+        "role:           ACME Company\n",
+        "remarks:\n",
+        "address:        Packet Street 6\n",
+        "+\n",
+        "+               Internet\n",
+        "\n",
+    ))]
+    #[case(FormatOptions::new().continuation(Continuation::Tab).alignment(Alignment::None), concat!(
+        "role: ACME Company\n",
+        "remarks:\n",
+        "address: Packet Street 6\n",
+        "\t\n",
+        "\t Internet\n",
+        "\n",
+    ))]
+    #[case(FormatOptions::new().name_case(NameCase::Uppercase).trailing_blank_line(false), concat!(
+        "ROLE:           ACME Company\n",
+        "REMARKS:\n",
+        "ADDRESS:        Packet Street 6\n",
+        " \n",
+        "                Internet\n",
+    ))]
+    fn format_object_with_options(#[case] options: FormatOptions, #[case] expected: &str) {
+        let object = object! {
+            "role": "ACME Company";
+            "remarks": "";
+            "address": "Packet Street 6", "", "Internet";
+        };
+        assert_eq!(Formatter::new(options).format_object(&object), expected);
+    }
+
+    #[rstest]
+    #[case(FormatOptions::new().name_case(NameCase::Lowercase), "asnumber:       32934\n")]
+    #[case(FormatOptions::new().name_case(NameCase::Preserve), "ASNumber:       32934\n")]
+    fn format_attribute_name_case(#[case] options: FormatOptions, #[case] expected: &str) {
+        let attribute = Attribute::unchecked_single("ASNumber", "32934");
+        assert_eq!(
+            Formatter::new(options).format_attribute(&attribute),
+            expected
+        );
+    }
+
+    #[test]
+    /// Formatting with the default options is equivalent to displaying the object.
+    fn format_object_default_is_display() {
+        let object = object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6", "128 Series of Tubes", "Internet";
+            "source": "RIPE";
+        };
+        assert_eq!(
+            Formatter::default().format_object(&object),
+            object.to_string()
+        );
+    }
+
+    #[test]
+    /// Objects formatted with any continuation character can be parsed again.
+    fn formatted_object_can_be_parsed() {
+        let object = object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6", "", "Internet";
+        };
+        for continuation in [Continuation::Space, Continuation::Tab, Continuation::Plus] {
+            let rpsl = Formatter::new(FormatOptions::new().continuation(continuation))
+                .format_object(&object);
+            assert_eq!(crate::parse_object(&rpsl).unwrap(), object);
+        }
+    }
+}
@@ -56,6 +56,11 @@
 //!
 //! For more information on object validation, see the [`spec`] module.
 //!
+//! ### Formatting RPSL objects
+//!
+//! Objects display as RPSL with values aligned to column 16. To produce RPSL in a different
+//! style, for example using `+` as the continuation character, see the [`format`] module.
+//!
 //! ### Parsing a WHOIS server response
 //!
 //! WHOIS servers often respond to queries by returning multiple related objects.
@@ -93,15 +98,45 @@
 //! The following cargo features can be used to enable additional functionality.
 //!
 //! - **simd** _(enabled by default)_: Enables the [Winnow](https://github.com/winnow-rs/winnow) simd feature which improves string search performance using simd.
-//! - **serde**: Enables [Object] serialization using [Serde](https://github.com/serde-rs/serde).
-//! - **json**: Provides JSON serialization of an [Object] using [Serde JSON](https://github.com/serde-rs/json).
+//! - **serde**: Enables [Object] serialization and deserialization using [Serde](https://github.com/serde-rs/serde),
+//!   as well as mapping user defined types from and into an [Object] using the `de` and `ser` modules.
+//! - **derive**: Provides derive macros for converting typed models from and into an [Object], see the [`convert`] module.
+//! - **json**: Provides JSON serialization of an [Object] using [Serde JSON](https://github.com/serde-rs/json),
+//!   as well as conversion from and into the RIPE Database REST API JSON format.
+//! - **xml**: Provides conversion from and into the RIPE Database REST API XML format.
+//! - **schema**: Provides validators compiled from a declarative TOML or JSON schema, see the `schema` module.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub use attribute::{Attribute, Name, Value};
 pub use object::{Object, ObjectValidationError};
 pub use parser::{parse_object, parse_whois_response, ParseError};
 
+pub mod as_path;
 mod attribute;
+pub mod convert;
+pub mod database;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod de;
+pub mod filter;
+pub mod format;
+pub mod model;
 mod object;
 mod parser;
+pub mod policy;
+pub mod prefix_list;
+pub mod prefix_set;
+pub mod resolve;
+#[cfg(any(feature = "json", feature = "xml"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "json", feature = "xml"))))]
+pub mod ripe;
+#[cfg(feature = "schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+pub mod schema;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod ser;
 pub mod spec;
+pub mod template;
+pub mod trie;
+pub mod types;
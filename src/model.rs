@@ -0,0 +1,697 @@
+//! Typed models of the core RPSL object classes.
+//!
+//! Each class is represented by a struct that can be created from an [`Object`] of that class
+//! using [`TryFrom`], validating the syntax of the attributes identifying the object, and turned
+//! back into an [`Object`] using [`From`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{model::Route, parse_object, types::AsNumber, Object};
+//!
+//! let object = parse_object("
+//! route:          193.254.30.0/24
+//! origin:         AS12312
+//! mnt-by:         MAINT-AS12312
+//! source:         RIPE # Filtered
+//!
+//! ")?;
+//! let route = Route::try_from(&object)?;
+//! assert_eq!(route.origin, AsNumber::new(12312));
+//! assert_eq!(route.route.length(), 24);
+//! assert_eq!(route.source.as_deref(), Some("RIPE"));
+//!
+//! let object: Object = route.into();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only attributes identifying an object or defining its meaning are required, such that objects
+//! of any IRR can be converted. Attributes not modeled by a class are ignored and comments are
+//! removed, attributes containing a comma separated list are split into their items. Converting
+//! a model back into an [`Object`] therefore produces an equivalent object rather than an
+//! identical one, with attributes in the order of the model's fields.
+//!
+//! The models implement [`FromObject`] and [`IntoObject`] as well, so they can be used in the
+//! same way as models defined using the [`convert`](crate::convert) module.
+
+use crate::{
+    convert::{
+        attribute, list, multiple, optional, required, required_multiple, FromObject,
+        FromObjectError, IntoObject,
+    },
+    spec::{InvalidValueError, Specification},
+    types::{
+        AsNumber, AsSetName, FilterSetName, Ipv4Range, NicHandle, PeeringSetName, Prefix,
+        RouteSetName,
+    },
+    Attribute, Name, Object,
+};
+
+/// Define a struct modeling an object class.
+///
+/// Each field is declared as `<kind> <field>: <type> = "<attribute>"`, optionally followed by
+/// `if <check>` to validate the parsed value of a `required` field.
+macro_rules! class {
+    (@type required $ty:ty) => { $ty };
+    (@type optional $ty:ty) => { Option<$ty> };
+    (@type multiple $ty:ty) => { Vec<$ty> };
+    (@type required_multiple $ty:ty) => { Vec<$ty> };
+    (@type list $ty:ty) => { Vec<$ty> };
+    (@type text $ty:ty) => { $ty };
+
+    (@doc required $name:literal) => { concat!("The `", $name, "` attribute.") };
+    (@doc optional $name:literal) => { concat!("The `", $name, "` attribute, if present.") };
+    (@doc multiple $name:literal) => { concat!("The values of all `", $name, "` attributes.") };
+    (@doc required_multiple $name:literal) => {
+        concat!("The values of all `", $name, "` attributes, of which there is at least one.")
+    };
+    (@doc list $name:literal) => { concat!("The items listed by all `", $name, "` attributes.") };
+    (@doc text $name:literal) => {
+        concat!("The lines of all `", $name, "` attributes, separated by newlines.")
+    };
+
+    (@read text $object:ident, $name:literal) => { text($object, $name)? };
+    (@read $kind:ident $object:ident, $name:literal) => { $kind($object, $name)? };
+
+    (@write required $attributes:ident, $name:literal, $value:expr) => {
+        $attributes.push(attribute($name, &$value));
+    };
+    (@write text $attributes:ident, $name:literal, $value:expr) => {
+        $attributes.push(attribute($name, &$value));
+    };
+    (@write optional $attributes:ident, $name:literal, $value:expr) => {
+        if let Some(value) = &$value {
+            $attributes.push(attribute($name, value));
+        }
+    };
+    (@write $kind:ident $attributes:ident, $name:literal, $value:expr) => {
+        for value in &$value {
+            $attributes.push(attribute($name, value));
+        }
+    };
+
+    (
+        $(#[$meta:meta])*
+        $ident:ident => $class:literal {
+            $($kind:ident $field:ident: $ty:ty = $name:literal $(if $check:expr)?,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $ident {
+            $(
+                #[doc = class!(@doc $kind $name)]
+                pub $field: class!(@type $kind $ty),
+            )*
+        }
+
+        impl $ident {
+            /// The name of the class, which is the name of the first attribute of its objects.
+            pub const CLASS: &'static str = $class;
+        }
+
+        impl FromObject for $ident {
+            fn from_object<Spec: Specification>(
+                object: &Object<'_, Spec>,
+            ) -> Result<Self, FromObjectError> {
+                expect_class(object, $class)?;
+                $(
+                    let $field = class!(@read $kind object, $name);
+                    $(check(object, $name, &$field, $check)?;)?
+                )*
+                Ok(Self { $($field,)* })
+            }
+        }
+
+        impl IntoObject for $ident {
+            fn into_object(self) -> Object<'static> {
+                let mut attributes = Vec::new();
+                $(class!(@write $kind attributes, $name, self.$field);)*
+                Object::new(attributes)
+            }
+        }
+
+        impl<Spec: Specification> TryFrom<&Object<'_, Spec>> for $ident {
+            type Error = FromObjectError;
+
+            fn try_from(object: &Object<'_, Spec>) -> Result<Self, Self::Error> {
+                Self::from_object(object)
+            }
+        }
+
+        impl From<$ident> for Object<'static> {
+            fn from(model: $ident) -> Self {
+                model.into_object()
+            }
+        }
+    };
+}
+
+class! {
+    /// An `aut-num` object, describing an autonomous system and its routing policy.
+    AutNum => "aut-num" {
+        required aut_num: AsNumber = "aut-num",
+        required as_name: String = "as-name",
+        multiple descr: String = "descr",
+        list member_of: String = "member-of",
+        multiple import: String = "import",
+        multiple mp_import: String = "mp-import",
+        multiple export: String = "export",
+        multiple mp_export: String = "mp-export",
+        multiple default: String = "default",
+        multiple mp_default: String = "mp-default",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// An `as-set` object, a named set of autonomous systems.
+    AsSet => "as-set" {
+        required as_set: AsSetName = "as-set",
+        multiple descr: String = "descr",
+        list members: String = "members",
+        list mp_members: String = "mp-members",
+        list mbrs_by_ref: String = "mbrs-by-ref",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `route` object, describing the origin of an IPv4 prefix.
+    Route => "route" {
+        required route: Prefix = "route" if ipv4,
+        required origin: AsNumber = "origin",
+        multiple descr: String = "descr",
+        list holes: String = "holes",
+        list member_of: String = "member-of",
+        multiple inject: String = "inject",
+        optional aggr_mtd: String = "aggr-mtd",
+        optional aggr_bndry: String = "aggr-bndry",
+        optional export_comps: String = "export-comps",
+        optional components: String = "components",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `route6` object, describing the origin of an IPv6 prefix.
+    Route6 => "route6" {
+        required route6: Prefix = "route6" if ipv6,
+        required origin: AsNumber = "origin",
+        multiple descr: String = "descr",
+        list holes: String = "holes",
+        list member_of: String = "member-of",
+        multiple inject: String = "inject",
+        optional aggr_mtd: String = "aggr-mtd",
+        optional aggr_bndry: String = "aggr-bndry",
+        optional export_comps: String = "export-comps",
+        optional components: String = "components",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `route-set` object, a named set of address prefixes.
+    RouteSet => "route-set" {
+        required route_set: RouteSetName = "route-set",
+        multiple descr: String = "descr",
+        list members: String = "members",
+        list mp_members: String = "mp-members",
+        list mbrs_by_ref: String = "mbrs-by-ref",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `mntner` object, authorizing changes to the objects it maintains.
+    Mntner => "mntner" {
+        required mntner: String = "mntner",
+        multiple descr: String = "descr",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        required_multiple upd_to: String = "upd-to",
+        multiple mnt_nfy: String = "mnt-nfy",
+        required_multiple auth: String = "auth",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `person` object, describing a technical or administrative contact.
+    Person => "person" {
+        required person: String = "person",
+        required_multiple address: String = "address",
+        required_multiple phone: String = "phone",
+        multiple fax_no: String = "fax-no",
+        multiple e_mail: String = "e-mail",
+        required nic_hdl: NicHandle = "nic-hdl",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `role` object, describing a role performed by one or more people.
+    Role => "role" {
+        required role: String = "role",
+        multiple trouble: String = "trouble",
+        required_multiple address: String = "address",
+        multiple phone: String = "phone",
+        multiple fax_no: String = "fax-no",
+        multiple e_mail: String = "e-mail",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        required nic_hdl: NicHandle = "nic-hdl",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// An `inetnum` object, describing the assignment of an IPv4 address range.
+    Inetnum => "inetnum" {
+        required inetnum: Ipv4Range = "inetnum",
+        required netname: String = "netname",
+        multiple descr: String = "descr",
+        multiple country: String = "country",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        optional status: String = "status",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        list mnt_lower: String = "mnt-lower",
+        multiple mnt_routes: String = "mnt-routes",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// An `inet6num` object, describing the assignment of an IPv6 prefix.
+    Inet6num => "inet6num" {
+        required inet6num: Prefix = "inet6num" if ipv6,
+        required netname: String = "netname",
+        multiple descr: String = "descr",
+        multiple country: String = "country",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        optional status: String = "status",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        list mnt_lower: String = "mnt-lower",
+        multiple mnt_routes: String = "mnt-routes",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// An `inet-rtr` object, describing a router and its peerings.
+    InetRtr => "inet-rtr" {
+        required inet_rtr: String = "inet-rtr",
+        multiple descr: String = "descr",
+        multiple alias: String = "alias",
+        required local_as: AsNumber = "local-as",
+        required_multiple ifaddr: String = "ifaddr",
+        multiple interface: String = "interface",
+        multiple peer: String = "peer",
+        multiple mp_peer: String = "mp-peer",
+        list member_of: String = "member-of",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `peering-set` object, a named set of peerings.
+    PeeringSet => "peering-set" {
+        required peering_set: PeeringSetName = "peering-set",
+        multiple descr: String = "descr",
+        multiple peering: String = "peering",
+        multiple mp_peering: String = "mp-peering",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `filter-set` object, a named set of routes matched by a filter.
+    FilterSet => "filter-set" {
+        required filter_set: FilterSetName = "filter-set",
+        multiple descr: String = "descr",
+        optional filter: String = "filter",
+        optional mp_filter: String = "mp-filter",
+        multiple admin_c: String = "admin-c",
+        multiple tech_c: String = "tech-c",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+class! {
+    /// A `key-cert` object, containing a public key used for authentication.
+    KeyCert => "key-cert" {
+        required key_cert: String = "key-cert" if |name: &String| key_cert_name(name),
+        optional method: String = "method",
+        multiple owner: String = "owner",
+        optional fingerpr: String = "fingerpr",
+        text certif: String = "certif",
+        multiple remarks: String = "remarks",
+        multiple notify: String = "notify",
+        list mnt_by: String = "mnt-by",
+        multiple changed: String = "changed",
+        optional source: String = "source",
+    }
+}
+
+/// Ensure the first attribute of the object is named after the class.
+fn expect_class<Spec: Specification>(
+    object: &Object<'_, Spec>,
+    class: &'static str,
+) -> Result<(), FromObjectError> {
+    match object.first() {
+        Some(attribute) if attribute.name == class => Ok(()),
+        Some(attribute) => Err(FromObjectError::UnexpectedClass {
+            expected: Name::new(class),
+            found: attribute.name.clone().into_owned().into_raw(),
+        }),
+        None => Err(FromObjectError::MissingAttribute(Name::new(class))),
+    }
+}
+
+/// Validate the value parsed from the single attribute with the given name.
+fn check<T, Spec: Specification>(
+    object: &Object<'_, Spec>,
+    name: &str,
+    value: &T,
+    check: impl Fn(&T) -> Result<(), String>,
+) -> Result<(), FromObjectError> {
+    check(value).map_err(|message| {
+        let (index, attribute) = object
+            .iter()
+            .enumerate()
+            .find(|(_, attribute)| attribute.name == name)
+            .expect("checked values are parsed from an attribute");
+        FromObjectError::InvalidAttribute {
+            index,
+            error: InvalidValueError::new(&attribute.value, message).into(),
+        }
+    })
+}
+
+/// The lines of all attributes with the given name, of which there must be at least one.
+fn text<Spec: Specification>(
+    object: &Object<'_, Spec>,
+    name: &str,
+) -> Result<String, FromObjectError> {
+    let lines: Vec<&str> = object
+        .iter()
+        .filter(|attribute| attribute.name == name)
+        .flat_map(|attribute: &Attribute<'_, Spec>| attribute.value.values())
+        .map(Option::unwrap_or_default)
+        .collect();
+    if lines.is_empty() {
+        return Err(FromObjectError::MissingAttribute(Name::new(name)));
+    }
+    Ok(lines.join("\n"))
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn ipv4(prefix: &Prefix) -> Result<(), String> {
+    if prefix.is_ipv4() {
+        Ok(())
+    } else {
+        Err("expected an IPv4 prefix".to_string())
+    }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn ipv6(prefix: &Prefix) -> Result<(), String> {
+    if prefix.is_ipv6() {
+        Ok(())
+    } else {
+        Err("expected an IPv6 prefix".to_string())
+    }
+}
+
+/// Validate the name of a key certificate, e.g. `PGPKEY-1290F9D2`.
+fn key_cert_name(name: &str) -> Result<(), String> {
+    let valid = match name.split_once('-') {
+        Some((method, id)) if method.eq_ignore_ascii_case("PGPKEY") => {
+            id.len() == 8 && id.bytes().all(|b| b.is_ascii_hexdigit())
+        }
+        Some((method, id))
+            if method.eq_ignore_ascii_case("X509") || method.eq_ignore_ascii_case("AUTO") =>
+        {
+            !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+        }
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("expected a key certificate name, e.g. PGPKEY-1290F9D2 or X509-1".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{object, parse_object, spec::AttributeError};
+
+    #[rstest]
+    #[case(
+        "aut-num:        AS65536\nas-name:        EXAMPLE\nimport:         from AS1 accept ANY\nexport:         to AS1 announce AS65536\nadmin-c:        EX1-RIPE\nmnt-by:         MAINT-EXAMPLE\nsource:         RIPE\n\n",
+        AutNum::CLASS
+    )]
+    #[case(
+        "as-set:         AS-EXAMPLE\nmembers:        AS65536\nmembers:        AS-CUSTOMERS\nmbrs-by-ref:    ANY\nsource:         RIPE\n\n",
+        AsSet::CLASS
+    )]
+    #[case(
+        "route:          192.0.2.0/24\norigin:         AS65536\nmember-of:      RS-EXAMPLE\nmnt-by:         MAINT-EXAMPLE\n\n",
+        Route::CLASS
+    )]
+    #[case(
+        "route6:         2001:db8::/32\norigin:         AS65536\nmnt-by:         MAINT-EXAMPLE\n\n",
+        Route6::CLASS
+    )]
+    #[case(
+        "route-set:      AS65536:RS-EXAMPLE\nmembers:        192.0.2.0/24^+\nmp-members:     2001:db8::/32\n\n",
+        RouteSet::CLASS
+    )]
+    #[case(
+        "mntner:         MAINT-EXAMPLE\nupd-to:         noc@example.net\nauth:           PGPKEY-1290F9D2\nmnt-by:         MAINT-EXAMPLE\n\n",
+        Mntner::CLASS
+    )]
+    #[case(
+        "person:         Jane Doe\naddress:        Packet Street 6\naddress:        Internet\nphone:          +1 555 0100\nnic-hdl:        JD1-RIPE\n\n",
+        Person::CLASS
+    )]
+    #[case(
+        "role:           Example NOC\naddress:        Packet Street 6\nnic-hdl:        EX1-RIPE\n\n",
+        Role::CLASS
+    )]
+    #[case(
+        "inetnum:        192.0.2.0 - 192.0.2.255\nnetname:        EXAMPLE-NET\ncountry:        NL\nstatus:         ASSIGNED PA\n\n",
+        Inetnum::CLASS
+    )]
+    #[case(
+        "inet6num:       2001:db8::/32\nnetname:        EXAMPLE-NET\nstatus:         ASSIGNED\n\n",
+        Inet6num::CLASS
+    )]
+    #[case(
+        "inet-rtr:       rtr.example.net\nlocal-as:       AS65536\nifaddr:         192.0.2.1 masklen 24\npeer:           BGP4 192.0.2.2 asno(AS1)\n\n",
+        InetRtr::CLASS
+    )]
+    #[case(
+        "peering-set:    PRNG-EXAMPLE\npeering:        AS1 at 192.0.2.1\n\n",
+        PeeringSet::CLASS
+    )]
+    #[case(
+        "filter-set:     FLTR-EXAMPLE\nfilter:         { 192.0.2.0/24^+ }\n\n",
+        FilterSet::CLASS
+    )]
+    #[case(
+        "key-cert:       PGPKEY-1290F9D2\nmethod:         PGP\ncertif:         -----BEGIN PGP PUBLIC KEY BLOCK-----\n                +\n                mQINBF...\n                -----END PGP PUBLIC KEY BLOCK-----\nsource:         RIPE\n\n",
+        KeyCert::CLASS
+    )]
+    /// Objects of each class are converted into their model and back into an equivalent object.
+    fn model_roundtrip(#[case] rpsl: &str, #[case] class: &str) {
+        fn roundtrip<M>(object: &Object) -> Object<'static>
+        where
+            M: for<'o> TryFrom<&'o Object<'o>, Error = FromObjectError>,
+            Object<'static>: From<M>,
+        {
+            M::try_from(object).unwrap().into()
+        }
+
+        let object = parse_object(rpsl).unwrap();
+        let converted = match class {
+            "aut-num" => roundtrip::<AutNum>(&object),
+            "as-set" => roundtrip::<AsSet>(&object),
+            "route" => roundtrip::<Route>(&object),
+            "route6" => roundtrip::<Route6>(&object),
+            "route-set" => roundtrip::<RouteSet>(&object),
+            "mntner" => roundtrip::<Mntner>(&object),
+            "person" => roundtrip::<Person>(&object),
+            "role" => roundtrip::<Role>(&object),
+            "inetnum" => roundtrip::<Inetnum>(&object),
+            "inet6num" => roundtrip::<Inet6num>(&object),
+            "inet-rtr" => roundtrip::<InetRtr>(&object),
+            "peering-set" => roundtrip::<PeeringSet>(&object),
+            "filter-set" => roundtrip::<FilterSet>(&object),
+            "key-cert" => roundtrip::<KeyCert>(&object),
+            _ => unreachable!(),
+        };
+        assert_eq!(converted, object);
+    }
+
+    #[test]
+    fn model_is_populated() {
+        let object = object! {
+            "as-set": "AS-EXAMPLE";
+            "descr": "Example customers";
+            "members": "AS65536, AS65537, # Customers", "AS-CUSTOMERS";
+            "mnt-by": "MAINT-EXAMPLE";
+            "created": "2024-01-01T00:00:00Z";
+            "source": "RIPE";
+        };
+        assert_eq!(
+            AsSet::try_from(&object).unwrap(),
+            AsSet {
+                as_set: "AS-EXAMPLE".parse().unwrap(),
+                descr: vec!["Example customers".to_string()],
+                members: vec![
+                    "AS65536".to_string(),
+                    "AS65537".to_string(),
+                    "AS-CUSTOMERS".to_string()
+                ],
+                mp_members: vec![],
+                mbrs_by_ref: vec![],
+                admin_c: vec![],
+                tech_c: vec![],
+                remarks: vec![],
+                notify: vec![],
+                mnt_by: vec!["MAINT-EXAMPLE".to_string()],
+                changed: vec![],
+                source: Some("RIPE".to_string()),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route6": "2001:db8::/32";
+            "origin": "AS65536";
+        },
+        FromObjectError::UnexpectedClass { expected: Name::new("route"), found: Name::new("route6") }
+    )]
+    #[case(
+        object! {
+            "route": "2001:db8::/32";
+            "origin": "AS65536";
+        },
+        FromObjectError::InvalidAttribute {
+            index: 0,
+            error: AttributeError::from(InvalidValueError::new(
+                &crate::Value::new_single("2001:db8::/32"),
+                "expected an IPv4 prefix"
+            )),
+        }
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "65536";
+        },
+        FromObjectError::InvalidAttribute {
+            index: 1,
+            error: AttributeError::from(InvalidValueError::new(
+                &crate::Value::new_single("65536"),
+                "invalid AS number `65536` at offset 0: expected an AS number, e.g. AS65536"
+            )),
+        }
+    )]
+    #[case(
+        object! { "route": "192.0.2.0/24"; },
+        FromObjectError::MissingAttribute(Name::new("origin"))
+    )]
+    fn invalid_route_is_expected_err(#[case] object: Object, #[case] expected: FromObjectError) {
+        assert_eq!(Route::try_from(&object).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn key_cert_certif_is_text() {
+        let object = parse_object(
+            "key-cert:       X509-1\ncertif:         -----BEGIN CERTIFICATE-----\ncertif:         MIIB...\ncertif:         -----END CERTIFICATE-----\n\n",
+        )
+        .unwrap();
+        let key_cert = KeyCert::try_from(&object).unwrap();
+        assert_eq!(
+            key_cert.certif,
+            "-----BEGIN CERTIFICATE-----\nMIIB...\n-----END CERTIFICATE-----"
+        );
+        assert_eq!(
+            Object::from(key_cert),
+            object! {
+                "key-cert": "X509-1";
+                "certif": "-----BEGIN CERTIFICATE-----", "MIIB...", "-----END CERTIFICATE-----";
+            }
+        );
+    }
+}
@@ -1,14 +1,18 @@
 use std::{
     borrow::Cow,
     fmt,
+    hash::{Hash, Hasher},
     ops::{Deref, Index},
 };
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 
 use super::Attribute;
-use crate::spec::{AttributeError, Raw, Specification};
+use crate::{
+    format::Formatter,
+    spec::{AttributeError, ObjectError, Raw, Specification, Validator},
+};
 
 /// A RPSL object.
 ///
@@ -188,10 +192,14 @@ pub struct Object<'a, Spec: Specification = Raw> {
 }
 
 impl<'a, Spec: Specification> Object<'a, Spec> {
-    /// Validate that all attributes of this object conform to a target specification.
+    /// Validate that this object and all of its attributes conform to a target specification.
+    ///
+    /// Each attribute is validated first, followed by the rules the target specification
+    /// defines for the object as a whole.
     ///
     /// # Errors
-    /// Returns an [`ObjectValidationError`] if any attribute fails to satisfy the target specification.
+    /// Returns an [`ObjectValidationError`] if any attribute or the object itself fails to satisfy
+    /// the target specification.
     ///
     /// # Examples
     /// ```
@@ -204,22 +212,65 @@ impl<'a, Spec: Specification> Object<'a, Spec> {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn validate<TargetSpec: Specification>(&self) -> Result<(), ObjectValidationError> {
-        let mut errors = Vec::new();
-        for (index, attribute) in self.attributes.iter().enumerate() {
-            if let Err(error) = attribute.validate::<TargetSpec>() {
-                errors.push((index, error));
-            }
-        }
+        self.check(
+            |attribute| TargetSpec::validate_attribute(&attribute.as_specification()),
+            |object| TargetSpec::validate_object(&object.as_specification()),
+        )
+    }
+
+    /// Validate that this object and all of its attributes conform to a validator selected at
+    /// runtime.
+    ///
+    /// This is the dynamic counterpart of [`Object::validate`].
+    ///
+    /// # Errors
+    /// Returns an [`ObjectValidationError`] if any attribute or the object itself fails to satisfy
+    /// the validator.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rpsl::{object, spec::{Builtin, Validator}};
+    /// let obj = object! {
+    ///     "role": "ACME Company";
+    ///     "address": "Packet Street 6";
+    /// };
+    /// let validator: Box<dyn Validator> = Box::new("rfc2622".parse::<Builtin>()?);
+    /// obj.validate_with(validator.as_ref())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_with(&self, validator: &dyn Validator) -> Result<(), ObjectValidationError> {
+        self.check(
+            |attribute| validator.check_attribute(attribute),
+            |object| validator.check_object(object),
+        )
+    }
 
-        if errors.is_empty() {
+    /// Check each attribute and then the object as a whole, collecting every error.
+    fn check(
+        &self,
+        attribute: impl Fn(&Attribute<'_>) -> Result<(), AttributeError>,
+        object: impl FnOnce(&Object<'_>) -> Result<(), Vec<ObjectError>>,
+    ) -> Result<(), ObjectValidationError> {
+        let candidate: Object<Raw> = self.as_specification();
+        let errors: Vec<(usize, AttributeError)> = candidate
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| attribute(item).err().map(|error| (index, error)))
+            .collect();
+        let object_errors = object(&candidate).err().unwrap_or_default();
+
+        if errors.is_empty() && object_errors.is_empty() {
             Ok(())
         } else {
-            Err(ObjectValidationError::new(errors))
+            Err(ObjectValidationError::new(errors, object_errors))
         }
     }
 
     /// Convert every attribute in this object into a target specification.
     ///
+    /// Only the attributes are validated, use [`Object::into_validated`] to also check the rules
+    /// of the specification concerning the object as a whole.
+    ///
     /// # Errors
     /// Returns the first [`AttributeError`] encountered. Use [`Object::validate`] to collect
     /// all attribute errors before converting.
@@ -249,6 +300,50 @@ impl<'a, Spec: Specification> Object<'a, Spec> {
         })
     }
 
+    /// Validate this object against a target specification and convert it if it conforms.
+    ///
+    /// Unlike [`Object::into_spec`], this checks the rules of the specification concerning the
+    /// object as a whole as well, exactly like [`Object::validate`].
+    ///
+    /// # Errors
+    /// Returns an [`ObjectValidationError`] containing every attribute and object error if the
+    /// object does not conform to the target specification.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rpsl::{object, Object, spec::Rfc2622Templates};
+    /// let obj = object! {
+    ///     "role": "ACME Company";
+    /// };
+    /// let error = obj.into_validated::<Rfc2622Templates>().unwrap_err();
+    /// assert!(!error.object_errors().is_empty());
+    /// ```
+    pub fn into_validated<TargetSpec: Specification>(
+        self,
+    ) -> Result<Object<'a, TargetSpec>, ObjectValidationError> {
+        self.validate::<TargetSpec>()?;
+        let Object { attributes, source } = self;
+        Ok(Object {
+            attributes: attributes
+                .into_iter()
+                .map(Attribute::into_specification)
+                .collect(),
+            source,
+        })
+    }
+
+    /// Borrow this object as a target specification without validating or copying it.
+    pub(crate) fn as_specification<TargetSpec: Specification>(&self) -> Object<'_, TargetSpec> {
+        Object {
+            attributes: self
+                .attributes
+                .iter()
+                .map(Attribute::as_specification)
+                .collect(),
+            source: self.source.as_deref().map(Cow::Borrowed),
+        }
+    }
+
     /// Create a new RPSL object from a vector of attributes.
     ///
     /// # Example
@@ -355,24 +450,65 @@ impl<'a, Spec: Specification> IntoIterator for Object<'a, Spec> {
     }
 }
 
-impl PartialEq for Object<'_> {
-    /// Compare two objects.
-    /// Since objects that are semantically equal may display differently, only `PartialEq` is implemented.
-    fn eq(&self, other: &Self) -> bool {
-        self.attributes == other.attributes
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de: 'a, 'a> Deserialize<'de> for Object<'a, Raw> {
+    /// Deserialize an object, borrowing its attributes from the input where possible.
+    ///
+    /// Since the source is not serialized, deserialized objects always display as formatted
+    /// from their attributes. Use [`Object::into_owned`] to detach an object from its input.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Object")]
+        struct Repr<'a> {
+            #[serde(borrow)]
+            attributes: Vec<Attribute<'a>>,
+        }
+
+        let Repr { attributes } = Repr::deserialize(deserializer)?;
+        Ok(Self {
+            attributes,
+            source: None,
+        })
+    }
+}
+
+impl<Spec: Specification, OtherSpec: Specification> PartialEq<Object<'_, OtherSpec>>
+    for Object<'_, Spec>
+{
+    /// Compare two objects by their attributes, regardless of their specification.
+    ///
+    /// The source an object was parsed from is not taken into consideration, meaning objects that
+    /// are equal may still display differently.
+    fn eq(&self, other: &Object<'_, OtherSpec>) -> bool {
+        self.attributes.len() == other.attributes.len()
+            && self
+                .attributes
+                .iter()
+                .zip(&other.attributes)
+                .all(|(a, b)| a == b)
     }
 }
 
-impl fmt::Display for Object<'_> {
+impl<Spec: Specification> Eq for Object<'_, Spec> {}
+
+impl<Spec: Specification> Hash for Object<'_, Spec> {
+    /// Hash the attributes of the object, ignoring its source to stay consistent with [`PartialEq`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.attributes.hash(state);
+    }
+}
+
+impl<Spec: Specification> fmt::Display for Object<'_, Spec> {
     /// Display the object as RPSL.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(source) = &self.source {
             write!(f, "{source}")
         } else {
-            for attribute in &self.attributes {
-                write!(f, "{attribute}")?;
-            }
-            writeln!(f)
+            Formatter::default().write_object(f, self)
         }
     }
 }
@@ -434,29 +570,45 @@ macro_rules! object {
     };
 }
 
-/// Contains all attribute validation errors for an [`Object`].
+/// Contains all attribute and object validation errors for an [`Object`].
 #[derive(Debug, thiserror::Error)]
-#[error("{num} attribute(s) failed validation", num = .errors.len())]
+#[error(
+    "{num} attribute(s) and {rules} object rule(s) failed validation",
+    num = .errors.len(),
+    rules = .object_errors.len()
+)]
 pub struct ObjectValidationError {
     /// Validation errors paired with the index of the offending attribute.
     errors: Vec<(usize, AttributeError)>,
+    /// Validation errors concerning the object as a whole.
+    object_errors: Vec<ObjectError>,
 }
 
 impl ObjectValidationError {
-    fn new(errors: Vec<(usize, AttributeError)>) -> Self {
-        Self { errors }
+    fn new(errors: Vec<(usize, AttributeError)>, object_errors: Vec<ObjectError>) -> Self {
+        Self {
+            errors,
+            object_errors,
+        }
     }
 
-    /// The number of attributes that failed validation.
+    /// The number of errors, counting both attribute and object errors.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.errors.len()
+        self.errors.len() + self.object_errors.len()
     }
 
-    /// Returns `true` if no attribute validation errors are contained.
+    /// Returns `true` if neither attribute nor object validation errors are contained.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.errors.is_empty()
+        self.errors.is_empty() && self.object_errors.is_empty()
+    }
+
+    /// The errors concerning the object as a whole, as reported by
+    /// [`Specification::validate_object`].
+    #[must_use]
+    pub fn object_errors(&self) -> &[ObjectError] {
+        &self.object_errors
     }
 
     /// Iterate over the attribute errors.
@@ -474,6 +626,12 @@ impl ObjectValidationError {
     pub fn into_errors(self) -> Vec<(usize, AttributeError)> {
         self.errors
     }
+
+    /// Return the errors concerning the object as a whole.
+    #[must_use]
+    pub fn into_object_errors(self) -> Vec<ObjectError> {
+        self.object_errors
+    }
 }
 
 #[cfg(test)]
@@ -481,10 +639,12 @@ mod tests {
     use rstest::*;
     #[cfg(feature = "json")]
     use serde_json::json;
+    #[cfg(feature = "serde")]
+    use serde_test::{assert_tokens, Token};
 
     use super::*;
     use crate::{
-        spec::{InvalidNameError, Rfc2622},
+        spec::{Builtin, InvalidNameError, Irrd, Rfc2622, Rfc2622Templates, Rfc4012, RipeDb},
         Name,
     };
 
@@ -717,6 +877,64 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[rstest]
+    #[case(
+        object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6", "", "Internet";
+        },
+        &[
+            Token::Struct { name: "Object", len: 1 },
+            Token::Str("attributes"),
+            Token::Seq { len: Some(2) },
+            Token::Struct { name: "Attribute", len: 2 },
+            Token::Str("name"),
+            Token::Str("role"),
+            Token::Str("values"),
+            Token::Seq { len: Some(1) },
+            Token::Some,
+            Token::Str("ACME Company"),
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::Struct { name: "Attribute", len: 2 },
+            Token::Str("name"),
+            Token::Str("address"),
+            Token::Str("values"),
+            Token::Seq { len: Some(3) },
+            Token::Some,
+            Token::Str("Packet Street 6"),
+            Token::None,
+            Token::Some,
+            Token::Str("Internet"),
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    )]
+    #[cfg(feature = "serde")]
+    /// Objects deserialize from the same shape they are serialized to.
+    fn object_serde_roundtrip(#[case] object: Object, #[case] tokens: &[Token]) {
+        assert_tokens(&object, tokens);
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6", "", "Internet";
+            "source": "RIPE";
+        }
+    )]
+    #[cfg(feature = "json")]
+    /// Objects written as JSON can be read back, borrowing from the JSON text.
+    fn object_json_roundtrip(#[case] object: Object) {
+        let json = serde_json::to_string(&object).unwrap();
+        let deserialized: Object = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, object);
+        assert_eq!(deserialized.to_string(), object.to_string());
+    }
+
     #[rstest]
     #[case(
         Object::new_parsed(
@@ -977,6 +1195,147 @@ mod tests {
         assert_eq!(error, expected);
     }
 
+    /// Requires a route to have exactly one origin and reports every `changed` attribute.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct RouteRules;
+
+    impl Specification for RouteRules {
+        fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+            Rfc2622::validate_attribute(&attribute.as_specification())
+        }
+
+        fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+            let mut errors = Vec::new();
+            if object.get("origin").len() != 1 {
+                errors.push(ObjectError::new("route must have exactly one origin"));
+            }
+            for (index, attribute) in object.iter().enumerate() {
+                if attribute.name == "changed" {
+                    errors.push(ObjectError::at(index, "`changed` is deprecated"));
+                }
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("origin", "AS65536"),
+            Attribute::unchecked_single("source", "TEST"),
+        ]),
+        vec![],
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("changed", "noc@example.com 20240101"),
+            Attribute::unchecked_single("source", "TEST"),
+        ]),
+        vec![
+            ObjectError::new("route must have exactly one origin"),
+            ObjectError::at(1, "`changed` is deprecated"),
+        ],
+    )]
+    fn object_validate_reports_object_errors(
+        #[case] object: Object,
+        #[case] expected: Vec<ObjectError>,
+    ) {
+        match object.validate::<RouteRules>() {
+            Ok(()) => assert!(expected.is_empty()),
+            Err(error) => {
+                assert_eq!(error.iter_errors().count(), 0);
+                assert_eq!(error.into_object_errors(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn object_validate_reports_attribute_and_object_errors_together() {
+        let object = Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("a", "invalid"),
+        ]);
+        let error = object.validate::<RouteRules>().unwrap_err();
+        assert_eq!(error.len(), 2);
+        assert_eq!(error.iter_errors().count(), 1);
+        assert_eq!(
+            error.object_errors(),
+            [ObjectError::new("route must have exactly one origin")]
+        );
+    }
+
+    #[rstest]
+    #[case(Object::new(vec![
+        Attribute::unchecked_single("route", "192.0.2.1/24"),
+        Attribute::unchecked_single("origin", "AS65536"),
+        Attribute::unchecked_single("a", "invalid"),
+        Attribute::unchecked_single("source", "EXAMPLE"),
+    ]))]
+    #[case(Object::new(vec![
+        Attribute::unchecked_single("role", "ACME Company"),
+        Attribute::unchecked_single("address", "Packet Street 6"),
+        Attribute::unchecked_single("source", "RIPE"),
+    ]))]
+    fn object_validate_with_builtin_matches_validate(#[case] object: Object) {
+        fn errors(
+            result: Result<(), ObjectValidationError>,
+        ) -> Option<(Vec<String>, Vec<ObjectError>)> {
+            result.err().map(|error| {
+                let attributes = error
+                    .iter_indexed()
+                    .map(|(index, error)| format!("{index}: {error}"))
+                    .collect();
+                (attributes, error.into_object_errors())
+            })
+        }
+
+        for (builtin, validated) in [
+            (Builtin::Raw, object.validate::<Raw>()),
+            (Builtin::Rfc2622, object.validate::<Rfc2622>()),
+            (
+                Builtin::Rfc2622Templates,
+                object.validate::<Rfc2622Templates>(),
+            ),
+            (Builtin::Rfc4012, object.validate::<Rfc4012>()),
+            (Builtin::RipeDb, object.validate::<RipeDb>()),
+            (Builtin::Irrd, object.validate::<Irrd>()),
+        ] {
+            assert_eq!(errors(object.validate_with(&builtin)), errors(validated));
+        }
+    }
+
+    #[test]
+    fn object_into_spec_does_not_check_object_rules() {
+        let object = Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("origin", "AS65536"),
+            Attribute::unchecked_single("origin", "AS65537"),
+        ]);
+        assert!(object.validate::<RouteRules>().is_err());
+        assert!(object.into_spec::<RouteRules>().is_ok());
+    }
+
+    #[test]
+    fn object_into_validated_checks_object_rules() {
+        let object = Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("origin", "AS65536"),
+            Attribute::unchecked_single("origin", "AS65537"),
+        ]);
+        let expected = object.validate::<RouteRules>().unwrap_err();
+        let error = object.clone().into_validated::<RouteRules>().unwrap_err();
+        assert_eq!(error.object_errors(), expected.object_errors());
+
+        let valid = Object::new(object.attributes[..2].to_vec());
+        assert_eq!(valid.clone().into_validated::<RouteRules>().unwrap(), valid);
+    }
+
     #[rstest]
     #[case(
         Object::new(vec![
@@ -1101,4 +1460,59 @@ mod tests {
     fn ne_objects_are_ne(#[case] object_1: Object, #[case] object_2: Object) {
         assert_ne!(object_1, object_2);
     }
+
+    #[test]
+    /// Objects converted into a different specification still evaluate as equal to the original.
+    fn objects_of_different_specs_are_eq() {
+        let raw = object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6", "128 Series of Tubes", "Internet";
+        };
+        let validated = raw.clone().into_spec::<Rfc2622>().unwrap();
+        assert_eq!(validated, raw);
+        assert_eq!(raw, validated);
+    }
+
+    #[test]
+    /// Objects that are equal hash equally, regardless of whether they were parsed.
+    fn eq_objects_hash_eq() {
+        use std::collections::HashSet;
+
+        let parsed = Object::new_parsed(
+            concat!(
+                "role:           ACME Company\n",
+                "address:        Packet Street 6\n",
+                "\n"
+            ),
+            vec![
+                Attribute::unchecked_single("role", "ACME Company"),
+                Attribute::unchecked_single("address", "Packet Street 6"),
+            ],
+        );
+        let created = object! {
+            "role": "ACME Company";
+            "address": "Packet Street 6";
+        };
+
+        let set: HashSet<Object> = [parsed, created].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn object_of_typed_spec_displays_as_rpsl() {
+        let object = object! {
+            "role": "ACME Company";
+            "source": "RIPE";
+        }
+        .into_spec::<Rfc2622>()
+        .unwrap();
+        assert_eq!(
+            object.to_string(),
+            concat!(
+                "role:           ACME Company\n",
+                "source:         RIPE\n",
+                "\n"
+            )
+        );
+    }
 }
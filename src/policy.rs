@@ -0,0 +1,1402 @@
+//! Parsing of routing policy expressions.
+//!
+//! The `import`, `export` and `default` attributes of an `aut-num` object, as well as their
+//! multiprotocol counterparts defined by RFC 4012, describe the routing policy of an autonomous
+//! system. This module parses their values into an abstract syntax tree.
+//!
+//! Policies are parsed from text, which for attribute values spanning multiple lines can be
+//! created using [`text`]. All nodes of the tree contain a [`Span`] of the text they were parsed
+//! from, so the tree can be related back to its source. As comments are removed and lines are
+//! joined, these offsets differ from those in the lines of the value, which are obtained using
+//! [`position`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     policy::{self, FilterKind, Peering, Policy},
+//!     types::AsNumber,
+//!     Value,
+//! };
+//!
+//! let value: Value = vec![
+//!     "from AS65537 action pref = 100;",
+//!     "accept AS-CUSTOMERS AND NOT {0.0.0.0/0^0-7}",
+//! ]
+//! .into();
+//! let text = policy::text(&value);
+//! let import = Policy::parse_import(&text)?;
+//!
+//! let factor = &import.expression.factors[0];
+//! assert!(matches!(
+//!     factor.peerings[0].peering,
+//!     Peering::Expression { .. }
+//! ));
+//! assert_eq!(factor.peerings[0].actions[0].attribute, "pref");
+//! assert!(matches!(factor.filter.kind, FilterKind::And(..)));
+//! assert_eq!(&text[factor.filter.span.clone()], "AS-CUSTOMERS AND NOT {0.0.0.0/0^0-7}");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Structured policies
+//!
+//! Policies consisting of multiple terms, combined using `refine` and `except`, are parsed into
+//! a [`PolicyExpression`] containing the [`Refinement`] of its term.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::policy::{Policy, RefineOperator, Afi};
+//!
+//! let import = Policy::parse_mp_import(
+//!     "afi ipv6.unicast { from AS-ANY action pref = 1; accept community(65536:10); } \
+//!      refine { from AS65537 accept AS65537; from AS65538 accept AS65538; }",
+//! )?;
+//! assert_eq!(import.expression.afi, vec![Afi::Ipv6Unicast]);
+//! let refinement = import.expression.refinement.unwrap();
+//! assert_eq!(refinement.operator, RefineOperator::Refine);
+//! assert_eq!(refinement.expression.factors.len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fmt, net::IpAddr, ops::Range, str::FromStr};
+
+use winnow::{
+    ascii::{multispace0, Caseless},
+    combinator::{
+        alt, cut_err, delimited, eof, fail, not, opt, peek, preceded, repeat, separated, terminated,
+    },
+    error::{ContextError, ErrMode, StrContext, StrContextValue},
+    stream::LocatingSlice,
+    token::{one_of, take_till, take_while},
+    ModalResult, Parser,
+};
+
+use crate::{
+    spec::Specification,
+    types::{
+        self, complete, range_operator, AddressPrefixRange, AsNumber, AsSetName, FilterSetName,
+        PeeringSetName, RangeOperator, RouteSetName, TypeError,
+    },
+    Value,
+};
+
+/// A range of byte offsets into the text a node was parsed from.
+pub type Span = Range<usize>;
+
+/// The text of a policy value, as parsed by this module.
+///
+/// Comments are removed and lines are joined by spaces.
+#[must_use]
+pub fn text<Spec: Specification>(value: &Value<'_, Spec>) -> String {
+    types::content(value)
+}
+
+/// The position of an offset into the [`text`] of a value in the lines of the value.
+///
+/// Returns the index of the line and the byte offset within it, or `None` if the offset is
+/// beyond the end of the text. An offset at the end of a line, such as the end of a [`Span`],
+/// is placed on that line rather than at the start of the next one.
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use rpsl::{policy::{self, Filter}, Value};
+///
+/// let value: Value = vec!["AS-FOO # Customers", "  AND AS-BAR"].into();
+/// let filter = Filter::parse(&policy::text(&value))?;
+/// assert_eq!(policy::position(&value, filter.span.start), Some((0, 0)));
+/// assert_eq!(policy::position(&value, filter.span.end), Some((1, 12)));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn position<Spec: Specification>(
+    value: &Value<'_, Spec>,
+    offset: usize,
+) -> Option<(usize, usize)> {
+    let mut start = 0;
+    for (index, line) in value.values().into_iter().enumerate() {
+        let line = line.unwrap_or_default();
+        let content = line.split_once('#').map_or(line, |(content, _)| content);
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let end = start + trimmed.len();
+        if offset <= end {
+            let column = content.len() - content.trim_start().len();
+            return Some((index, column + offset.saturating_sub(start)));
+        }
+        // Lines are joined by a single space.
+        start = end + 1;
+    }
+    None
+}
+
+/// An `import` or `export` policy, or their multiprotocol counterparts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// The protocol routes are exchanged with, e.g. `BGP4` in `protocol BGP4`.
+    pub protocol: Option<String>,
+    /// The protocol routes are imported into or exported from, e.g. `OSPF` in `into OSPF`.
+    pub into: Option<String>,
+    /// The expression describing the policy.
+    pub expression: PolicyExpression,
+    /// The span of the policy.
+    pub span: Span,
+}
+
+impl Policy {
+    /// Parse the value of an `import` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid import policy.
+    pub fn parse_import(text: &str) -> Result<Self, TypeError> {
+        parse(policy(Syntax::IMPORT), "import policy", text)
+    }
+
+    /// Parse the value of an `export` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid export policy.
+    pub fn parse_export(text: &str) -> Result<Self, TypeError> {
+        parse(policy(Syntax::EXPORT), "export policy", text)
+    }
+
+    /// Parse the value of an `mp-import` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid multiprotocol import policy.
+    pub fn parse_mp_import(text: &str) -> Result<Self, TypeError> {
+        parse(policy(Syntax::MP_IMPORT), "mp-import policy", text)
+    }
+
+    /// Parse the value of an `mp-export` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid multiprotocol export policy.
+    pub fn parse_mp_export(text: &str) -> Result<Self, TypeError> {
+        parse(policy(Syntax::MP_EXPORT), "mp-export policy", text)
+    }
+}
+
+/// A `default` policy, or its multiprotocol counterpart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultPolicy {
+    /// The address families the policy applies to, empty if not restricted.
+    pub afi: Vec<Afi>,
+    /// The peering default routes are received from, e.g. `to AS65536 action pref = 100;`.
+    pub peering: PeeringAction,
+    /// The filter selecting the routes used as default, e.g. `ANY` in `networks ANY`.
+    pub networks: Option<Filter>,
+    /// The span of the policy.
+    pub span: Span,
+}
+
+impl DefaultPolicy {
+    /// Parse the value of a `default` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid default policy.
+    pub fn parse(text: &str) -> Result<Self, TypeError> {
+        parse(default_policy(false), "default policy", text)
+    }
+
+    /// Parse the value of an `mp-default` attribute.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid multiprotocol default policy.
+    pub fn parse_mp(text: &str) -> Result<Self, TypeError> {
+        parse(default_policy(true), "mp-default policy", text)
+    }
+}
+
+/// A policy term, optionally refined by another expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyExpression {
+    /// The address families the expression applies to, empty if not restricted.
+    pub afi: Vec<Afi>,
+    /// The factors of the term, of which there are multiple if enclosed in braces.
+    pub factors: Vec<PolicyFactor>,
+    /// The expression refining the term, e.g. `refine { ... }` or `except { ... }`.
+    pub refinement: Option<Box<Refinement>>,
+    /// The span of the expression.
+    pub span: Span,
+}
+
+/// The refinement of a policy term by another expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refinement {
+    /// How the expression refines the term.
+    pub operator: RefineOperator,
+    /// The refining expression.
+    pub expression: PolicyExpression,
+}
+
+/// How a policy term is refined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefineOperator {
+    /// `refine`, the refining expression applies to the routes matched by the term.
+    Refine,
+    /// `except`, the refining expression overrides the term for the routes it matches.
+    Except,
+}
+
+/// A policy factor, the peerings routes are exchanged with and the filter they must match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyFactor {
+    /// The peerings, introduced by `from` in import and `to` in export policies.
+    pub peerings: Vec<PeeringAction>,
+    /// The filter, introduced by `accept` in import and `announce` in export policies.
+    pub filter: Filter,
+    /// The span of the factor.
+    pub span: Span,
+}
+
+/// A peering and the actions applied to routes exchanged with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeeringAction {
+    /// The peering.
+    pub peering: Peering,
+    /// The actions, introduced by `action`.
+    pub actions: Vec<Action>,
+    /// The span of the peering and its actions.
+    pub span: Span,
+}
+
+/// A peering with one or more autonomous systems.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Peering {
+    /// A reference to a `peering-set`.
+    Set(PeeringSetName),
+    /// An expression describing the peering.
+    Expression {
+        /// The autonomous systems peered with.
+        as_expression: AsExpression,
+        /// The routers of the peer autonomous systems.
+        remote_router: Option<RouterExpression>,
+        /// The local routers, introduced by `at`.
+        local_router: Option<RouterExpression>,
+    },
+}
+
+impl Peering {
+    /// Parse a peering, such as the value of the `peering` attribute of a `peering-set`.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid peering.
+    pub fn parse(text: &str) -> Result<Self, TypeError> {
+        parse(peering, "peering", text)
+    }
+}
+
+/// An expression over autonomous systems, e.g. `AS-CUSTOMERS EXCEPT AS65536`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsExpression {
+    /// A single autonomous system.
+    AsNumber(AsNumber),
+    /// The members of an `as-set`.
+    AsSet(AsSetName),
+    /// The autonomous systems contained in both expressions.
+    And(Box<AsExpression>, Box<AsExpression>),
+    /// The autonomous systems contained in either expression.
+    Or(Box<AsExpression>, Box<AsExpression>),
+    /// The autonomous systems of the first expression that are not contained in the second.
+    Except(Box<AsExpression>, Box<AsExpression>),
+}
+
+/// An expression over routers, e.g. `192.0.2.1 OR rtr.example.net`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterExpression {
+    /// A router identified by its address.
+    Address(IpAddr),
+    /// An `inet-rtr` or `rtr-set` name.
+    Name(String),
+    /// The routers contained in both expressions.
+    And(Box<RouterExpression>, Box<RouterExpression>),
+    /// The routers contained in either expression.
+    Or(Box<RouterExpression>, Box<RouterExpression>),
+    /// The routers of the first expression that are not contained in the second.
+    Except(Box<RouterExpression>, Box<RouterExpression>),
+}
+
+/// An action applied to the attributes of a route, e.g. `pref = 100` or `community.append(65536:1)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Action {
+    /// The route attribute acted on, e.g. `pref`.
+    pub attribute: String,
+    /// The operation performed on the attribute.
+    pub operation: ActionOperation,
+    /// The span of the action.
+    pub span: Span,
+}
+
+/// An operation performed on a route attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOperation {
+    /// An operator, e.g. `=` or `.=`, applied with a value.
+    Operator {
+        /// The operator.
+        operator: String,
+        /// The value of the right-hand side.
+        value: String,
+    },
+    /// A method called with arguments.
+    Method {
+        /// The name of the method, e.g. `append`.
+        method: String,
+        /// The arguments of the call.
+        arguments: Vec<String>,
+    },
+}
+
+/// A filter matching routes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    /// What the filter matches.
+    pub kind: FilterKind,
+    /// The span of the filter.
+    pub span: Span,
+}
+
+impl Filter {
+    /// Parse a filter, such as the value of the `filter` attribute of a `filter-set`.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the text is not a valid filter, or nests more than 64 filters
+    /// in `()` or `NOT`.
+    pub fn parse(text: &str) -> Result<Self, TypeError> {
+        parse(filter, "filter", text)
+    }
+}
+
+/// What a filter matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterKind {
+    /// `ANY`, matching all routes.
+    Any,
+    /// `PeerAS`, matching routes originated by the peer autonomous system.
+    PeerAs,
+    /// Routes originated by an autonomous system, with a range operator applied to them.
+    AsNumber(AsNumber, RangeOperator),
+    /// Routes originated by the members of an `as-set`, with a range operator applied to them.
+    AsSet(AsSetName, RangeOperator),
+    /// The members of a `route-set`, with a range operator applied to them.
+    RouteSet(RouteSetName, RangeOperator),
+    /// The routes matched by the filter of a `filter-set`.
+    FilterSet(FilterSetName),
+    /// An explicit list of address prefix ranges, with a range operator applied to them.
+    Prefixes(Vec<AddressPrefixRange>, RangeOperator),
+    /// An AS path regular expression, the text between `<` and `>`.
+    AsPath(String),
+    /// A filter on the communities of a route, e.g. `community(65536:1)`.
+    Community {
+        /// The method applied, e.g. `contains`, or `None` for `community(...)`.
+        method: Option<String>,
+        /// The communities passed to the method.
+        arguments: Vec<String>,
+    },
+    /// Routes not matched by the filter.
+    Not(Box<Filter>),
+    /// Routes matched by both filters.
+    And(Box<Filter>, Box<Filter>),
+    /// Routes matched by either filter.
+    Or(Box<Filter>, Box<Filter>),
+}
+
+/// An address family identifier as defined by RFC 4012, e.g. `ipv6.unicast`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Afi {
+    /// `ipv4`
+    Ipv4,
+    /// `ipv4.unicast`
+    Ipv4Unicast,
+    /// `ipv4.multicast`
+    Ipv4Multicast,
+    /// `ipv6`
+    Ipv6,
+    /// `ipv6.unicast`
+    Ipv6Unicast,
+    /// `ipv6.multicast`
+    Ipv6Multicast,
+    /// `any`
+    Any,
+    /// `any.unicast`
+    AnyUnicast,
+    /// `any.multicast`
+    AnyMulticast,
+}
+
+impl Afi {
+    const ALL: [(Self, &'static str); 9] = [
+        (Self::Ipv4, "ipv4"),
+        (Self::Ipv4Unicast, "ipv4.unicast"),
+        (Self::Ipv4Multicast, "ipv4.multicast"),
+        (Self::Ipv6, "ipv6"),
+        (Self::Ipv6Unicast, "ipv6.unicast"),
+        (Self::Ipv6Multicast, "ipv6.multicast"),
+        (Self::Any, "any"),
+        (Self::AnyUnicast, "any.unicast"),
+        (Self::AnyMulticast, "any.multicast"),
+    ];
+
+    /// Whether the address family includes IPv4.
+    #[must_use]
+    pub const fn includes_ipv4(self) -> bool {
+        !matches!(self, Self::Ipv6 | Self::Ipv6Unicast | Self::Ipv6Multicast)
+    }
+
+    /// Whether the address family includes IPv6.
+    #[must_use]
+    pub const fn includes_ipv6(self) -> bool {
+        !matches!(self, Self::Ipv4 | Self::Ipv4Unicast | Self::Ipv4Multicast)
+    }
+}
+
+impl FromStr for Afi {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+            .map(|(afi, _)| *afi)
+            .ok_or_else(|| {
+                TypeError::new(
+                    "afi",
+                    s,
+                    0,
+                    "expected one of ipv4, ipv6 or any, optionally followed by .unicast or .multicast",
+                )
+            })
+    }
+}
+
+impl fmt::Display for Afi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = Self::ALL
+            .iter()
+            .find(|(afi, _)| afi == self)
+            .expect("all variants are named");
+        f.write_str(name)
+    }
+}
+
+type Input<'s> = LocatingSlice<&'s str>;
+
+/// Words with a meaning in policy expressions, which cannot be used as names.
+const KEYWORDS: [&str; 15] = [
+    "accept", "action", "afi", "and", "announce", "at", "except", "from", "into", "networks",
+    "not", "or", "protocol", "refine", "to",
+];
+
+/// The keywords that differ between import and export policies and whether `afi` is allowed.
+#[derive(Debug, Clone, Copy)]
+struct Syntax {
+    peering: &'static str,
+    filter: &'static str,
+    multiprotocol: bool,
+}
+
+impl Syntax {
+    const IMPORT: Self = Self {
+        peering: "from",
+        filter: "accept",
+        multiprotocol: false,
+    };
+    const EXPORT: Self = Self {
+        peering: "to",
+        filter: "announce",
+        multiprotocol: false,
+    };
+    const MP_IMPORT: Self = Self {
+        multiprotocol: true,
+        ..Self::IMPORT
+    };
+    const MP_EXPORT: Self = Self {
+        multiprotocol: true,
+        ..Self::EXPORT
+    };
+}
+
+/// Parse the complete text using the parser.
+fn parse<'s, O>(
+    parser: impl Parser<Input<'s>, O, ErrMode<ContextError>>,
+    kind: &'static str,
+    text: &'s str,
+) -> Result<O, TypeError> {
+    terminated(parser, multispace0)
+        .parse(LocatingSlice::new(text))
+        .map_err(|e| TypeError::from_context(kind, text, e.offset(), e.inner()))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-_:./^+".contains(c)
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+/// Parse a keyword, preceded by optional whitespace.
+fn keyword<'s>(keyword: &'static str) -> impl Parser<Input<'s>, (), ErrMode<ContextError>> {
+    preceded(
+        multispace0,
+        terminated(Caseless(keyword), not(one_of(is_word_char))),
+    )
+    .void()
+}
+
+/// Parse a word that is not a keyword, without preceding whitespace.
+fn word<'s>(input: &mut Input<'s>) -> ModalResult<&'s str> {
+    take_while(1.., is_word_char)
+        .verify(|word: &str| !is_keyword(word))
+        .parse_next(input)
+}
+
+/// Parse a character, preceded by optional whitespace.
+fn symbol<'s>(c: char) -> impl Parser<Input<'s>, char, ErrMode<ContextError>> {
+    preceded(multispace0, c)
+}
+
+fn policy<'s>(syntax: Syntax) -> impl Parser<Input<'s>, Policy, ErrMode<ContextError>> {
+    move |input: &mut Input<'s>| {
+        multispace0.parse_next(input)?;
+        let named = |name| preceded(keyword(name), cut_err(preceded(multispace0, word)));
+        (
+            opt(named("protocol")),
+            opt(named("into")),
+            |input: &mut Input<'s>| expression(syntax, input),
+        )
+            .with_span()
+            .map(|((protocol, into, expression), span)| Policy {
+                protocol: protocol.map(ToOwned::to_owned),
+                into: into.map(ToOwned::to_owned),
+                expression,
+                span,
+            })
+            .parse_next(input)
+    }
+}
+
+fn default_policy<'s>(
+    multiprotocol: bool,
+) -> impl Parser<Input<'s>, DefaultPolicy, ErrMode<ContextError>> {
+    move |input: &mut Input<'s>| {
+        multispace0.parse_next(input)?;
+        (
+            |input: &mut Input<'s>| afi_list(multiprotocol, input),
+            preceded(multispace0, peering_action("to")),
+            opt(preceded(keyword("networks"), cut_err(filter))),
+        )
+            .with_span()
+            .map(|((afi, peering, networks), span)| DefaultPolicy {
+                afi,
+                peering,
+                networks,
+                span,
+            })
+            .parse_next(input)
+    }
+}
+
+fn expression(syntax: Syntax, input: &mut Input<'_>) -> ModalResult<PolicyExpression> {
+    multispace0.parse_next(input)?;
+    let refine_operator = alt((
+        keyword("refine").value(RefineOperator::Refine),
+        keyword("except").value(RefineOperator::Except),
+    ));
+    (
+        |input: &mut Input<'_>| afi_list(syntax.multiprotocol, input),
+        |input: &mut Input<'_>| term(syntax, input),
+        opt((
+            refine_operator,
+            cut_err(|input: &mut Input<'_>| expression(syntax, input)),
+        )),
+    )
+        .with_span()
+        .map(|((afi, factors, refinement), span)| PolicyExpression {
+            afi,
+            factors,
+            refinement: refinement.map(|(operator, expression)| {
+                Box::new(Refinement {
+                    operator,
+                    expression,
+                })
+            }),
+            span,
+        })
+        .parse_next(input)
+}
+
+/// Parse the optional `afi` list of a multiprotocol expression.
+fn afi_list(multiprotocol: bool, input: &mut Input<'_>) -> ModalResult<Vec<Afi>> {
+    if !multiprotocol {
+        return Ok(Vec::new());
+    }
+    let afi = preceded(
+        multispace0,
+        take_while(1.., is_word_char).try_map(str::parse::<Afi>),
+    );
+    opt(preceded(
+        keyword("afi"),
+        cut_err(separated(1.., afi, symbol(','))),
+    ))
+    .map(Option::unwrap_or_default)
+    .parse_next(input)
+}
+
+/// Parse a single policy factor or a list of them enclosed in braces.
+fn term(syntax: Syntax, input: &mut Input<'_>) -> ModalResult<Vec<PolicyFactor>> {
+    let factor = |input: &mut Input<'_>| factor(syntax, input);
+    alt((
+        delimited(
+            symbol('{'),
+            repeat(1.., terminated(factor, opt(symbol(';')))),
+            cut_err(symbol('}')),
+        ),
+        terminated(factor, opt(symbol(';'))).map(|factor| vec![factor]),
+    ))
+    .parse_next(input)
+}
+
+fn factor(syntax: Syntax, input: &mut Input<'_>) -> ModalResult<PolicyFactor> {
+    multispace0.parse_next(input)?;
+    peek(keyword(syntax.peering))
+        .context(StrContext::Expected(StrContextValue::StringLiteral(
+            syntax.peering,
+        )))
+        .parse_next(input)?;
+    (
+        repeat(1.., preceded(multispace0, peering_action(syntax.peering))),
+        preceded(
+            keyword(syntax.filter).context(StrContext::Expected(StrContextValue::StringLiteral(
+                syntax.filter,
+            ))),
+            cut_err(filter),
+        ),
+    )
+        .with_span()
+        .map(|((peerings, filter), span)| PolicyFactor {
+            peerings,
+            filter,
+            span,
+        })
+        .parse_next(input)
+}
+
+/// Parse a peering introduced by the keyword, followed by optional actions.
+fn peering_action<'s>(
+    keyword_: &'static str,
+) -> impl Parser<Input<'s>, PeeringAction, ErrMode<ContextError>> {
+    (
+        preceded(keyword(keyword_), cut_err(peering)),
+        opt(preceded(keyword("action"), cut_err(repeat(1.., action)))),
+    )
+        .with_span()
+        .map(|((peering, actions), span)| PeeringAction {
+            peering,
+            actions: actions.unwrap_or_default(),
+            span,
+        })
+}
+
+fn peering(input: &mut Input<'_>) -> ModalResult<Peering> {
+    multispace0.parse_next(input)?;
+    let router = |input: &mut Input<'_>| preceded(multispace0, router_expression).parse_next(input);
+    alt((
+        terminated(word.try_map(str::parse), not(one_of(is_word_char))).map(Peering::Set),
+        (
+            as_expression,
+            opt(router),
+            opt(preceded(keyword("at"), cut_err(router))),
+        )
+            .map(
+                |(as_expression, remote_router, local_router)| Peering::Expression {
+                    as_expression,
+                    remote_router,
+                    local_router,
+                },
+            ),
+    ))
+    .context(StrContext::Label("peering"))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "an AS number, as-set or peering-set name",
+    )))
+    .parse_next(input)
+}
+
+fn closing_parenthesis(input: &mut Input<'_>) -> ModalResult<char> {
+    symbol(')')
+        .context(StrContext::Expected(StrContextValue::CharLiteral(')')))
+        .parse_next(input)
+}
+
+/// Parse an expression combining operands using `AND`, `OR` and `EXCEPT`.
+///
+/// `AND` and `EXCEPT` bind more tightly than `OR`.
+fn binary_expression<'s, T>(
+    operand: fn(&mut Input<'s>) -> ModalResult<T>,
+    and: fn(Box<T>, Box<T>) -> T,
+    or: fn(Box<T>, Box<T>) -> T,
+    except: fn(Box<T>, Box<T>) -> T,
+    input: &mut Input<'s>,
+) -> ModalResult<T> {
+    let conjunction = |input: &mut Input<'s>| {
+        let mut left = operand(input)?;
+        while let Some(combine) = opt(alt((
+            keyword("and").value(and),
+            keyword("except").value(except),
+        )))
+        .parse_next(input)?
+        {
+            let right = operand(input)?;
+            left = combine(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    };
+
+    let mut left = conjunction(input)?;
+    while opt(keyword("or")).parse_next(input)?.is_some() {
+        let right = cut_err(conjunction).parse_next(input)?;
+        left = or(Box::new(left), Box::new(right));
+    }
+    Ok(left)
+}
+
+fn as_expression(input: &mut Input<'_>) -> ModalResult<AsExpression> {
+    binary_expression(
+        as_operand,
+        AsExpression::And,
+        AsExpression::Or,
+        AsExpression::Except,
+        input,
+    )
+}
+
+fn as_operand(input: &mut Input<'_>) -> ModalResult<AsExpression> {
+    preceded(
+        multispace0,
+        alt((
+            delimited('(', as_expression, cut_err(closing_parenthesis)),
+            word.try_map(|word| match word.parse::<AsNumber>() {
+                Ok(as_number) => Ok(AsExpression::AsNumber(as_number)),
+                Err(_) => word.parse::<AsSetName>().map(AsExpression::AsSet),
+            }),
+        )),
+    )
+    .parse_next(input)
+}
+
+fn router_expression(input: &mut Input<'_>) -> ModalResult<RouterExpression> {
+    binary_expression(
+        router_operand,
+        RouterExpression::And,
+        RouterExpression::Or,
+        RouterExpression::Except,
+        input,
+    )
+}
+
+fn router_operand(input: &mut Input<'_>) -> ModalResult<RouterExpression> {
+    preceded(
+        multispace0,
+        alt((
+            delimited('(', router_expression, cut_err(closing_parenthesis)),
+            word.map(|word| match word.parse() {
+                Ok(address) => RouterExpression::Address(address),
+                Err(_) => RouterExpression::Name(word.to_string()),
+            }),
+        )),
+    )
+    .parse_next(input)
+}
+
+fn action(input: &mut Input<'_>) -> ModalResult<Action> {
+    multispace0.parse_next(input)?;
+    let attribute = take_while(1.., |c: char| {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_'
+    })
+    .verify(|attribute: &str| !is_keyword(attribute));
+    let method = (
+        preceded('.', take_while(1.., |c: char| c.is_ascii_alphanumeric())),
+        delimited(symbol('('), take_till(0.., ')'), cut_err(')')),
+    )
+        .map(
+            |(method, arguments): (&str, &str)| ActionOperation::Method {
+                method: method.to_string(),
+                arguments: list(arguments),
+            },
+        );
+    let operator = (
+        preceded(
+            multispace0,
+            alt(("<<=", ">>=", ".=", "+=", "-=", "*=", "/=", "=")),
+        ),
+        cut_err(take_till(1.., ';')),
+    )
+        .map(
+            |(operator, value): (&str, &str)| ActionOperation::Operator {
+                operator: operator.to_string(),
+                value: value.trim().to_string(),
+            },
+        );
+
+    terminated(
+        (attribute, cut_err(alt((method, operator))))
+            .with_span()
+            .map(|((attribute, operation), span)| Action {
+                attribute: attribute.to_string(),
+                operation,
+                span,
+            }),
+        opt(symbol(';')),
+    )
+    .context(StrContext::Label("action"))
+    .parse_next(input)
+}
+
+/// Split a comma separated list into its trimmed, non-empty items.
+fn list(items: &str) -> Vec<String> {
+    items
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// The maximum number of filters in `()` or negated by `NOT` nested in each other.
+const MAX_FILTER_DEPTH: usize = 64;
+
+/// Parse a filter, in which `AND` binds more tightly than `OR`.
+///
+/// Filters placed next to each other without an operator are combined using `OR`.
+fn filter(input: &mut Input<'_>) -> ModalResult<Filter> {
+    or_filter(0, input)
+}
+
+/// Parse a filter nested in `depth` filters in `()` or negated by `NOT`.
+fn or_filter(depth: usize, input: &mut Input<'_>) -> ModalResult<Filter> {
+    let end = preceded(
+        multispace0,
+        alt((
+            one_of([';', '}', ')']).void(),
+            eof.void(),
+            keyword("refine"),
+            keyword("except"),
+        )),
+    );
+    let mut end = opt(peek(end));
+
+    let mut and_filter = |input: &mut Input<'_>| and_filter(depth, input);
+    let mut left = and_filter(input)?;
+    loop {
+        let right = if opt(keyword("or")).parse_next(input)?.is_some() {
+            cut_err(&mut and_filter).parse_next(input)?
+        } else if end.parse_next(input)?.is_some() {
+            break;
+        } else {
+            cut_err(&mut and_filter).parse_next(input)?
+        };
+        left = Filter {
+            span: left.span.start..right.span.end,
+            kind: FilterKind::Or(Box::new(left), Box::new(right)),
+        };
+    }
+    Ok(left)
+}
+
+fn and_filter(depth: usize, input: &mut Input<'_>) -> ModalResult<Filter> {
+    let not_filter = |input: &mut Input<'_>| not_filter(depth, input);
+    let mut left = not_filter(input)?;
+    while opt(keyword("and")).parse_next(input)?.is_some() {
+        let right = cut_err(not_filter).parse_next(input)?;
+        left = Filter {
+            span: left.span.start..right.span.end,
+            kind: FilterKind::And(Box::new(left), Box::new(right)),
+        };
+    }
+    Ok(left)
+}
+
+fn not_filter(depth: usize, input: &mut Input<'_>) -> ModalResult<Filter> {
+    multispace0.parse_next(input)?;
+    if depth > MAX_FILTER_DEPTH {
+        return cut_err(fail)
+            .context(StrContext::Label("filter"))
+            .context(StrContext::Expected(StrContextValue::Description(
+                "at most 64 filters nested in () or NOT",
+            )))
+            .parse_next(input);
+    }
+    let nested = |input: &mut Input<'_>| not_filter(depth + 1, input);
+    alt((
+        (keyword("not"), cut_err(nested))
+            .with_span()
+            .map(|(((), filter), span)| Filter {
+                kind: FilterKind::Not(Box::new(filter)),
+                span,
+            }),
+        |input: &mut Input<'_>| primary_filter(depth, input),
+    ))
+    .parse_next(input)
+}
+
+fn primary_filter(depth: usize, input: &mut Input<'_>) -> ModalResult<Filter> {
+    let nested = |input: &mut Input<'_>| or_filter(depth + 1, input);
+    alt((
+        delimited('(', nested, cut_err(closing_parenthesis)),
+        alt((prefix_list, as_path, community, filter_word))
+            .with_span()
+            .map(|(kind, span)| Filter { kind, span }),
+    ))
+    .context(StrContext::Label("filter"))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "ANY, PeerAS, an AS number, a set name, a prefix list, an AS path or a community",
+    )))
+    .parse_next(input)
+}
+
+/// Parse a list of address prefix ranges, e.g. `{ 192.0.2.0/24^+, 198.51.100.0/24 }^-`.
+fn prefix_list(input: &mut Input<'_>) -> ModalResult<FilterKind> {
+    let prefix_range = preceded(
+        (multispace0, not('}')),
+        cut_err(
+            take_till(1.., |c: char| c.is_whitespace() || c == ',' || c == '}')
+                .try_map(str::parse::<AddressPrefixRange>),
+        ),
+    );
+    (
+        preceded('{', separated(0.., prefix_range, symbol(','))),
+        cut_err(symbol('}')),
+        operator,
+    )
+        .map(|(prefixes, _, operator)| FilterKind::Prefixes(prefixes, operator))
+        .parse_next(input)
+}
+
+/// Parse an optional range operator directly following a filter.
+fn operator(input: &mut Input<'_>) -> ModalResult<RangeOperator> {
+    take_while(0.., |c: char| c.is_ascii_digit() || "^+-".contains(c))
+        .try_map(|operator| complete(range_operator, "range operator", operator))
+        .parse_next(input)
+}
+
+fn as_path(input: &mut Input<'_>) -> ModalResult<FilterKind> {
+    delimited('<', take_till(0.., '>'), cut_err('>'))
+        .map(|regex: &str| FilterKind::AsPath(regex.trim().to_string()))
+        .parse_next(input)
+}
+
+fn community(input: &mut Input<'_>) -> ModalResult<FilterKind> {
+    let arguments = |open, close| delimited(open, take_till(0.., close), cut_err(close));
+    preceded(
+        Caseless("community"),
+        alt((
+            arguments('(', ')').map(|arguments| (None, arguments)),
+            (
+                preceded('.', take_while(1.., |c: char| c.is_ascii_alphanumeric())),
+                preceded(multispace0, arguments('(', ')')),
+            )
+                .map(|(method, arguments)| (Some(method), arguments)),
+            preceded(
+                (multispace0, "=="),
+                preceded(multispace0, arguments('{', '}')),
+            )
+            .map(|arguments| (Some("=="), arguments)),
+        )),
+    )
+    .map(
+        |(method, arguments): (Option<&str>, &str)| FilterKind::Community {
+            method: method.map(ToOwned::to_owned),
+            arguments: list(arguments),
+        },
+    )
+    .parse_next(input)
+}
+
+/// Parse a filter consisting of a single word, e.g. `ANY` or `AS-CUSTOMERS^+`.
+fn filter_word(input: &mut Input<'_>) -> ModalResult<FilterKind> {
+    word.try_map(|word: &str| {
+        let (name, operator) = match word.find('^') {
+            Some(index) => {
+                let operator = complete(range_operator, "range operator", &word[index..])
+                    .map_err(|e| TypeError::new("filter", word, index + e.offset(), e.message()))?;
+                (&word[..index], operator)
+            }
+            None => (word, RangeOperator::None),
+        };
+
+        if name.eq_ignore_ascii_case("ANY") && operator == RangeOperator::None {
+            return Ok(FilterKind::Any);
+        }
+        if name.eq_ignore_ascii_case("PeerAS") && operator == RangeOperator::None {
+            return Ok(FilterKind::PeerAs);
+        }
+        if let Ok(as_number) = name.parse() {
+            return Ok(FilterKind::AsNumber(as_number, operator));
+        }
+        if let Ok(as_set) = name.parse() {
+            return Ok(FilterKind::AsSet(as_set, operator));
+        }
+        if let Ok(route_set) = name.parse() {
+            return Ok(FilterKind::RouteSet(route_set, operator));
+        }
+        match name.parse() {
+            Ok(filter_set) if operator == RangeOperator::None => {
+                Ok(FilterKind::FilterSet(filter_set))
+            }
+            _ => Err(TypeError::new(
+                "filter",
+                word,
+                0,
+                "expected ANY, PeerAS, an AS number or an as-set, route-set or filter-set name",
+            )),
+        }
+    })
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn asn(number: u32) -> AsNumber {
+        AsNumber::new(number)
+    }
+
+    /// The kind of a filter, with the spans of all nested filters removed.
+    #[rstest]
+    #[case("ANY", FilterKind::Any)]
+    #[case("peeras", FilterKind::PeerAs)]
+    #[case("AS65536", FilterKind::AsNumber(asn(65536), RangeOperator::None))]
+    #[case("AS65536^+", FilterKind::AsNumber(asn(65536), RangeOperator::LessIncl))]
+    #[case(
+        "AS65536:AS-CUSTOMERS^24-32",
+        FilterKind::AsSet("AS65536:AS-CUSTOMERS".parse().unwrap(), RangeOperator::Range(24, 32))
+    )]
+    #[case("RS-BOGONS", FilterKind::RouteSet("RS-BOGONS".parse().unwrap(), RangeOperator::None))]
+    #[case("FLTR-MARTIAN", FilterKind::FilterSet("FLTR-MARTIAN".parse().unwrap()))]
+    #[case(
+        "{ 192.0.2.0/24^+, 2001:db8::/32 }^-",
+        FilterKind::Prefixes(
+            vec!["192.0.2.0/24^+".parse().unwrap(), "2001:db8::/32".parse().unwrap()],
+            RangeOperator::LessExcl
+        )
+    )]
+    #[case("{}", FilterKind::Prefixes(vec![], RangeOperator::None))]
+    #[case("<^AS65536 AS65537* .* $>", FilterKind::AsPath("^AS65536 AS65537* .* $".to_string()))]
+    #[case(
+        "community(65536:1, NO_EXPORT)",
+        FilterKind::Community { method: None, arguments: vec!["65536:1".into(), "NO_EXPORT".into()] }
+    )]
+    #[case(
+        "community.contains(65536:1)",
+        FilterKind::Community { method: Some("contains".into()), arguments: vec!["65536:1".into()] }
+    )]
+    #[case(
+        "community == {65536:1}",
+        FilterKind::Community { method: Some("==".into()), arguments: vec!["65536:1".into()] }
+    )]
+    fn filter_primary(#[case] text: &str, #[case] expected: FilterKind) {
+        let filter = Filter::parse(text).unwrap();
+        assert_eq!(filter.kind, expected);
+        assert_eq!(filter.span, 0..text.len());
+    }
+
+    #[rstest]
+    #[case(
+        "AS1 OR AS2 AND NOT AS3",
+        "Or(AsNumber(AS1) And(AsNumber(AS2) Not(AsNumber(AS3))))"
+    )]
+    #[case("AS1 AS2 AS3", "Or(Or(AsNumber(AS1) AsNumber(AS2)) AsNumber(AS3))")]
+    #[case(
+        "NOT (AS1 OR AS2) AND AS3",
+        "And(Not(Or(AsNumber(AS1) AsNumber(AS2))) AsNumber(AS3))"
+    )]
+    #[case("not not ANY", "Not(Not(Any))")]
+    #[case("AS-FOO and not {0.0.0.0/0^0-7}", "And(AsSet(AS-FOO) Not(Prefixes))")]
+    /// Operators are case insensitive, `NOT` binds more tightly than `AND`, which binds more
+    /// tightly than `OR`. Filters without an operator between them are combined using `OR`.
+    fn filter_precedence(#[case] text: &str, #[case] expected: &str) {
+        fn render(filter: &Filter) -> String {
+            match &filter.kind {
+                FilterKind::Or(l, r) => format!("Or({} {})", render(l), render(r)),
+                FilterKind::And(l, r) => format!("And({} {})", render(l), render(r)),
+                FilterKind::Not(f) => format!("Not({})", render(f)),
+                FilterKind::AsNumber(asn, _) => format!("AsNumber({asn})"),
+                FilterKind::AsSet(name, _) => format!("AsSet({name})"),
+                FilterKind::Prefixes(..) => "Prefixes".to_string(),
+                other => format!("{other:?}"),
+            }
+        }
+        assert_eq!(render(&Filter::parse(text).unwrap()), expected);
+    }
+
+    #[test]
+    fn filter_spans_point_into_text() {
+        let text = "  AS-FOO AND NOT {0.0.0.0/0^0-7}";
+        let filter = Filter::parse(text).unwrap();
+        let FilterKind::And(left, right) = &filter.kind else {
+            unreachable!("expected AND, got {:?}", filter.kind);
+        };
+        assert_eq!(&text[filter.span.clone()], "AS-FOO AND NOT {0.0.0.0/0^0-7}");
+        assert_eq!(&text[left.span.clone()], "AS-FOO");
+        assert_eq!(&text[right.span.clone()], "NOT {0.0.0.0/0^0-7}");
+    }
+
+    #[rstest]
+    #[case("from AS1 accept AS1", 1, 1)]
+    #[case("FROM AS2 ACTION pref = 1; from AS3 action pref = 2; ACCEPT AS4", 1, 2)]
+    #[case("from AS1 accept AS1;", 1, 1)]
+    #[case("{ from AS1 accept AS1; from AS2 accept AS2 }", 2, 1)]
+    #[case("{ from AS1 accept AS1; from AS2 accept AS2; }", 2, 1)]
+    fn import_factors(#[case] text: &str, #[case] factors: usize, #[case] peerings: usize) {
+        let import = Policy::parse_import(text).unwrap();
+        assert_eq!(import.expression.factors.len(), factors);
+        assert_eq!(import.expression.factors[0].peerings.len(), peerings);
+        assert_eq!(import.span, 0..text.len());
+    }
+
+    #[test]
+    fn import_peering_with_routers_and_actions() {
+        let text = "from AS1 7.7.7.2 at 7.7.7.1 action pref = 10; med = 0; \
+                    community.append(10250, 3561:10); accept { 128.9.0.0/16 }";
+        let import = Policy::parse_import(text).unwrap();
+        let peering = &import.expression.factors[0].peerings[0];
+        assert_eq!(
+            peering.peering,
+            Peering::Expression {
+                as_expression: AsExpression::AsNumber(asn(1)),
+                remote_router: Some(RouterExpression::Address("7.7.7.2".parse().unwrap())),
+                local_router: Some(RouterExpression::Address("7.7.7.1".parse().unwrap())),
+            }
+        );
+        let actions: Vec<_> = peering
+            .actions
+            .iter()
+            .map(|action| (action.attribute.as_str(), &action.operation))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (
+                    "pref",
+                    &ActionOperation::Operator {
+                        operator: "=".into(),
+                        value: "10".into()
+                    }
+                ),
+                (
+                    "med",
+                    &ActionOperation::Operator {
+                        operator: "=".into(),
+                        value: "0".into()
+                    }
+                ),
+                (
+                    "community",
+                    &ActionOperation::Method {
+                        method: "append".into(),
+                        arguments: vec!["10250".into(), "3561:10".into()]
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            &text[peering.actions[2].span.clone()],
+            "community.append(10250, 3561:10)"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "from AS-FOO EXCEPT AS1 OR AS2 accept ANY",
+        AsExpression::Or(
+            Box::new(AsExpression::Except(
+                Box::new(AsExpression::AsSet("AS-FOO".parse().unwrap())),
+                Box::new(AsExpression::AsNumber(asn(1))),
+            )),
+            Box::new(AsExpression::AsNumber(asn(2))),
+        )
+    )]
+    #[case(
+        "from (AS1 OR AS2) AND AS-FOO accept ANY",
+        AsExpression::And(
+            Box::new(AsExpression::Or(
+                Box::new(AsExpression::AsNumber(asn(1))),
+                Box::new(AsExpression::AsNumber(asn(2))),
+            )),
+            Box::new(AsExpression::AsSet("AS-FOO".parse().unwrap())),
+        )
+    )]
+    fn import_as_expression(#[case] text: &str, #[case] expected: AsExpression) {
+        let import = Policy::parse_import(text).unwrap();
+        let Peering::Expression { as_expression, .. } =
+            &import.expression.factors[0].peerings[0].peering
+        else {
+            unreachable!("expected a peering expression");
+        };
+        assert_eq!(as_expression, &expected);
+    }
+
+    #[test]
+    fn import_peering_set() {
+        let import = Policy::parse_import("from AS1:PRNG-PEERS accept ANY").unwrap();
+        assert_eq!(
+            import.expression.factors[0].peerings[0].peering,
+            Peering::Set("AS1:PRNG-PEERS".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn import_protocol_into() {
+        let import = Policy::parse_import("protocol BGP4 into RIP from AS1 accept ANY").unwrap();
+        assert_eq!(import.protocol.as_deref(), Some("BGP4"));
+        assert_eq!(import.into.as_deref(), Some("RIP"));
+    }
+
+    #[test]
+    fn structured_import() {
+        let text = "{ from AS-ANY action pref = 1; accept community(3560:10); \
+                    from AS-ANY action pref = 2; accept community(3560:20); } \
+                    refine { from AS1 accept AS1; from AS2 accept AS2; } \
+                    except from AS3 accept AS3";
+        let import = Policy::parse_import(text).unwrap();
+        assert_eq!(import.expression.factors.len(), 2);
+        let refine = import.expression.refinement.as_ref().unwrap();
+        assert_eq!(refine.operator, RefineOperator::Refine);
+        assert_eq!(refine.expression.factors.len(), 2);
+        let except = refine.expression.refinement.as_ref().unwrap();
+        assert_eq!(except.operator, RefineOperator::Except);
+        assert_eq!(&text[except.expression.span.clone()], "from AS3 accept AS3");
+    }
+
+    #[test]
+    fn export_policy() {
+        let export = Policy::parse_export("to AS1 action med = 10; announce AS-FOO").unwrap();
+        assert_eq!(
+            export.expression.factors[0].filter.kind,
+            FilterKind::AsSet("AS-FOO".parse().unwrap(), RangeOperator::None)
+        );
+        assert!(Policy::parse_export("from AS1 accept ANY").is_err());
+    }
+
+    #[test]
+    fn mp_import_afi() {
+        let text = "afi ipv4.unicast, ipv6.unicast from AS1 accept ANY \
+                    except afi ipv6.unicast from AS1 accept {2001:db8::/32^+}";
+        let import = Policy::parse_mp_import(text).unwrap();
+        assert_eq!(
+            import.expression.afi,
+            vec![Afi::Ipv4Unicast, Afi::Ipv6Unicast]
+        );
+        let except = import.expression.refinement.unwrap();
+        assert_eq!(except.expression.afi, vec![Afi::Ipv6Unicast]);
+    }
+
+    #[rstest]
+    #[case("to AS1 networks ANY", vec![], true)]
+    #[case("to AS1 action pref = 100; networks { 0.0.0.0/0 }", vec![], true)]
+    #[case("to AS1", vec![], false)]
+    fn default_policy(#[case] text: &str, #[case] afi: Vec<Afi>, #[case] networks: bool) {
+        let default = DefaultPolicy::parse(text).unwrap();
+        assert_eq!(default.afi, afi);
+        assert_eq!(default.networks.is_some(), networks);
+        assert_eq!(
+            default.peering.peering,
+            Peering::Expression {
+                as_expression: AsExpression::AsNumber(asn(1)),
+                remote_router: None,
+                local_router: None
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("AS65536 2001:db8::1 at 2001:db8::2", true)]
+    #[case("prng-example", true)]
+    #[case("AS65536 at", false)]
+    fn peering_parse(#[case] text: &str, #[case] valid: bool) {
+        assert_eq!(Peering::parse(text).is_ok(), valid);
+    }
+
+    #[test]
+    fn mp_default_afi() {
+        let default = DefaultPolicy::parse_mp("afi ipv6 to AS1 networks ANY").unwrap();
+        assert_eq!(default.afi, vec![Afi::Ipv6]);
+        assert!(DefaultPolicy::parse("afi ipv6 to AS1 networks ANY").is_err());
+    }
+
+    #[rstest]
+    #[case("from AS1", 8, "expected `accept`")]
+    #[case("from AS1 accept", 15, "invalid filter")]
+    #[case("from accept ANY", 5, "invalid peering")]
+    #[case("from AS1 accept { 192.0.2.0/33 }", 28, "invalid prefix length")]
+    #[case("from AS1 accept AS1^x", 20, "expected one of ^-, ^+, ^n or ^n-m")]
+    #[case("from AS1 accept (AS1", 20, "expected `)`")]
+    #[case("afi ipv6 from AS1 accept ANY", 0, "expected `from`")]
+    #[case("from AS1 action pref accept ANY", 21, "invalid action")]
+    fn import_invalid_is_expected_err(
+        #[case] text: &str,
+        #[case] offset: usize,
+        #[case] message: &str,
+    ) {
+        let err = Policy::parse_import(text).unwrap_err();
+        assert_eq!(err.offset(), offset, "{err}");
+        assert!(err.message().contains(message), "{err}");
+    }
+
+    #[rstest]
+    #[case("(".repeat(64) + "ANY" + &")".repeat(64), true)]
+    #[case("NOT ".repeat(64) + "ANY", true)]
+    #[case("(NOT ".repeat(32) + "ANY" + &")".repeat(32), true)]
+    #[case("(".repeat(65) + "ANY" + &")".repeat(65), false)]
+    #[case("NOT ".repeat(65) + "ANY", false)]
+    #[case("(".repeat(1000) + "ANY" + &")".repeat(1000), false)]
+    #[case("NOT ".repeat(5000) + "ANY", false)]
+    fn filter_nesting_is_limited(#[case] text: String, #[case] valid: bool) {
+        assert_eq!(Filter::parse(&text).is_ok(), valid);
+    }
+
+    #[rstest]
+    #[case("ipv4", Afi::Ipv4)]
+    #[case("IPv6.Unicast", Afi::Ipv6Unicast)]
+    #[case("any.multicast", Afi::AnyMulticast)]
+    fn afi_from_str(#[case] s: &str, #[case] expected: Afi) {
+        assert_eq!(s.parse::<Afi>().unwrap(), expected);
+        assert!(expected.to_string().eq_ignore_ascii_case(s));
+    }
+
+    #[test]
+    fn position_slices_lines_of_value() {
+        let lines = [
+            "from AS65537 action pref = 100; # Transit",
+            "",
+            "  accept AS-CUSTOMERS AND",
+            "  NOT {0.0.0.0/0^0-7}  # Bogons",
+        ];
+        let value: Value = lines.to_vec().into();
+        let import = Policy::parse_import(&text(&value)).unwrap();
+        let span = &import.expression.factors[0].filter.span;
+
+        let (first, start) = position(&value, span.start).unwrap();
+        let (last, end) = position(&value, span.end).unwrap();
+        assert_eq!((first, last), (2, 3));
+        assert_eq!(&lines[first][start..], "AS-CUSTOMERS AND");
+        assert_eq!(&lines[last][..end], "  NOT {0.0.0.0/0^0-7}");
+        assert_eq!(position(&value, 0), Some((0, 0)));
+        assert_eq!(position(&value, span.end + 1), None);
+    }
+
+    #[test]
+    fn text_joins_lines_and_removes_comments() {
+        let value: Value = vec!["from AS1 # Transit", "accept ANY"].into();
+        assert_eq!(text(&value), "from AS1 accept ANY");
+    }
+}
@@ -0,0 +1,645 @@
+//! Generation of prefix lists for router configurations.
+//!
+//! A [`Generator`] expands an AS number, `as-set` or `route-set` into the prefixes of the
+//! `route` or `route6` objects it contains, similar to bgpq4. The resulting [`PrefixList`] can be
+//! rendered for several router platforms and as JSON, see [`Format`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     parse_whois_response,
+//!     prefix_list::{Family, Format, Generator},
+//!     resolve::ObjectResolver,
+//! };
+//!
+//! let objects = parse_whois_response(
+//!     "
+//! as-set:         AS-CUSTOMERS
+//! members:        AS65536, AS65537
+//!
+//! route:          192.0.2.0/25
+//! origin:         AS65536
+//!
+//! route:          192.0.2.128/25
+//! origin:         AS65537
+//!
+//! ",
+//! )?;
+//! let resolver: ObjectResolver = objects.iter().collect();
+//! let prefix_list = Generator::new(&resolver, Family::Ipv4)
+//!     .aggregate(true)
+//!     .generate("CUSTOMERS", &"AS-CUSTOMERS".parse()?);
+//!
+//! assert_eq!(
+//!     prefix_list.render(Format::CiscoIos),
+//!     concat!(
+//!         "no ip prefix-list CUSTOMERS\n",
+//!         "ip prefix-list CUSTOMERS permit 192.0.2.0/24 ge 25 le 25\n",
+//!     )
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{
+    prefix_set::PrefixSet,
+    resolve::{Expander, Issue, Member, Resolver},
+    types::{AddressPrefixRange, RangeOperator},
+};
+
+/// An address family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Family {
+    /// IPv4 prefixes.
+    Ipv4,
+    /// IPv6 prefixes.
+    Ipv6,
+}
+
+impl Family {
+    /// The maximum length of a prefix of this family.
+    #[must_use]
+    pub const fn max_length(self) -> u8 {
+        match self {
+            Self::Ipv4 => 32,
+            Self::Ipv6 => 128,
+        }
+    }
+
+    /// The prefix covering all addresses of this family.
+    fn default_route(self) -> &'static str {
+        match self {
+            Self::Ipv4 => "0.0.0.0/0",
+            Self::Ipv6 => "::/0",
+        }
+    }
+}
+
+/// The syntax to render a prefix list in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// A Cisco IOS `ip prefix-list` or `ipv6 prefix-list`.
+    CiscoIos,
+    /// A Cisco IOS XR `prefix-set`.
+    CiscoIosXr,
+    /// A Juniper Junos `route-filter-list`.
+    Juniper,
+    /// An Arista EOS `ip prefix-list` or `ipv6 prefix-list`.
+    Arista,
+    /// A BIRD prefix set.
+    Bird,
+    /// An FRR `ip prefix-list` or `ipv6 prefix-list` with sequence numbers.
+    Frr,
+    /// A JSON object mapping the name of the list to its entries, as produced by `bgpq4 -j`.
+    Json,
+}
+
+/// Generates prefix lists using a [`Resolver`].
+///
+/// Members are expanded using an [`Expander`], such that range operators of `route-set` members
+/// are included in the entries of the list, e.g. `192.0.2.0/24^+` results in
+/// `192.0.2.0/24 le 32`.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator<'r, R: ?Sized> {
+    expander: Expander<'r, R>,
+    family: Family,
+    max_length: Option<u8>,
+    more_specifics: Option<u8>,
+    aggregate: bool,
+}
+
+impl<'r, R: Resolver + ?Sized> Generator<'r, R> {
+    /// Create a generator of prefix lists of the address family.
+    pub fn new(resolver: &'r R, family: Family) -> Self {
+        Self {
+            expander: Expander::new(resolver),
+            family,
+            max_length: None,
+            more_specifics: None,
+            aggregate: false,
+        }
+    }
+
+    /// Exclude prefixes longer than the length, and lengths beyond it from the entries.
+    #[must_use]
+    pub fn max_length(mut self, length: u8) -> Self {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Allow prefixes more specific than the routes up to the length, like `bgpq4 -R`.
+    #[must_use]
+    pub fn more_specifics(mut self, length: u8) -> Self {
+        self.more_specifics = Some(length);
+        self
+    }
+
+    /// Whether to aggregate adjacent and overlapping entries, like `bgpq4 -A`.
+    ///
+    /// Aggregation does not change the prefixes matched by the list, for example the entries
+    /// `192.0.2.0/25` and `192.0.2.128/25` are aggregated into `192.0.2.0/24 ge 25 le 25`, see
+    /// [`PrefixSet`].
+    #[must_use]
+    pub fn aggregate(mut self, aggregate: bool) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Set the maximum number of nested sets expanded, see [`Expander::max_depth`].
+    #[must_use]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.expander = self.expander.max_depth(max_depth);
+        self
+    }
+
+    /// Generate a prefix list of the given name from the prefixes contained in the member.
+    #[must_use]
+    pub fn generate(&self, name: &str, member: &Member) -> PrefixList {
+        let expansion = self.expander.prefixes(member);
+        let max = self
+            .max_length
+            .unwrap_or(u8::MAX)
+            .min(self.family.max_length());
+        let entries = expansion.members.into_iter().filter_map(|range| {
+            let prefix = range.prefix();
+            if prefix.is_ipv4() != (self.family == Family::Ipv4) {
+                return None;
+            }
+            let lengths = range.lengths();
+            let end = self
+                .more_specifics
+                .map_or(*lengths.end(), |more_specifics| {
+                    (*lengths.end()).max(more_specifics)
+                });
+            AddressPrefixRange::clamped(
+                prefix,
+                RangeOperator::Range(*lengths.start(), end.min(max)),
+            )
+        });
+        let entries = if self.aggregate {
+            entries.collect::<PrefixSet>().ranges()
+        } else {
+            entries.collect::<BTreeSet<_>>().into_iter().collect()
+        };
+        PrefixList {
+            name: name.to_string(),
+            family: self.family,
+            entries,
+            issues: expansion.issues,
+        }
+    }
+}
+
+/// A prefix list of a single address family.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixList {
+    /// The name of the list.
+    pub name: String,
+    /// The address family of the entries.
+    pub family: Family,
+    /// The entries of the list, ordered by prefix.
+    pub entries: Vec<AddressPrefixRange>,
+    /// The problems encountered while expanding the sets of the list.
+    pub issues: Vec<Issue>,
+}
+
+impl PrefixList {
+    /// Render the list in the format.
+    ///
+    /// Since a missing prefix list matches all routes on some platforms, empty lists are
+    /// rendered as a list denying all prefixes where applicable.
+    #[must_use]
+    pub fn render(&self, format: Format) -> String {
+        let mut output = String::new();
+        // Writing to a `String` cannot fail.
+        let _ = match format {
+            Format::CiscoIos => self.cisco_ios(&mut output),
+            Format::CiscoIosXr => self.cisco_ios_xr(&mut output),
+            Format::Juniper => self.juniper(&mut output),
+            Format::Arista => self.arista(&mut output),
+            Format::Bird => self.bird(&mut output),
+            Format::Frr => self.frr(&mut output),
+            Format::Json => self.json(&mut output),
+        };
+        output
+    }
+
+    /// The keyword of prefix lists of the family on Cisco like platforms.
+    fn keyword(&self) -> &'static str {
+        match self.family {
+            Family::Ipv4 => "ip",
+            Family::Ipv6 => "ipv6",
+        }
+    }
+
+    /// The entry denying all prefixes of the family.
+    fn deny_all(&self) -> String {
+        format!(
+            "deny {} le {}",
+            self.family.default_route(),
+            self.family.max_length()
+        )
+    }
+
+    fn cisco_ios(&self, output: &mut String) -> std::fmt::Result {
+        let (keyword, name) = (self.keyword(), &self.name);
+        writeln!(output, "no {keyword} prefix-list {name}")?;
+        for entry in &self.entries {
+            writeln!(
+                output,
+                "{keyword} prefix-list {name} permit {}",
+                cisco(entry)
+            )?;
+        }
+        if self.entries.is_empty() {
+            writeln!(output, "{keyword} prefix-list {name} {}", self.deny_all())?;
+        }
+        Ok(())
+    }
+
+    fn cisco_ios_xr(&self, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "no prefix-set {}", self.name)?;
+        writeln!(output, "prefix-set {}", self.name)?;
+        let entries: Vec<_> = self.entries.iter().map(cisco).collect();
+        if !entries.is_empty() {
+            writeln!(output, " {}", entries.join(",\n "))?;
+        }
+        writeln!(output, "end-set")
+    }
+
+    fn juniper(&self, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "policy-options {{")?;
+        writeln!(output, "replace:")?;
+        writeln!(output, " route-filter-list {} {{", self.name)?;
+        for entry in &self.entries {
+            let prefix = entry.prefix();
+            let (start, end) = bounds(entry);
+            let modifier = match (start - prefix.length(), end) {
+                (0, end) if end == prefix.length() => "exact".to_string(),
+                (0, end) if end == prefix.max_length() => "orlonger".to_string(),
+                (0, end) => format!("upto /{end}"),
+                (1, end) if end == prefix.max_length() => "longer".to_string(),
+                (_, end) => format!("prefix-length-range /{start}-/{end}"),
+            };
+            writeln!(output, "  {prefix} {modifier};")?;
+        }
+        writeln!(output, " }}")?;
+        writeln!(output, "}}")
+    }
+
+    fn arista(&self, output: &mut String) -> std::fmt::Result {
+        let (keyword, name) = (self.keyword(), &self.name);
+        writeln!(output, "no {keyword} prefix-list {name}")?;
+        writeln!(output, "{keyword} prefix-list {name}")?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                output,
+                "   seq {} permit {}",
+                (index + 1) * 10,
+                cisco(entry)
+            )?;
+        }
+        if self.entries.is_empty() {
+            writeln!(output, "   seq 10 {}", self.deny_all())?;
+        }
+        Ok(())
+    }
+
+    fn bird(&self, output: &mut String) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            return writeln!(output, "{} = [ ];", self.name);
+        }
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| match bounds(entry) {
+                (start, end) if start == end && start == entry.prefix().length() => {
+                    entry.prefix().to_string()
+                }
+                (start, end) => format!("{}{{{start},{end}}}", entry.prefix()),
+            })
+            .collect();
+        writeln!(output, "{} = [", self.name)?;
+        writeln!(output, "    {}", entries.join(",\n    "))?;
+        writeln!(output, "];")
+    }
+
+    fn frr(&self, output: &mut String) -> std::fmt::Result {
+        let (keyword, name) = (self.keyword(), &self.name);
+        writeln!(output, "no {keyword} prefix-list {name}")?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let sequence = (index + 1) * 5;
+            writeln!(
+                output,
+                "{keyword} prefix-list {name} seq {sequence} permit {}",
+                cisco(entry)
+            )?;
+        }
+        if self.entries.is_empty() {
+            writeln!(
+                output,
+                "{keyword} prefix-list {name} seq 5 {}",
+                self.deny_all()
+            )?;
+        }
+        Ok(())
+    }
+
+    fn json(&self, output: &mut String) -> std::fmt::Result {
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let prefix = entry.prefix();
+                match bounds(entry) {
+                    (start, end) if start == end && start == prefix.length() => {
+                        format!("{{ \"prefix\": \"{prefix}\", \"exact\": true }}")
+                    }
+                    (start, end) => format!(
+                        "{{ \"prefix\": \"{prefix}\", \"exact\": false, \
+                         \"greater-equal\": {start}, \"less-equal\": {end} }}"
+                    ),
+                }
+            })
+            .collect();
+        let name = self.name.replace('\\', "\\\\").replace('"', "\\\"");
+        if entries.is_empty() {
+            return writeln!(output, "{{ \"{name}\": [] }}");
+        }
+        writeln!(output, "{{ \"{name}\": [")?;
+        writeln!(output, "    {}", entries.join(",\n    "))?;
+        writeln!(output, "] }}")
+    }
+}
+
+/// The first and last length matched by the range.
+fn bounds(range: &AddressPrefixRange) -> (u8, u8) {
+    let lengths = range.lengths();
+    (*lengths.start(), *lengths.end())
+}
+
+/// An entry of a Cisco like prefix list, e.g. `192.0.2.0/24 ge 25 le 32`.
+fn cisco(range: &AddressPrefixRange) -> String {
+    let prefix = range.prefix();
+    match bounds(range) {
+        (start, end) if start == end && start == prefix.length() => prefix.to_string(),
+        (start, end) if start == prefix.length() => format!("{prefix} le {end}"),
+        (start, end) => format!("{prefix} ge {start} le {end}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_whois_response, resolve::ObjectResolver};
+
+    const OBJECTS: &str = "
+as-set:         AS-FOO
+members:        AS1, AS2, AS-MISSING
+
+route-set:      RS-FOO
+members:        203.0.113.0/24^+, AS-FOO
+mp-members:     2001:db8::/32^48
+
+route:          192.0.2.0/25
+origin:         AS1
+
+route:          192.0.2.128/25
+origin:         AS2
+
+route:          192.0.2.0/24
+origin:         AS2
+
+route:          198.51.100.0/24
+origin:         AS1
+
+route:          198.51.100.0/26
+origin:         AS1
+
+route6:         2001:db8::/32
+origin:         AS1
+
+";
+
+    fn generate(
+        member: &str,
+        family: Family,
+        configure: impl Fn(Generator<'_, ObjectResolver>) -> Generator<'_, ObjectResolver>,
+    ) -> Vec<String> {
+        let objects = parse_whois_response(OBJECTS).unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        configure(Generator::new(&resolver, family))
+            .generate("TEST", &member.parse().unwrap())
+            .entries
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[rstest]
+    #[case(
+        "AS-FOO",
+        &["192.0.2.0/24", "192.0.2.0/25", "192.0.2.128/25", "198.51.100.0/24", "198.51.100.0/26"]
+    )]
+    #[case("AS1", &["192.0.2.0/25", "198.51.100.0/24", "198.51.100.0/26"])]
+    #[case(
+        "RS-FOO^24-25",
+        &[
+            "192.0.2.0/24^24-25",
+            "192.0.2.0/25",
+            "192.0.2.128/25",
+            "198.51.100.0/24^24-25",
+            "203.0.113.0/24^24-25"
+        ]
+    )]
+    fn generator_expands_members(#[case] member: &str, #[case] expected: &[&str]) {
+        assert_eq!(
+            generate(member, Family::Ipv4, |generator| generator),
+            expected
+        );
+    }
+
+    #[test]
+    fn generator_filters_family() {
+        assert_eq!(
+            generate("RS-FOO", Family::Ipv6, |generator| generator),
+            ["2001:db8::/32", "2001:db8::/32^48"]
+        );
+    }
+
+    #[test]
+    fn generator_applies_lengths() {
+        assert_eq!(
+            generate("AS-FOO", Family::Ipv4, |generator| generator.max_length(24)),
+            ["192.0.2.0/24", "198.51.100.0/24"]
+        );
+        assert_eq!(
+            generate("AS1", Family::Ipv4, |generator| generator
+                .more_specifics(26)),
+            [
+                "192.0.2.0/25^25-26",
+                "198.51.100.0/24^24-26",
+                "198.51.100.0/26"
+            ]
+        );
+        assert_eq!(
+            generate("RS-FOO", Family::Ipv4, |generator| {
+                generator.more_specifics(25).max_length(28)
+            }),
+            [
+                "192.0.2.0/24^24-25",
+                "192.0.2.0/25",
+                "192.0.2.128/25",
+                "198.51.100.0/24^24-25",
+                "198.51.100.0/26",
+                "203.0.113.0/24^24-28"
+            ]
+        );
+    }
+
+    #[test]
+    fn generator_aggregates() {
+        assert_eq!(
+            generate("AS-FOO", Family::Ipv4, |generator| generator
+                .aggregate(true)),
+            ["192.0.2.0/24^24-25", "198.51.100.0/24", "198.51.100.0/26"]
+        );
+        assert_eq!(
+            generate("RS-FOO", Family::Ipv4, |generator| {
+                generator.more_specifics(26).aggregate(true)
+            }),
+            [
+                "192.0.2.0/24^24-26",
+                "198.51.100.0/24^24-26",
+                "203.0.113.0/24^+"
+            ]
+        );
+    }
+
+    fn prefix_list(entries: &[&str], family: Family) -> PrefixList {
+        PrefixList {
+            name: "AS-FOO".to_string(),
+            family,
+            entries: entries.iter().map(|entry| entry.parse().unwrap()).collect(),
+            issues: Vec::new(),
+        }
+    }
+
+    const ENTRIES: &[&str] = &["192.0.2.0/24", "198.51.100.0/24^+", "203.0.113.0/24^25-26"];
+
+    #[rstest]
+    #[case(
+        Format::CiscoIos,
+        "no ip prefix-list AS-FOO
+ip prefix-list AS-FOO permit 192.0.2.0/24
+ip prefix-list AS-FOO permit 198.51.100.0/24 le 32
+ip prefix-list AS-FOO permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Format::CiscoIosXr,
+        "no prefix-set AS-FOO
+prefix-set AS-FOO
+ 192.0.2.0/24,
+ 198.51.100.0/24 le 32,
+ 203.0.113.0/24 ge 25 le 26
+end-set
+"
+    )]
+    #[case(
+        Format::Juniper,
+        "policy-options {
+replace:
+ route-filter-list AS-FOO {
+  192.0.2.0/24 exact;
+  198.51.100.0/24 orlonger;
+  203.0.113.0/24 prefix-length-range /25-/26;
+ }
+}
+"
+    )]
+    #[case(
+        Format::Arista,
+        "no ip prefix-list AS-FOO
+ip prefix-list AS-FOO
+   seq 10 permit 192.0.2.0/24
+   seq 20 permit 198.51.100.0/24 le 32
+   seq 30 permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Format::Bird,
+        "AS-FOO = [
+    192.0.2.0/24,
+    198.51.100.0/24{24,32},
+    203.0.113.0/24{25,26}
+];
+"
+    )]
+    #[case(
+        Format::Frr,
+        "no ip prefix-list AS-FOO
+ip prefix-list AS-FOO seq 5 permit 192.0.2.0/24
+ip prefix-list AS-FOO seq 10 permit 198.51.100.0/24 le 32
+ip prefix-list AS-FOO seq 15 permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Format::Json,
+        r#"{ "AS-FOO": [
+    { "prefix": "192.0.2.0/24", "exact": true },
+    { "prefix": "198.51.100.0/24", "exact": false, "greater-equal": 24, "less-equal": 32 },
+    { "prefix": "203.0.113.0/24", "exact": false, "greater-equal": 25, "less-equal": 26 }
+] }
+"#
+    )]
+    fn prefix_list_render(#[case] format: Format, #[case] expected: &str) {
+        assert_eq!(prefix_list(ENTRIES, Family::Ipv4).render(format), expected);
+    }
+
+    #[rstest]
+    #[case(
+        Format::CiscoIos,
+        "no ipv6 prefix-list AS-FOO\nipv6 prefix-list AS-FOO deny ::/0 le 128\n"
+    )]
+    #[case(
+        Format::Arista,
+        "no ipv6 prefix-list AS-FOO\nipv6 prefix-list AS-FOO\n   seq 10 deny ::/0 le 128\n"
+    )]
+    #[case(
+        Format::Frr,
+        "no ipv6 prefix-list AS-FOO\nipv6 prefix-list AS-FOO seq 5 deny ::/0 le 128\n"
+    )]
+    #[case(
+        Format::CiscoIosXr,
+        "no prefix-set AS-FOO\nprefix-set AS-FOO\nend-set\n"
+    )]
+    #[case(Format::Bird, "AS-FOO = [ ];\n")]
+    #[case(Format::Json, "{ \"AS-FOO\": [] }\n")]
+    fn prefix_list_render_empty(#[case] format: Format, #[case] expected: &str) {
+        assert_eq!(prefix_list(&[], Family::Ipv6).render(format), expected);
+    }
+
+    #[test]
+    fn prefix_list_render_juniper_ipv6() {
+        assert_eq!(
+            prefix_list(
+                &["2001:db8::/32^33-128", "2001:db8::/32^32-48"],
+                Family::Ipv6
+            )
+            .render(Format::Juniper),
+            "policy-options {
+replace:
+ route-filter-list AS-FOO {
+  2001:db8::/32 longer;
+  2001:db8::/32 upto /48;
+ }
+}
+"
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Sets of prefixes described by address prefix ranges.
+//!
+//! A [`PrefixSet`] contains the IPv4 and IPv6 prefixes matched by a collection of
+//! [`AddressPrefixRange`]s, e.g. `10.0.0.0/8^16-24` matches all prefixes of length 16 to 24
+//! within `10.0.0.0/8`. Sets support union, intersection and difference, and are kept in an
+//! aggregated form which can be rendered as the members of a `route-set`.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::prefix_set::PrefixSet;
+//!
+//! let customers: PrefixSet = "192.0.2.0/25, 192.0.2.128/25, 198.51.100.0/24^+".parse()?;
+//! assert_eq!(customers.to_string(), "192.0.2.0/24^25, 198.51.100.0/24^+");
+//!
+//! let bogons: PrefixSet = "198.51.100.0/24^25-32".parse()?;
+//! assert_eq!(customers.difference(&bogons).to_string(), "192.0.2.0/24^25, 198.51.100.0/24");
+//! assert!(customers.contains(&"198.51.100.128/25".parse()?));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+use crate::{
+    resolve::Member,
+    types::{self, AddressPrefixRange, Prefix, RangeOperator, TypeError},
+};
+
+/// The lengths matched at a prefix, as sorted and disjoint inclusive intervals.
+type Lengths = Vec<(u8, u8)>;
+
+/// A set of IPv4 and IPv6 prefixes.
+///
+/// The set is stored as the ranges of its aggregated form, which is the same for all
+/// collections of ranges matching the same prefixes. Ranges of sibling prefixes matching the
+/// same lengths are merged into a range of their parent prefix, and lengths already matched by
+/// a range of a less specific prefix are removed. For example, `192.0.2.0/25` and
+/// `192.0.2.128/25` are aggregated into `192.0.2.0/24^25`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PrefixSet {
+    ranges: BTreeMap<Prefix, Lengths>,
+}
+
+impl PrefixSet {
+    /// Create an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the set contains no prefixes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Whether the prefix is contained in the set.
+    #[must_use]
+    pub fn contains(&self, prefix: &Prefix) -> bool {
+        let length = prefix.length();
+        self.ancestors(prefix.network()).any(|(_, lengths)| {
+            lengths
+                .iter()
+                .any(|(start, end)| (start..=end).contains(&&length))
+        })
+    }
+
+    /// The prefixes contained in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges().into_iter().chain(other.ranges()).collect()
+    }
+
+    /// The prefixes contained in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = BTreeMap::new();
+        for (prefix, lengths) in &self.ranges {
+            for (other_prefix, other_lengths) in other.related(*prefix) {
+                let common = intersection(lengths, other_lengths);
+                let more_specific = if prefix.length() < other_prefix.length() {
+                    other_prefix
+                } else {
+                    *prefix
+                };
+                insert(&mut ranges, more_specific, &common);
+            }
+        }
+        Self::aggregated(ranges)
+    }
+
+    /// The prefixes contained in this set but not in the other set.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = BTreeMap::new();
+        for (prefix, lengths) in &self.ranges {
+            let mut pieces = vec![(*prefix, lengths.clone())];
+            for (other_prefix, other_lengths) in other.related(*prefix) {
+                pieces = pieces
+                    .into_iter()
+                    .flat_map(|(prefix, lengths)| {
+                        subtract(prefix, &lengths, other_prefix, other_lengths)
+                    })
+                    .collect();
+            }
+            for (prefix, lengths) in pieces {
+                insert(&mut ranges, prefix, &lengths);
+            }
+        }
+        Self::aggregated(ranges)
+    }
+
+    /// The ranges of the aggregated form of the set, ordered by prefix.
+    #[must_use]
+    pub fn ranges(&self) -> Vec<AddressPrefixRange> {
+        self.ranges
+            .iter()
+            .flat_map(|(prefix, lengths)| {
+                lengths.iter().filter_map(|(start, end)| {
+                    AddressPrefixRange::clamped(*prefix, RangeOperator::Range(*start, *end))
+                })
+            })
+            .collect()
+    }
+
+    /// The ranges of the set as members of a `route-set`.
+    #[must_use]
+    pub fn members(&self) -> Vec<Member> {
+        self.ranges().into_iter().map(Member::Prefix).collect()
+    }
+
+    /// The prefixes stored for the prefix and its less specific prefixes.
+    fn ancestors(&self, prefix: Prefix) -> impl Iterator<Item = (Prefix, &Lengths)> {
+        (0..=prefix.length()).filter_map(move |length| {
+            let ancestor = Prefix::new(prefix.address(), length).ok()?.network();
+            self.ranges
+                .get(&ancestor)
+                .map(|lengths| (ancestor, lengths))
+        })
+    }
+
+    /// The prefixes stored that are less or more specific than the prefix, or equal to it.
+    fn related(&self, prefix: Prefix) -> impl Iterator<Item = (Prefix, &Lengths)> {
+        let start = Prefix::new(prefix.network().address(), 0).ok();
+        let last = last_address(prefix);
+        let more_specifics = start
+            .zip(last)
+            .into_iter()
+            .flat_map(|(start, last)| self.ranges.range(start..=last))
+            .filter(move |(other, _)| prefix.contains(other) && **other != prefix)
+            .map(|(other, lengths)| (*other, lengths));
+        self.ancestors(prefix).chain(more_specifics)
+    }
+
+    /// Aggregate the lengths stored by prefix, see [`PrefixSet`].
+    fn aggregated(mut ranges: BTreeMap<Prefix, Lengths>) -> Self {
+        let mut pending: BTreeSet<_> = ranges
+            .keys()
+            .map(|prefix| (Reverse(prefix.length()), *prefix))
+            .collect();
+        while let Some((_, prefix)) = pending.pop_first() {
+            let Some((parent, sibling)) = parent_and_sibling(prefix) else {
+                continue;
+            };
+            let (Some(own), Some(other)) = (ranges.get(&prefix), ranges.get(&sibling)) else {
+                continue;
+            };
+            let common = intersection(own, other);
+            if common.is_empty() {
+                continue;
+            }
+            for prefix in [prefix, sibling] {
+                let remaining = difference(&ranges[&prefix], &common);
+                if remaining.is_empty() {
+                    ranges.remove(&prefix);
+                } else {
+                    ranges.insert(prefix, remaining);
+                }
+            }
+            insert(&mut ranges, parent, &common);
+            pending.insert((Reverse(parent.length()), parent));
+        }
+
+        let mut set = Self::default();
+        for (prefix, lengths) in &ranges {
+            let mut remaining = lengths.clone();
+            for length in 0..prefix.length() {
+                let Ok(ancestor) = Prefix::new(prefix.address(), length) else {
+                    continue;
+                };
+                if let Some(covering) = ranges.get(&ancestor.network()) {
+                    remaining = difference(&remaining, covering);
+                }
+            }
+            if !remaining.is_empty() {
+                set.ranges.insert(*prefix, remaining);
+            }
+        }
+        set
+    }
+}
+
+impl FromIterator<AddressPrefixRange> for PrefixSet {
+    fn from_iter<I: IntoIterator<Item = AddressPrefixRange>>(ranges: I) -> Self {
+        let mut lengths = BTreeMap::new();
+        for range in ranges {
+            let bounds = range.lengths();
+            insert(
+                &mut lengths,
+                range.prefix().network(),
+                &[(*bounds.start(), *bounds.end())],
+            );
+        }
+        Self::aggregated(lengths)
+    }
+}
+
+impl From<AddressPrefixRange> for PrefixSet {
+    fn from(range: AddressPrefixRange) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+impl FromStr for PrefixSet {
+    type Err = TypeError;
+
+    /// Parse a comma separated list of address prefix ranges, e.g. `192.0.2.0/24^+, 2001:db8::/32`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = Vec::new();
+        let mut offset = 0;
+        for item in s.split(',') {
+            let trimmed = item.trim_start();
+            let start = offset + item.len() - trimmed.len();
+            offset += item.len() + 1;
+            let trimmed = trimmed.trim_end();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let range = trimmed
+                .parse::<AddressPrefixRange>()
+                .map_err(|e| TypeError::new("prefix set", s, start + e.offset(), e.message()))?;
+            ranges.push(range);
+        }
+        Ok(ranges.into_iter().collect())
+    }
+}
+
+impl fmt::Display for PrefixSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges: Vec<_> = self.ranges().iter().map(ToString::to_string).collect();
+        f.write_str(&ranges.join(", "))
+    }
+}
+
+/// Add the lengths of the prefix to the stored ranges.
+fn insert(ranges: &mut BTreeMap<Prefix, Lengths>, prefix: Prefix, lengths: &[(u8, u8)]) {
+    if lengths.is_empty() {
+        return;
+    }
+    let stored = ranges.entry(prefix).or_default();
+    *stored = union(stored, lengths);
+}
+
+/// The lengths of the prefix that are not matched by the lengths of the other prefix.
+///
+/// If the other prefix is more specific, the remaining prefixes of the matched lengths are
+/// described by the siblings of the prefixes between both prefixes.
+fn subtract(
+    prefix: Prefix,
+    lengths: &[(u8, u8)],
+    other: Prefix,
+    other_lengths: &[(u8, u8)],
+) -> Vec<(Prefix, Lengths)> {
+    if other.contains(&prefix) {
+        return vec![(prefix, difference(lengths, other_lengths))];
+    }
+    if !prefix.contains(&other) {
+        return vec![(prefix, lengths.to_vec())];
+    }
+    let common = intersection(lengths, other_lengths);
+    let mut pieces = vec![(prefix, difference(lengths, &common))];
+    for length in prefix.length() + 1..=other.length() {
+        let Some((_, sibling)) = Prefix::new(other.address(), length)
+            .ok()
+            .and_then(|between| parent_and_sibling(between.network()))
+        else {
+            continue;
+        };
+        pieces.push((sibling, common.clone()));
+    }
+    pieces.retain(|(_, lengths)| !lengths.is_empty());
+    pieces
+}
+
+/// The parent of the prefix and its other child, or `None` for a prefix of length zero.
+fn parent_and_sibling(prefix: Prefix) -> Option<(Prefix, Prefix)> {
+    let length = prefix.length().checked_sub(1)?;
+    let parent = Prefix::new(prefix.address(), length).ok()?.network();
+    let bit = 1u128 << (127 - u32::from(length));
+    let address = types::address_bits(prefix.address()) ^ bit;
+    let sibling = Prefix::new(
+        types::address_from_bits(address, prefix.is_ipv4()),
+        prefix.length(),
+    )
+    .ok()?;
+    Some((parent, sibling))
+}
+
+/// The most specific prefix of the last address within the prefix.
+fn last_address(prefix: Prefix) -> Option<Prefix> {
+    let host = u128::MAX
+        .checked_shr(u32::from(prefix.length()))
+        .unwrap_or(0);
+    let host = if prefix.is_ipv4() {
+        host & (u128::from(u32::MAX) << 96)
+    } else {
+        host
+    };
+    let address = types::address_bits(prefix.address()) | host;
+    Prefix::new(
+        types::address_from_bits(address, prefix.is_ipv4()),
+        prefix.max_length(),
+    )
+    .ok()
+}
+
+/// The union of sorted, disjoint intervals of lengths.
+fn union(left: &[(u8, u8)], right: &[(u8, u8)]) -> Lengths {
+    let mut intervals: Vec<_> = left.iter().chain(right).copied().collect();
+    intervals.sort_unstable();
+    let mut merged: Lengths = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if u16::from(start) <= u16::from(last.1) + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// The intersection of sorted, disjoint intervals of lengths.
+fn intersection(left: &[(u8, u8)], right: &[(u8, u8)]) -> Lengths {
+    let mut intervals = Vec::new();
+    for (start, end) in left {
+        for (other_start, other_end) in right {
+            let (start, end) = (*start.max(other_start), *end.min(other_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+        }
+    }
+    union(&intervals, &[])
+}
+
+/// The lengths of the intervals on the left which are not in the intervals on the right.
+fn difference(left: &[(u8, u8)], right: &[(u8, u8)]) -> Lengths {
+    let mut intervals = left.to_vec();
+    for (other_start, other_end) in right {
+        intervals = intervals
+            .into_iter()
+            .flat_map(|(start, end)| {
+                let before = (start < *other_start).then(|| (start, end.min(other_start - 1)));
+                let after = (end > *other_end).then(|| (start.max(other_end + 1), end));
+                before.into_iter().chain(after)
+            })
+            .collect();
+    }
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("192.0.2.0/25, 192.0.2.128/25", "192.0.2.0/24^25")]
+    #[case(
+        "192.0.2.0/26, 192.0.2.64/26, 192.0.2.128/26, 192.0.2.192/26",
+        "192.0.2.0/24^26"
+    )]
+    #[case("192.0.2.0/25^+, 192.0.2.128/25", "192.0.2.0/24^25, 192.0.2.0/25^-")]
+    #[case("192.0.2.0/24^+, 192.0.2.0/25^-", "192.0.2.0/24^+")]
+    #[case("192.0.2.0/24, 192.0.2.0/24^25", "192.0.2.0/24^24-25")]
+    #[case("192.0.2.0/24, 192.0.2.0/25^+, 192.0.2.128/25^+", "192.0.2.0/24^+")]
+    #[case("192.0.2.0/25, 192.0.3.0/25", "192.0.2.0/25, 192.0.3.0/25")]
+    #[case(
+        "2001:db8::/33, 2001:db8:8000::/33, 192.0.2.1/24",
+        "192.0.2.0/24, 2001:db8::/32^33"
+    )]
+    #[case(
+        "10.0.0.0/8^16-24, 10.0.0.0/16^+",
+        "10.0.0.0/8^16-24, 10.0.0.0/16^25-32"
+    )]
+    #[case("", "")]
+    fn prefix_set_aggregates(#[case] set: PrefixSet, #[case] expected: &str) {
+        assert_eq!(set.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24^+", "198.51.100.0/24", "192.0.2.0/24^+, 198.51.100.0/24")]
+    #[case("192.0.2.0/25", "192.0.2.128/25", "192.0.2.0/24^25")]
+    #[case("10.0.0.0/8^16", "10.1.0.0/16^+", "10.0.0.0/8^16, 10.1.0.0/16^-")]
+    fn prefix_set_union(#[case] left: PrefixSet, #[case] right: PrefixSet, #[case] expected: &str) {
+        assert_eq!(left.union(&right).to_string(), expected);
+        assert_eq!(right.union(&left).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("10.0.0.0/8^16-24", "10.1.0.0/16^+", "10.1.0.0/16^16-24")]
+    #[case("10.0.0.0/8^16-24", "10.0.0.0/8^20-28", "10.0.0.0/8^20-24")]
+    #[case("10.0.0.0/8^+", "192.0.2.0/24", "")]
+    #[case(
+        "192.0.2.0/24^24-25",
+        "192.0.2.0/25, 192.0.2.128/25",
+        "192.0.2.0/24^25"
+    )]
+    #[case("0.0.0.0/0^+", "192.0.2.0/24, 2001:db8::/32", "192.0.2.0/24")]
+    fn prefix_set_intersection(
+        #[case] left: PrefixSet,
+        #[case] right: PrefixSet,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(left.intersection(&right).to_string(), expected);
+        assert_eq!(right.intersection(&left).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24^+", "192.0.2.0/24", "192.0.2.0/24^-")]
+    #[case("192.0.2.0/24^24-25", "192.0.2.0/25", "192.0.2.0/24, 192.0.2.128/25")]
+    #[case("192.0.2.0/24^26", "192.0.2.64/26", "192.0.2.0/26, 192.0.2.128/25^26")]
+    #[case("192.0.2.0/25", "192.0.2.0/24^+", "")]
+    #[case("192.0.2.0/24", "198.51.100.0/24^+", "192.0.2.0/24")]
+    #[case(
+        "10.0.0.0/8^16-24",
+        "10.0.0.0/9^16-20, 10.0.0.0/8^24",
+        "10.0.0.0/8^21-23, 10.128.0.0/9^16-20"
+    )]
+    fn prefix_set_difference(
+        #[case] left: PrefixSet,
+        #[case] right: PrefixSet,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(left.difference(&right).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("10.0.0.0/8^16-24", "10.0.0.0/9^16-20, 10.0.0.0/8^24")]
+    #[case("192.0.2.0/24^+", "192.0.2.64/26^27, 198.51.100.0/24")]
+    #[case("0.0.0.0/0^+, ::/0^+", "192.0.2.0/24^-, 2001:db8::/32^48-64")]
+    fn prefix_set_identities(#[case] left: PrefixSet, #[case] right: PrefixSet) {
+        let difference = left.difference(&right);
+        let intersection = left.intersection(&right);
+        assert_eq!(difference.union(&intersection), left);
+        assert!(difference.intersection(&right).is_empty());
+        assert_eq!(left.union(&right).difference(&difference), right);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", true)]
+    #[case("192.0.2.0/25", false)]
+    #[case("192.0.2.128/26", true)]
+    #[case("198.51.100.0/27", true)]
+    #[case("198.51.100.0/29", false)]
+    #[case("2001:db8::/48", false)]
+    fn prefix_set_contains(#[case] prefix: Prefix, #[case] expected: bool) {
+        let set: PrefixSet = "192.0.2.0/24, 192.0.2.128/25^26, 198.51.100.0/24^25-28"
+            .parse()
+            .unwrap();
+        assert_eq!(set.contains(&prefix), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24, 10.0.0.0/33", 23)]
+    #[case("192.0.2.0/24^x", 13)]
+    fn prefix_set_invalid_is_err(#[case] s: &str, #[case] offset: usize) {
+        assert_eq!(s.parse::<PrefixSet>().unwrap_err().offset(), offset);
+    }
+
+    #[test]
+    fn prefix_set_members() {
+        let set: PrefixSet = "192.0.2.0/24^+, 2001:db8::/32".parse().unwrap();
+        assert_eq!(
+            set.members(),
+            [
+                "192.0.2.0/24^+".parse().unwrap(),
+                "2001:db8::/32".parse().unwrap()
+            ]
+        );
+    }
+}
@@ -0,0 +1,1048 @@
+//! Resolution of the sets referenced by routing policies.
+//!
+//! Filters refer to routes and AS numbers indirectly, using the names of `as-set`, `route-set`
+//! and `filter-set` objects and the origin of `route` objects. A [`Resolver`] looks up these
+//! references, such that filters can be evaluated using the [`filter`](crate::filter) module.
+//!
+//! The [`ObjectResolver`] resolves references using an in-memory collection of objects, and the
+//! [`Expander`] flattens sets into the AS numbers and prefix ranges they contain.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{
+//!     parse_whois_response,
+//!     resolve::{Expander, Issue, Member, ObjectResolver, Resolver, SetName},
+//!     types::AsNumber,
+//! };
+//!
+//! let objects = parse_whois_response(
+//!     "
+//! as-set:         AS-CUSTOMERS
+//! members:        AS65536, AS-RESELLERS
+//!
+//! route:          192.0.2.0/24
+//! origin:         AS65536
+//!
+//! ",
+//! )?;
+//! let resolver: ObjectResolver = objects.iter().collect();
+//!
+//! let members = resolver.as_set(&"AS-CUSTOMERS".parse()?).unwrap();
+//! assert_eq!(members[1], "AS-RESELLERS".parse()?);
+//! assert_eq!(resolver.routes(AsNumber::new(65536))[0], "192.0.2.0/24".parse()?);
+//!
+//! let expansion = Expander::new(&resolver).as_set(&"AS-CUSTOMERS".parse()?);
+//! assert_eq!(expansion.members.into_iter().collect::<Vec<_>>(), [AsNumber::new(65536)]);
+//! assert_eq!(expansion.issues, [Issue::Missing(SetName::AsSet("AS-RESELLERS".parse()?))]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use crate::{
+    policy::Filter,
+    spec::Specification,
+    types::{
+        self, AddressPrefixRange, AsNumber, AsSetName, FilterSetName, Prefix, RangeOperator,
+        RouteSetName, TypeError,
+    },
+    Object,
+};
+
+/// Looks up the objects referenced by filters.
+///
+/// Names of sets are case insensitive, implementations are expected to look them up
+/// accordingly.
+pub trait Resolver {
+    /// The prefixes of the `route` and `route6` objects originated by the AS.
+    fn routes(&self, origin: AsNumber) -> Cow<'_, [Prefix]>;
+
+    /// The members of an `as-set`, or `None` if it is not defined.
+    ///
+    /// Includes the members added by objects referring to the set using `member-of`, if they are
+    /// allowed by its `mbrs-by-ref` attribute.
+    fn as_set(&self, name: &AsSetName) -> Option<Cow<'_, [Member]>>;
+
+    /// The members of a `route-set`, or `None` if it is not defined.
+    ///
+    /// Includes the members added by objects referring to the set using `member-of`, if they are
+    /// allowed by its `mbrs-by-ref` attribute.
+    fn route_set(&self, name: &RouteSetName) -> Option<Cow<'_, [Member]>>;
+
+    /// The filter of a `filter-set`, or `None` if it is not defined.
+    fn filter_set(&self, name: &FilterSetName) -> Option<Cow<'_, Filter>>;
+}
+
+/// A member of an `as-set` or `route-set`, e.g. `AS65536`, `AS-CUSTOMERS^+` or `192.0.2.0/24^-`.
+///
+/// Members of an `as-set` are AS numbers and `as-set` names without a range operator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Member {
+    /// The routes originated by an AS.
+    AsNumber(AsNumber, RangeOperator),
+    /// The routes originated by the members of an `as-set`.
+    AsSet(AsSetName, RangeOperator),
+    /// The members of a `route-set`.
+    RouteSet(RouteSetName, RangeOperator),
+    /// An address prefix range.
+    Prefix(AddressPrefixRange),
+}
+
+impl FromStr for Member {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with(|c: char| c.is_ascii_hexdigit() || c == ':') && s.contains('/') {
+            return s.parse().map(Self::Prefix);
+        }
+        let (name, operator) = match s.find('^') {
+            Some(index) => {
+                let operator = types::complete(types::range_operator, "member", &s[index..])
+                    .map_err(|e| TypeError::new("member", s, index + e.offset(), e.message()))?;
+                (&s[..index], operator)
+            }
+            None => (s, RangeOperator::None),
+        };
+        if let Ok(number) = name.parse() {
+            return Ok(Self::AsNumber(number, operator));
+        }
+        if let Ok(name) = name.parse() {
+            return Ok(Self::AsSet(name, operator));
+        }
+        name.parse()
+            .map(|name| Self::RouteSet(name, operator))
+            .map_err(|_| {
+                TypeError::new(
+                    "member",
+                    s,
+                    0,
+                    "expected an AS number, an as-set or route-set name or a prefix",
+                )
+            })
+    }
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AsNumber(number, operator) => write!(f, "{number}{operator}"),
+            Self::AsSet(name, operator) => write!(f, "{name}{operator}"),
+            Self::RouteSet(name, operator) => write!(f, "{name}{operator}"),
+            Self::Prefix(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+/// A [`Resolver`] using an in-memory collection of objects.
+///
+/// Objects are indexed when inserted, the following attributes are used:
+/// - `members`, `mp-members` and `mbrs-by-ref` of `as-set` and `route-set` objects.
+/// - `filter` and `mp-filter` of `filter-set` objects, preferring `mp-filter`.
+/// - `route`, `route6` and `origin` of `route` and `route6` objects.
+/// - `member-of` and `mnt-by` of `aut-num`, `route` and `route6` objects.
+///
+/// As specified in RFC 2622, section 5, an `aut-num` or route object referring to a set using
+/// `member-of` is a member of the set if the set lists one of the maintainers of the object in
+/// its `mbrs-by-ref` attribute, or `ANY`. Such references are ignored for sets without
+/// `mbrs-by-ref`.
+///
+/// Members that cannot be parsed are skipped, as are `filter-set` objects with an invalid
+/// filter, which are therefore treated as undefined.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectResolver {
+    routes: HashMap<AsNumber, Vec<Prefix>>,
+    as_sets: HashMap<String, Set>,
+    route_sets: HashMap<String, Set>,
+    filter_sets: HashMap<String, Filter>,
+    /// The objects referring to sets using `member-of`, by the name of the set.
+    references: HashMap<String, Vec<Reference>>,
+    /// The names of the sets referred to by each object, to replace its references.
+    referrers: HashMap<String, Vec<String>>,
+}
+
+/// The members of an `as-set` or `route-set`.
+#[derive(Debug, Clone)]
+struct Set {
+    members: Vec<Member>,
+    /// The maintainers allowed to add members using `member-of`, in uppercase.
+    mbrs_by_ref: Vec<String>,
+}
+
+impl Set {
+    fn new<Spec: Specification>(object: &Object<'_, Spec>) -> Self {
+        Self {
+            members: members(object),
+            mbrs_by_ref: names(object, "mbrs-by-ref"),
+        }
+    }
+}
+
+/// An object referring to a set using `member-of`.
+#[derive(Debug, Clone)]
+struct Reference {
+    /// The class and primary key of the object.
+    object: String,
+    member: Member,
+    /// The maintainers of the object, in uppercase.
+    mnt_by: Vec<String>,
+}
+
+impl ObjectResolver {
+    /// Create a resolver without any objects.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index an object, replacing any previously inserted object of the same name.
+    ///
+    /// Objects of classes not used to resolve references are ignored.
+    pub fn insert<Spec: Specification>(&mut self, object: &Object<'_, Spec>) {
+        let Some(first) = object.first() else {
+            return;
+        };
+        let key = types::content(&first.value);
+        match first.name.to_string().as_str() {
+            "as-set" => {
+                self.as_sets
+                    .insert(key.to_ascii_uppercase(), Set::new(object));
+            }
+            "route-set" => {
+                self.route_sets
+                    .insert(key.to_ascii_uppercase(), Set::new(object));
+            }
+            "filter-set" => {
+                let filter = values(object, "mp-filter")
+                    .or_else(|| values(object, "filter"))
+                    .and_then(|filter| Filter::parse(&filter).ok());
+                match filter {
+                    Some(filter) => self.filter_sets.insert(key.to_ascii_uppercase(), filter),
+                    None => self.filter_sets.remove(&key.to_ascii_uppercase()),
+                };
+            }
+            "aut-num" => {
+                if let Ok(number) = key.parse() {
+                    let member = Member::AsNumber(number, RangeOperator::None);
+                    self.refer(format!("aut-num:{number}"), &member, object);
+                }
+            }
+            "route" | "route6" => {
+                let origin = values(object, "origin").and_then(|origin| origin.parse().ok());
+                if let (Ok(prefix), Some(origin)) = (key.parse(), origin) {
+                    let routes = self.routes.entry(origin).or_default();
+                    if !routes.contains(&prefix) {
+                        routes.push(prefix);
+                    }
+                    let member = Member::Prefix(AddressPrefixRange::from(prefix));
+                    self.refer(format!("{}:{prefix}{origin}", first.name), &member, object);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace the `member-of` references of the object identified by `key`.
+    fn refer<Spec: Specification>(
+        &mut self,
+        key: String,
+        member: &Member,
+        object: &Object<'_, Spec>,
+    ) {
+        for set in self.referrers.remove(&key).unwrap_or_default() {
+            if let Some(references) = self.references.get_mut(&set) {
+                references.retain(|reference| reference.object != key);
+            }
+        }
+        let sets = names(object, "member-of");
+        if sets.is_empty() {
+            return;
+        }
+        let mnt_by = names(object, "mnt-by");
+        for set in &sets {
+            self.references
+                .entry(set.clone())
+                .or_default()
+                .push(Reference {
+                    object: key.clone(),
+                    member: member.clone(),
+                    mnt_by: mnt_by.clone(),
+                });
+        }
+        self.referrers.insert(key, sets);
+    }
+
+    /// The members of the set, including those added using `member-of`.
+    fn set_members<'s>(&'s self, set: &'s Set, key: &str) -> Cow<'s, [Member]> {
+        if set.mbrs_by_ref.is_empty() {
+            return Cow::Borrowed(&set.members);
+        }
+        let any = set.mbrs_by_ref.iter().any(|maintainer| maintainer == "ANY");
+        let mut members = set.members.clone();
+        let references = self.references.get(key).into_iter().flatten();
+        for reference in references.filter(|reference| {
+            any || reference
+                .mnt_by
+                .iter()
+                .any(|maintainer| set.mbrs_by_ref.contains(maintainer))
+        }) {
+            if !members.contains(&reference.member) {
+                members.push(reference.member.clone());
+            }
+        }
+        Cow::Owned(members)
+    }
+}
+
+impl Resolver for ObjectResolver {
+    fn routes(&self, origin: AsNumber) -> Cow<'_, [Prefix]> {
+        self.routes
+            .get(&origin)
+            .map_or(Cow::Borrowed(&[]), |routes| Cow::Borrowed(routes))
+    }
+
+    fn as_set(&self, name: &AsSetName) -> Option<Cow<'_, [Member]>> {
+        let key = name.as_str().to_ascii_uppercase();
+        self.as_sets
+            .get(&key)
+            .map(|set| self.set_members(set, &key))
+    }
+
+    fn route_set(&self, name: &RouteSetName) -> Option<Cow<'_, [Member]>> {
+        let key = name.as_str().to_ascii_uppercase();
+        self.route_sets
+            .get(&key)
+            .map(|set| self.set_members(set, &key))
+    }
+
+    fn filter_set(&self, name: &FilterSetName) -> Option<Cow<'_, Filter>> {
+        self.filter_sets
+            .get(&name.as_str().to_ascii_uppercase())
+            .map(Cow::Borrowed)
+    }
+}
+
+impl<'o, 'a: 'o, Spec: Specification + 'o> FromIterator<&'o Object<'a, Spec>> for ObjectResolver {
+    fn from_iter<I: IntoIterator<Item = &'o Object<'a, Spec>>>(objects: I) -> Self {
+        let mut resolver = Self::new();
+        objects
+            .into_iter()
+            .for_each(|object| resolver.insert(object));
+        resolver
+    }
+}
+
+/// The name of an `as-set` or `route-set`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetName {
+    /// The name of an `as-set`.
+    AsSet(AsSetName),
+    /// The name of a `route-set`.
+    RouteSet(RouteSetName),
+}
+
+impl SetName {
+    /// The name in uppercase, as names of sets are case insensitive.
+    fn key(&self) -> String {
+        match self {
+            Self::AsSet(name) => name.as_str().to_ascii_uppercase(),
+            Self::RouteSet(name) => name.as_str().to_ascii_uppercase(),
+        }
+    }
+}
+
+impl fmt::Display for SetName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AsSet(name) => write!(f, "{name}"),
+            Self::RouteSet(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// A problem encountered while expanding a set, which may result in missing members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// A referenced set that is not defined.
+    Missing(SetName),
+    /// A set containing itself, directly or through the other sets of the cycle.
+    ///
+    /// The sets are listed in the order they refer to each other, starting and ending with the
+    /// same set. Cycles do not result in missing members.
+    Cycle(Vec<SetName>),
+    /// A set nested deeper than the maximum depth, whose members are not included.
+    DepthExceeded(SetName),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "{name} is not defined"),
+            Self::Cycle(names) => {
+                let names: Vec<_> = names.iter().map(ToString::to_string).collect();
+                write!(f, "cycle between {}", names.join(" -> "))
+            }
+            Self::DepthExceeded(name) => write!(f, "{name} exceeds the maximum depth"),
+        }
+    }
+}
+
+/// The members of an expanded set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion<T> {
+    /// The members of the set and of its nested sets, without duplicates.
+    pub members: BTreeSet<T>,
+    /// The problems encountered while expanding the set.
+    pub issues: Vec<Issue>,
+}
+
+impl<T> Expansion<T> {
+    /// Whether all members are included, i.e. no set is missing or exceeds the maximum depth.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|issue| matches!(issue, Issue::Cycle(_)))
+    }
+}
+
+/// Recursively expands `as-set` and `route-set` objects using a [`Resolver`].
+///
+/// Range operators of members are applied to the prefixes they contain, dropping the prefixes
+/// for which an operator results in no valid lengths, e.g. `192.0.2.0/24^16`.
+#[derive(Debug, Clone, Copy)]
+pub struct Expander<'r, R: ?Sized> {
+    resolver: &'r R,
+    max_depth: usize,
+}
+
+impl<'r, R: Resolver + ?Sized> Expander<'r, R> {
+    /// The default maximum number of nested sets below the expanded set.
+    pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+    /// Create an expander looking up sets using the resolver.
+    pub fn new(resolver: &'r R) -> Self {
+        Self {
+            resolver,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Set the maximum number of nested sets below the expanded set.
+    ///
+    /// Sets nested deeper are not expanded and reported as [`Issue::DepthExceeded`].
+    #[must_use]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The AS numbers contained in the `as-set` and its nested sets.
+    #[must_use]
+    pub fn as_set(&self, name: &AsSetName) -> Expansion<AsNumber> {
+        let mut walk = Walk::new(self);
+        let numbers = walk.as_set(name);
+        Expansion {
+            members: numbers,
+            issues: walk.issues,
+        }
+    }
+
+    /// The prefix ranges contained in the `route-set` and its nested sets.
+    #[must_use]
+    pub fn route_set(&self, name: &RouteSetName) -> Expansion<AddressPrefixRange> {
+        self.prefixes(&Member::RouteSet(name.clone(), RangeOperator::None))
+    }
+
+    /// The prefix ranges contained in the member of a `route-set`.
+    ///
+    /// AS numbers and `as-set` names are expanded to the prefixes of the `route` and `route6`
+    /// objects originated by the AS numbers.
+    #[must_use]
+    pub fn prefixes(&self, member: &Member) -> Expansion<AddressPrefixRange> {
+        let mut walk = Walk::new(self);
+        let mut ranges = BTreeSet::new();
+        walk.member(member, RangeOperator::None, &mut ranges);
+        Expansion {
+            members: ranges,
+            issues: walk.issues,
+        }
+    }
+}
+
+/// The state of a single expansion.
+struct Walk<'e, 'r, R: ?Sized> {
+    expander: &'e Expander<'r, R>,
+    /// The sets being expanded, from the outermost to the innermost set.
+    path: Vec<SetName>,
+    /// The `route-set`s fully expanded, with the operator applied to their members.
+    expanded: HashSet<(String, RangeOperator)>,
+    /// The AS numbers of the `as-set`s fully expanded, reused when they are referenced again.
+    as_sets: HashMap<String, BTreeSet<AsNumber>>,
+    /// Whether a set was not expanded since it exceeds the maximum depth.
+    truncated: bool,
+    issues: Vec<Issue>,
+}
+
+impl<'e, 'r, R: Resolver + ?Sized> Walk<'e, 'r, R> {
+    fn new(expander: &'e Expander<'r, R>) -> Self {
+        Self {
+            expander,
+            path: Vec::new(),
+            expanded: HashSet::new(),
+            as_sets: HashMap::new(),
+            truncated: false,
+            issues: Vec::new(),
+        }
+    }
+
+    fn issue(&mut self, issue: Issue) {
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+
+    /// Whether the set should be expanded, pushing it to the path if so.
+    ///
+    /// Sets already `seen` are skipped, as are sets on the path, which are reported as a cycle.
+    fn enter(&mut self, name: &SetName, seen: bool) -> bool {
+        let key = name.key();
+        if let Some(index) = self.path.iter().position(|set| set.key() == key) {
+            let mut cycle = self.path[index..].to_vec();
+            cycle.push(name.clone());
+            self.issue(Issue::Cycle(cycle));
+            return false;
+        }
+        if seen {
+            return false;
+        }
+        if self.path.len() > self.expander.max_depth {
+            self.issue(Issue::DepthExceeded(name.clone()));
+            self.truncated = true;
+            return false;
+        }
+        self.path.push(name.clone());
+        true
+    }
+
+    /// The AS numbers contained in the `as-set` and its nested sets.
+    ///
+    /// The numbers are kept for later references to the set, unless the maximum depth was
+    /// exceeded while expanding it.
+    fn as_set(&mut self, name: &AsSetName) -> BTreeSet<AsNumber> {
+        let key = name.as_str().to_ascii_uppercase();
+        if let Some(numbers) = self.as_sets.get(&key) {
+            return numbers.clone();
+        }
+        let truncated = std::mem::replace(&mut self.truncated, false);
+        let mut numbers = BTreeSet::new();
+        self.collect(name, &mut numbers, &mut HashSet::new());
+        if !self.truncated {
+            self.as_sets.insert(key, numbers.clone());
+        }
+        self.truncated |= truncated;
+        numbers
+    }
+
+    /// Add the AS numbers of the `as-set` and of the nested sets not `visited` yet.
+    fn collect(
+        &mut self,
+        name: &AsSetName,
+        numbers: &mut BTreeSet<AsNumber>,
+        visited: &mut HashSet<String>,
+    ) {
+        let set = SetName::AsSet(name.clone());
+        let seen = !visited.insert(set.key());
+        if !self.enter(&set, seen) {
+            return;
+        }
+        let Some(members) = self.expander.resolver.as_set(name) else {
+            self.issue(Issue::Missing(set));
+            self.path.pop();
+            return;
+        };
+        for member in members.iter() {
+            match member {
+                Member::AsNumber(number, _) => {
+                    numbers.insert(*number);
+                }
+                Member::AsSet(name, _) => {
+                    match self.as_sets.get(&name.as_str().to_ascii_uppercase()) {
+                        Some(cached) => numbers.extend(cached),
+                        None => self.collect(name, numbers, visited),
+                    }
+                }
+                Member::RouteSet(..) | Member::Prefix(_) => {}
+            }
+        }
+        self.path.pop();
+    }
+
+    /// Add the prefix ranges of the member with the operator applied to them.
+    fn member(
+        &mut self,
+        member: &Member,
+        operator: RangeOperator,
+        ranges: &mut BTreeSet<AddressPrefixRange>,
+    ) {
+        match member {
+            Member::Prefix(range) => {
+                ranges.extend(AddressPrefixRange::clamped(
+                    range.prefix(),
+                    operator.apply_to(range.operator()),
+                ));
+            }
+            Member::AsNumber(number, inner) => {
+                self.routes(*number, operator.apply_to(*inner), ranges);
+            }
+            Member::AsSet(name, inner) => {
+                for number in self.as_set(name) {
+                    self.routes(number, operator.apply_to(*inner), ranges);
+                }
+            }
+            Member::RouteSet(name, inner) => {
+                let operator = operator.apply_to(*inner);
+                let set = SetName::RouteSet(name.clone());
+                let key = (set.key(), operator);
+                let seen = self.expanded.contains(&key);
+                if !self.enter(&set, seen) {
+                    return;
+                }
+                match self.expander.resolver.route_set(name) {
+                    Some(members) => {
+                        for member in members.iter() {
+                            self.member(member, operator, ranges);
+                        }
+                    }
+                    None => self.issue(Issue::Missing(set)),
+                }
+                self.path.pop();
+                self.expanded.insert(key);
+            }
+        }
+    }
+
+    fn routes(
+        &self,
+        origin: AsNumber,
+        operator: RangeOperator,
+        ranges: &mut BTreeSet<AddressPrefixRange>,
+    ) {
+        let routes = self.expander.resolver.routes(origin);
+        ranges.extend(
+            routes
+                .iter()
+                .filter_map(|prefix| AddressPrefixRange::clamped(*prefix, operator)),
+        );
+    }
+}
+
+/// The AS numbers contained in the as-set, including those of its nested sets.
+///
+/// Sets that are not defined are added to `unresolved` unless already contained.
+pub(crate) fn as_set_numbers<R: Resolver + ?Sized>(
+    resolver: &R,
+    name: &AsSetName,
+    unresolved: &mut Vec<AsSetName>,
+) -> HashSet<AsNumber> {
+    let expansion = Expander::new(resolver).as_set(name);
+    for issue in expansion.issues {
+        if let Issue::Missing(SetName::AsSet(name)) = issue {
+            if !unresolved.contains(&name) {
+                unresolved.push(name);
+            }
+        }
+    }
+    expansion.members.into_iter().collect()
+}
+
+/// The content of all attributes of the name joined by spaces, or `None` if there are none.
+fn values<Spec: Specification>(object: &Object<'_, Spec>, name: &str) -> Option<String> {
+    let values: Vec<_> = object
+        .iter()
+        .filter(|attribute| attribute.name == name)
+        .map(|attribute| types::content(&attribute.value))
+        .collect();
+    (!values.is_empty()).then(|| values.join(" "))
+}
+
+/// The comma separated items of all attributes of the name in uppercase, e.g. maintainers.
+fn names<Spec: Specification>(object: &Object<'_, Spec>, name: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for attribute in object.iter().filter(|attribute| attribute.name == name) {
+        for item in types::content(&attribute.value).split(',') {
+            let item = item.trim().to_ascii_uppercase();
+            if !item.is_empty() && !names.contains(&item) {
+                names.push(item);
+            }
+        }
+    }
+    names
+}
+
+/// The valid items of the `members` and `mp-members` attributes.
+fn members<Spec: Specification>(object: &Object<'_, Spec>) -> Vec<Member> {
+    object
+        .iter()
+        .filter(|attribute| attribute.name == "members" || attribute.name == "mp-members")
+        .flat_map(|attribute| {
+            types::content(&attribute.value)
+                .split(',')
+                .filter_map(|item| item.trim().parse().ok())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::parse_whois_response;
+
+    #[rstest]
+    #[case("AS65536", Member::AsNumber(AsNumber::new(65536), RangeOperator::None))]
+    #[case(
+        "AS-FOO^+",
+        Member::AsSet("AS-FOO".parse().unwrap(), RangeOperator::LessIncl)
+    )]
+    #[case(
+        "AS1:RS-FOO^24-28",
+        Member::RouteSet("AS1:RS-FOO".parse().unwrap(), RangeOperator::Range(24, 28))
+    )]
+    #[case("192.0.2.0/24^-", Member::Prefix("192.0.2.0/24^-".parse().unwrap()))]
+    #[case("2001:db8::/32", Member::Prefix("2001:db8::/32".parse().unwrap()))]
+    fn member_from_str(#[case] s: &str, #[case] expected: Member) {
+        assert_eq!(s.parse::<Member>().unwrap(), expected);
+        assert_eq!(expected.to_string(), s);
+    }
+
+    #[rstest]
+    #[case("FOO", 0)]
+    #[case("AS-FOO^x", 7)]
+    #[case("192.0.2.0/33", 10)]
+    fn member_invalid_is_expected_err(#[case] s: &str, #[case] offset: usize) {
+        assert_eq!(s.parse::<Member>().unwrap_err().offset(), offset);
+    }
+
+    #[test]
+    fn object_resolver_indexes_objects() {
+        let objects = parse_whois_response(
+            "
+as-set:         as-foo
+members:        AS1, AS-BAR # Comment
+mp-members:     AS2,
+                INVALID
+
+route-set:      RS-FOO
+members:        192.0.2.0/24^+
+mp-members:     2001:db8::/32
+
+filter-set:     FLTR-FOO
+filter:         AS1
+mp-filter:      AS2
+
+filter-set:     FLTR-INVALID
+filter:         AS1 AND
+
+route:          192.0.2.0/24
+origin:         AS1
+
+route6:         2001:db8::/32
+origin:         AS1
+
+route:          198.51.100.0/24
+origin:         AS2
+
+",
+        )
+        .unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+
+        assert_eq!(
+            resolver
+                .as_set(&"AS-FOO".parse().unwrap())
+                .unwrap()
+                .to_vec(),
+            vec![
+                "AS1".parse().unwrap(),
+                "AS-BAR".parse().unwrap(),
+                "AS2".parse().unwrap()
+            ]
+        );
+        assert_eq!(
+            resolver
+                .route_set(&"rs-foo".parse().unwrap())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            resolver
+                .filter_set(&"FLTR-FOO".parse().unwrap())
+                .unwrap()
+                .kind,
+            crate::policy::FilterKind::AsNumber(AsNumber::new(2), RangeOperator::None)
+        );
+        assert!(resolver
+            .filter_set(&"FLTR-INVALID".parse().unwrap())
+            .is_none());
+        assert!(resolver.as_set(&"AS-BAR".parse().unwrap()).is_none());
+        assert_eq!(
+            resolver.routes(AsNumber::new(1)).to_vec(),
+            vec![
+                "192.0.2.0/24".parse().unwrap(),
+                "2001:db8::/32".parse().unwrap()
+            ]
+        );
+        assert!(resolver.routes(AsNumber::new(3)).is_empty());
+    }
+
+    #[test]
+    fn object_resolver_honours_mbrs_by_ref() {
+        let objects = parse_whois_response(
+            "
+as-set:         AS-OPEN
+members:        AS1
+mbrs-by-ref:    ANY
+
+as-set:         AS-MAINTAINED
+mbrs-by-ref:    MNT-A, MNT-B
+
+as-set:         AS-CLOSED
+members:        AS1
+
+aut-num:        AS2
+member-of:      AS-OPEN, as-maintained, AS-CLOSED
+mnt-by:         mnt-b
+
+aut-num:        AS3
+member-of:      AS-MAINTAINED
+mnt-by:         MNT-C
+
+route-set:      RS-FOO
+mbrs-by-ref:    MNT-A
+
+route:          192.0.2.0/24
+origin:         AS1
+member-of:      RS-FOO
+mnt-by:         MNT-A
+
+",
+        )
+        .unwrap();
+        let mut resolver: ObjectResolver = objects.iter().collect();
+        let as_set = |resolver: &ObjectResolver, name: &str| {
+            resolver
+                .as_set(&name.parse().unwrap())
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(as_set(&resolver, "AS-OPEN"), ["AS1", "AS2"]);
+        assert_eq!(as_set(&resolver, "AS-MAINTAINED"), ["AS2"]);
+        assert_eq!(as_set(&resolver, "AS-CLOSED"), ["AS1"]);
+        assert_eq!(
+            resolver
+                .route_set(&"RS-FOO".parse().unwrap())
+                .unwrap()
+                .to_vec(),
+            ["192.0.2.0/24".parse().unwrap()]
+        );
+
+        let replacement = parse_whois_response(
+            "
+aut-num:        AS2
+member-of:      AS-OPEN
+mnt-by:         MNT-B
+
+",
+        )
+        .unwrap();
+        resolver.insert(&replacement[0]);
+        assert_eq!(as_set(&resolver, "AS-OPEN"), ["AS1", "AS2"]);
+        assert!(as_set(&resolver, "AS-MAINTAINED").is_empty());
+    }
+
+    const SETS: &str = "
+as-set:         AS-FOO
+members:        AS1, AS-BAR, AS-MISSING
+
+as-set:         AS-BAR
+members:        AS2, AS-BAZ
+
+as-set:         AS-BAZ
+members:        AS3, AS-FOO
+
+route-set:      RS-FOO
+members:        192.0.2.0/24^+, AS1^-, RS-BAR^26, AS-BAR
+mp-members:     2001:db8::/32
+
+route-set:      RS-BAR
+members:        198.51.100.0/24^24-28, AS4, RS-BAR
+
+route:          203.0.113.0/24
+origin:         AS1
+
+route:          203.0.113.0/25
+origin:         AS2
+
+route:          203.0.113.0/26
+origin:         AS3
+
+route:          203.0.113.0/27
+origin:         AS4
+
+";
+
+    #[test]
+    fn expander_expands_as_sets() {
+        let objects = parse_whois_response(SETS).unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        let expansion = Expander::new(&resolver).as_set(&"as-foo".parse().unwrap());
+
+        assert_eq!(
+            expansion.members,
+            [1, 2, 3].into_iter().map(AsNumber::new).collect()
+        );
+        assert_eq!(
+            expansion.issues,
+            [
+                Issue::Cycle(
+                    ["as-foo", "AS-BAR", "AS-BAZ", "AS-FOO"]
+                        .iter()
+                        .map(|name| SetName::AsSet(name.parse().unwrap()))
+                        .collect()
+                ),
+                Issue::Missing(SetName::AsSet("AS-MISSING".parse().unwrap())),
+            ]
+        );
+        assert!(!expansion.is_complete());
+        assert_eq!(
+            expansion.issues[0].to_string(),
+            "cycle between as-foo -> AS-BAR -> AS-BAZ -> AS-FOO"
+        );
+    }
+
+    #[rstest]
+    #[case(0, &[1], &["AS-BAR", "AS-MISSING"])]
+    #[case(1, &[1, 2], &["AS-BAZ"])]
+    #[case(2, &[1, 2, 3], &[])]
+    fn expander_limits_depth(
+        #[case] max_depth: usize,
+        #[case] expected: &[u32],
+        #[case] exceeded: &[&str],
+    ) {
+        let objects = parse_whois_response(SETS).unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        let expansion = Expander::new(&resolver)
+            .max_depth(max_depth)
+            .as_set(&"AS-FOO".parse().unwrap());
+
+        assert_eq!(
+            expansion.members,
+            expected.iter().copied().map(AsNumber::new).collect()
+        );
+        let depth_exceeded: Vec<_> = expansion
+            .issues
+            .iter()
+            .filter_map(|issue| match issue {
+                Issue::DepthExceeded(name) => Some(name.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(depth_exceeded, exceeded);
+    }
+
+    #[test]
+    fn expander_expands_route_sets() {
+        let objects = parse_whois_response(SETS).unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        let expansion = Expander::new(&resolver).route_set(&"RS-FOO".parse().unwrap());
+        let mut ranges: Vec<_> = expansion.members.iter().map(ToString::to_string).collect();
+        ranges.sort();
+
+        assert_eq!(
+            ranges,
+            [
+                "192.0.2.0/24^+",
+                "198.51.100.0/24^26",
+                "2001:db8::/32",
+                "203.0.113.0/24",
+                "203.0.113.0/24^-",
+                "203.0.113.0/25",
+                "203.0.113.0/26",
+            ]
+        );
+        assert_eq!(
+            expansion.issues[0],
+            Issue::Cycle(vec![
+                SetName::RouteSet("RS-BAR".parse().unwrap()),
+                SetName::RouteSet("RS-BAR".parse().unwrap()),
+            ])
+        );
+        assert!(expansion.issues.contains(&Issue::Missing(SetName::AsSet(
+            "AS-MISSING".parse().unwrap()
+        ))));
+        assert!(!expansion.is_complete());
+    }
+
+    #[test]
+    fn expander_expands_as_sets_referenced_with_several_operators() {
+        let objects = parse_whois_response(
+            "
+as-set:         AS-FOO
+members:        AS1
+
+route-set:      RS-X
+members:        AS-FOO, AS-FOO^+
+
+route-set:      RS-Y
+members:        AS-FOO, RS-Z
+
+route-set:      RS-Z
+members:        AS-FOO^-
+
+route:          192.0.2.0/24
+origin:         AS1
+
+",
+        )
+        .unwrap();
+        let resolver: ObjectResolver = objects.iter().collect();
+        let expander = Expander::new(&resolver);
+
+        for (name, expected) in [
+            ("RS-X", ["192.0.2.0/24", "192.0.2.0/24^+"]),
+            ("RS-Y", ["192.0.2.0/24", "192.0.2.0/24^-"]),
+        ] {
+            let expansion = expander.route_set(&name.parse().unwrap());
+            let mut ranges: Vec<_> = expansion.members.iter().map(ToString::to_string).collect();
+            ranges.sort();
+            assert_eq!(ranges, expected, "{name}");
+            assert!(expansion.issues.is_empty(), "{name}");
+        }
+    }
+
+    #[test]
+    fn expander_reports_missing_route_sets() {
+        let resolver = ObjectResolver::new();
+        let expansion = Expander::new(&resolver).prefixes(&"RS-MISSING^+".parse().unwrap());
+
+        assert!(expansion.members.is_empty());
+        assert_eq!(
+            expansion.issues,
+            [Issue::Missing(SetName::RouteSet(
+                "RS-MISSING".parse().unwrap()
+            ))]
+        );
+    }
+}
@@ -0,0 +1,553 @@
+//! Conversion between [`Object`]s and the RIPE Database REST API format.
+//!
+//! The RIPE Database REST API, as well as many tools built around it, exchange objects using a
+//! format that differs from the one produced by the crate's Serde implementations. Each object
+//! is typed by the name of its first attribute and each attribute contains a single value and an
+//! optional comment.
+//!
+//! ```json
+//! {
+//!   "objects": {
+//!     "object": [
+//!       {
+//!         "type": "route",
+//!         "source": { "id": "ripe" },
+//!         "attributes": {
+//!           "attribute": [
+//!             { "name": "route", "value": "193.254.30.0/24" },
+//!             { "name": "origin", "value": "AS12312", "comment": "ACME" },
+//!             { "name": "source", "value": "RIPE" }
+//!           ]
+//!         }
+//!       }
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! An equivalent XML representation is supported when the **xml** feature is enabled.
+//!
+//! ## Continuation lines and comments
+//!
+//! Values spanning multiple lines are represented using a single attribute, separating each line
+//! by a newline character. The comment of the last line, which starts with a `#` and extends
+//! until the end of the line, is moved into the `comment` of an attribute. When converting back
+//! into an [`Object`], the comment is appended to the last line of the value. Since an attribute
+//! has a single comment, the comments of the other lines are kept as part of the value.
+//!
+//! ```rust
+//! # #[cfg(feature = "json")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{object, ripe};
+//! use serde_json::json;
+//!
+//! let route = object! {
+//!     "route": "193.254.30.0/24";
+//!     "descr": "ACME Company", "Packet Street 6";
+//!     "origin": "AS12312 # ACME";
+//! };
+//! let json = ripe::to_json(&[route.clone()]);
+//! assert_eq!(
+//!     json,
+//!     json!({
+//!         "objects": {
+//!             "object": [{
+//!                 "type": "route",
+//!                 "attributes": {
+//!                     "attribute": [
+//!                         { "name": "route", "value": "193.254.30.0/24" },
+//!                         { "name": "descr", "value": "ACME Company\nPacket Street 6" },
+//!                         { "name": "origin", "value": "AS12312", "comment": "ACME" }
+//!                     ]
+//!                 }
+//!             }]
+//!         }
+//!     })
+//! );
+//! assert_eq!(ripe::from_json(&json)?, vec![route]);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "json"))]
+//! # fn main() {}
+//! ```
+
+use crate::{spec::Specification, Attribute, Name, Object, Value};
+
+/// An attribute as represented in the RIPE format.
+struct RipeAttribute {
+    name: String,
+    value: String,
+    comment: Option<String>,
+}
+
+impl RipeAttribute {
+    fn from_attribute<Spec: Specification>(attribute: &Attribute<'_, Spec>) -> Self {
+        let mut lines: Vec<&str> = attribute
+            .value
+            .values()
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+        let mut comment = None;
+        if let Some(last) = lines.last_mut() {
+            if let Some((value, text)) = last.split_once('#') {
+                *last = value.trim_end();
+                comment = Some(text.trim()).filter(|text| !text.is_empty());
+            }
+        }
+
+        Self {
+            name: attribute.name.to_string(),
+            value: lines.join("\n"),
+            comment: comment.map(ToOwned::to_owned),
+        }
+    }
+
+    fn into_attribute(self) -> Attribute<'static> {
+        let mut lines: Vec<String> = self.value.split('\n').map(ToOwned::to_owned).collect();
+        if let Some(comment) = self.comment.filter(|c| !c.trim().is_empty()) {
+            let last = lines.last_mut().expect("split returns at least one line");
+            if last.trim().is_empty() {
+                *last = format!("# {comment}");
+            } else {
+                *last = format!("{} # {comment}", last.trim_end());
+            }
+        }
+        let value: Value = lines.into();
+        Attribute::new(Name::new(self.name), value)
+    }
+}
+
+/// The type of an object, which is the name of its first attribute.
+fn object_type<'o, Spec: Specification>(object: &'o Object<'_, Spec>) -> Option<&'o str> {
+    object.first().map(|attribute| &*attribute.name)
+}
+
+/// The source of an object, lowercased like in the RIPE format.
+fn object_source<Spec: Specification>(object: &Object<'_, Spec>) -> Option<String> {
+    object
+        .get("source")
+        .first()
+        .and_then(|source| source.split_whitespace().next())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Convert objects into the RIPE Database REST API JSON format.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub fn to_json<Spec: Specification>(objects: &[Object<'_, Spec>]) -> serde_json::Value {
+    use serde_json::{json, Map};
+
+    let objects: Vec<serde_json::Value> = objects
+        .iter()
+        .map(|object| {
+            let attributes: Vec<serde_json::Value> = object
+                .iter()
+                .map(|attribute| {
+                    let RipeAttribute {
+                        name,
+                        value,
+                        comment,
+                    } = RipeAttribute::from_attribute(attribute);
+                    let mut attribute = json!({ "name": name, "value": value });
+                    if let Some(comment) = comment {
+                        attribute["comment"] = comment.into();
+                    }
+                    attribute
+                })
+                .collect();
+
+            let mut repr = Map::new();
+            if let Some(object_type) = object_type(object) {
+                repr.insert("type".to_string(), object_type.into());
+            }
+            if let Some(source) = object_source(object) {
+                repr.insert("source".to_string(), json!({ "id": source }));
+            }
+            repr.insert("attributes".to_string(), json!({ "attribute": attributes }));
+            serde_json::Value::Object(repr)
+        })
+        .collect();
+
+    json!({ "objects": { "object": objects } })
+}
+
+/// Convert objects from the RIPE Database REST API JSON format.
+///
+/// # Errors
+/// Returns an [`Error`] if the JSON does not match the expected format.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub fn from_json(json: &serde_json::Value) -> Result<Vec<Object<'static>>, Error> {
+    let objects = json
+        .pointer("/objects/object")
+        .and_then(serde_json::Value::as_array)
+        .ok_or(Error::MissingObjects)?;
+
+    objects
+        .iter()
+        .enumerate()
+        .map(|(index, object)| {
+            let attributes = object
+                .pointer("/attributes/attribute")
+                .and_then(serde_json::Value::as_array)
+                .ok_or(Error::MissingAttributes(index))?;
+
+            let attributes = attributes
+                .iter()
+                .map(|attribute| {
+                    let field = |field: &str| attribute.get(field).and_then(|v| v.as_str());
+                    let name = field("name").ok_or(Error::MissingName(index))?;
+                    Ok(RipeAttribute {
+                        name: name.to_string(),
+                        value: field("value").unwrap_or_default().to_string(),
+                        comment: field("comment").map(ToOwned::to_owned),
+                    }
+                    .into_attribute())
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            Ok(Object::new(attributes))
+        })
+        .collect()
+}
+
+/// Convert objects into the RIPE Database REST API XML format.
+#[cfg(feature = "xml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+#[must_use]
+pub fn to_xml<Spec: Specification>(objects: &[Object<'_, Spec>]) -> String {
+    use std::fmt::Write;
+
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n",
+        "<whois-resources>\n",
+        "    <objects>\n",
+    ));
+    for object in objects {
+        match object_type(object) {
+            Some(object_type) => {
+                let _ = writeln!(xml, "        <object type=\"{}\">", escape(object_type));
+            }
+            None => xml.push_str("        <object>\n"),
+        }
+        if let Some(source) = object_source(object) {
+            let _ = writeln!(xml, "            <source id=\"{}\"/>", escape(&source));
+        }
+        xml.push_str("            <attributes>\n");
+        for attribute in object.iter() {
+            let RipeAttribute {
+                name,
+                value,
+                comment,
+            } = RipeAttribute::from_attribute(attribute);
+            let _ = write!(
+                xml,
+                "                <attribute name=\"{}\" value=\"{}\"",
+                escape(&name),
+                escape(&value)
+            );
+            if let Some(comment) = comment {
+                let _ = write!(xml, " comment=\"{}\"", escape(&comment));
+            }
+            xml.push_str("/>\n");
+        }
+        xml.push_str("            </attributes>\n");
+        xml.push_str("        </object>\n");
+    }
+    xml.push_str("    </objects>\n");
+    xml.push_str("</whois-resources>\n");
+    xml
+}
+
+/// Convert objects from the RIPE Database REST API XML format.
+///
+/// # Errors
+/// Returns an [`Error`] if the XML is malformed or does not match the expected format.
+#[cfg(feature = "xml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+pub fn from_xml(xml: &str) -> Result<Vec<Object<'static>>, Error> {
+    use quick_xml::{
+        events::{BytesStart, Event},
+        Reader,
+    };
+
+    fn attribute(
+        reader: &Reader<&[u8]>,
+        element: &BytesStart,
+        name: &str,
+    ) -> Result<Option<String>, Error> {
+        let error = |message: String| Error::Xml {
+            position: reader.buffer_position(),
+            message,
+        };
+        element
+            .try_get_attribute(name)
+            .map_err(|e| error(e.to_string()))?
+            .map(|a| {
+                a.unescape_value()
+                    .map(std::borrow::Cow::into_owned)
+                    .map_err(|e| error(e.to_string()))
+            })
+            .transpose()
+    }
+
+    let mut reader = Reader::from_str(xml);
+    let mut objects = Vec::new();
+    let mut attributes: Option<Vec<Attribute<'static>>> = None;
+    let mut in_attributes = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| Error::Xml {
+            position: reader.error_position(),
+            message: e.to_string(),
+        })?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"object" => attributes = Some(Vec::new()),
+                b"attributes" => in_attributes = attributes.is_some(),
+                b"attribute" if in_attributes => {
+                    let name = attribute(&reader, &element, "name")?
+                        .ok_or(Error::MissingName(objects.len()))?;
+                    let ripe_attribute = RipeAttribute {
+                        name,
+                        value: attribute(&reader, &element, "value")?.unwrap_or_default(),
+                        comment: attribute(&reader, &element, "comment")?,
+                    };
+                    if let Some(attributes) = attributes.as_mut() {
+                        attributes.push(ripe_attribute.into_attribute());
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) => match element.name().as_ref() {
+                b"attributes" => in_attributes = false,
+                b"object" => {
+                    let attributes = attributes.take().ok_or(Error::UnexpectedEnd)?;
+                    objects.push(Object::new(attributes));
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Escape text for use within an XML attribute value, preserving whitespace characters.
+#[cfg(feature = "xml")]
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// An error that can occur when converting objects from the RIPE format.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The JSON does not contain an array of objects.
+    #[error("expected an `objects.object` array")]
+    MissingObjects,
+    /// The object at the index does not contain an array of attributes.
+    #[error("object {0}: expected an `attributes.attribute` array")]
+    MissingAttributes(usize),
+    /// An attribute of the object at the index has no name.
+    #[error("object {0}: attribute without a `name`")]
+    MissingName(usize),
+    /// The XML is malformed.
+    #[error("invalid XML at position {position}: {message}")]
+    Xml {
+        /// The byte offset into the XML at which the error occurred.
+        position: u64,
+        /// Why the XML is malformed.
+        message: String,
+    },
+    /// An object was closed before it was opened.
+    #[error("unexpected end of object")]
+    UnexpectedEnd,
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    #[cfg(feature = "json")]
+    use serde_json::json;
+
+    use super::*;
+    use crate::object;
+
+    fn objects() -> Vec<Object<'static>> {
+        vec![
+            object! {
+                "route": "193.254.30.0/24";
+                "descr": "ACME Company # Sales", "", "Packet Street 6";
+                "origin": "AS12312 # ACME";
+                "remarks": "";
+                "source": "RIPE # Filtered";
+            },
+            object! {
+                "mntner": "MAINT-ACME";
+                "remarks": "<Tubes> & \"Pipes\"";
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case(Attribute::new("origin", "AS12312"), "AS12312", None)]
+    #[case(Attribute::new("origin", "AS12312 # ACME"), "AS12312", Some("ACME"))]
+    #[case(Attribute::new("origin", "AS12312 #"), "AS12312", None)]
+    #[case(
+        Attribute::new("descr", vec!["ACME # first", "", "Company # second"]),
+        "ACME # first\n\nCompany",
+        Some("second")
+    )]
+    #[case(
+        Attribute::new("descr", vec!["ACME # first", "Company"]),
+        "ACME # first\nCompany",
+        None
+    )]
+    fn ripe_attribute_from_attribute(
+        #[case] attribute: Attribute,
+        #[case] value: &str,
+        #[case] comment: Option<&str>,
+    ) {
+        let ripe = RipeAttribute::from_attribute(&attribute);
+        assert_eq!(ripe.value, value);
+        assert_eq!(ripe.comment.as_deref(), comment);
+    }
+
+    #[rstest]
+    #[case(Attribute::new("descr", vec!["ACME # first", "Company"]))]
+    #[case(Attribute::new("descr", vec!["ACME # first", "", "Company # second"]))]
+    #[case(Attribute::new("descr", vec!["ACME", "# Company"]))]
+    fn ripe_attribute_roundtrip_keeps_comments_of_lines(#[case] attribute: Attribute) {
+        let ripe = RipeAttribute::from_attribute(&attribute);
+        assert_eq!(ripe.into_attribute(), attribute);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_repr() {
+        let json = to_json(&objects()[..1]);
+        assert_eq!(
+            json,
+            json!({
+                "objects": {
+                    "object": [{
+                        "type": "route",
+                        "source": { "id": "ripe" },
+                        "attributes": {
+                            "attribute": [
+                                { "name": "route", "value": "193.254.30.0/24" },
+                                { "name": "descr", "value": "ACME Company # Sales\n\nPacket Street 6" },
+                                { "name": "origin", "value": "AS12312", "comment": "ACME" },
+                                { "name": "remarks", "value": "" },
+                                { "name": "source", "value": "RIPE", "comment": "Filtered" }
+                            ]
+                        }
+                    }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_roundtrip() {
+        let objects = objects();
+        assert_eq!(from_json(&to_json(&objects)).unwrap(), objects);
+    }
+
+    #[rstest]
+    #[case(json!({}), "expected an `objects.object` array")]
+    #[case(
+        json!({ "objects": { "object": [{ "type": "route" }] } }),
+        "object 0: expected an `attributes.attribute` array"
+    )]
+    #[case(
+        json!({ "objects": { "object": [{ "attributes": { "attribute": [{ "value": "x" }] } }] } }),
+        "object 0: attribute without a `name`"
+    )]
+    #[cfg(feature = "json")]
+    fn from_json_invalid_is_expected_err(#[case] json: serde_json::Value, #[case] expected: &str) {
+        assert_eq!(from_json(&json).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "xml")]
+    fn xml_roundtrip() {
+        let objects = objects();
+        assert_eq!(from_xml(&to_xml(&objects)).unwrap(), objects);
+    }
+
+    #[test]
+    #[cfg(feature = "xml")]
+    /// Attributes that are not part of the object's attribute list, like its primary key, are ignored.
+    fn from_xml_ripe_response() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<whois-resources xmlns:xlink="http://www.w3.org/1999/xlink">
+    <objects>
+        <object type="aut-num">
+            <link xlink:type="locator" xlink:href="https://rest.db.ripe.net/ripe/aut-num/AS3333"/>
+            <source id="ripe"/>
+            <primary-key>
+                <attribute name="aut-num" value="AS3333"/>
+            </primary-key>
+            <attributes>
+                <attribute name="aut-num" value="AS3333"/>
+                <attribute name="as-name" value="RIPE-NCC-AS"/>
+                <attribute name="remarks" value="Reseaux IP Europeens&#10;Network Coordination Centre"/>
+                <attribute link="https://rest.db.ripe.net/ripe/mntner/RIPE-NCC-MNT" name="mnt-by" value="RIPE-NCC-MNT" referenced-type="mntner"/>
+                <attribute name="source" value="RIPE" comment="Filtered"/>
+            </attributes>
+        </object>
+    </objects>
+    <terms-and-conditions xlink:type="locator" xlink:href="https://apps.db.ripe.net/docs/HTML-Terms-And-Conditions"/>
+</whois-resources>
+"#;
+        assert_eq!(
+            from_xml(xml).unwrap(),
+            vec![object! {
+                "aut-num": "AS3333";
+                "as-name": "RIPE-NCC-AS";
+                "remarks": "Reseaux IP Europeens", "Network Coordination Centre";
+                "mnt-by": "RIPE-NCC-MNT";
+                "source": "RIPE # Filtered";
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("<objects><object></objects>")]
+    #[case("<objects><object><attributes><attribute value=\"x></object></objects>")]
+    #[cfg(feature = "xml")]
+    fn from_xml_malformed_is_err(#[case] xml: &str) {
+        assert!(matches!(from_xml(xml), Err(Error::Xml { .. })));
+    }
+
+    #[rstest]
+    #[case(
+        "<object><attributes><attribute value=\"x\"/></attributes></object>",
+        Error::MissingName(0)
+    )]
+    #[case("<object><object></object></object>", Error::UnexpectedEnd)]
+    #[cfg(feature = "xml")]
+    fn from_xml_invalid_is_expected_err(#[case] xml: &str, #[case] expected: Error) {
+        assert_eq!(from_xml(xml).unwrap_err(), expected);
+    }
+}
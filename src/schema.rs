@@ -0,0 +1,549 @@
+//! Validators defined by a declarative schema.
+//!
+//! A [`Schema`] describes the object classes of a registry and the syntax of their attribute
+//! values in TOML or JSON. It is compiled at runtime into a [`SchemaValidator`], which can be
+//! used to validate objects using [`Object::validate_with`](crate::Object::validate_with).
+//!
+//! The `attributes` table maps attribute names to their [`Syntax`], which is either the name of
+//! a [`Primitive`] type, a `regex` the entire value must match or a comma separated `list` of
+//! another syntax. Attributes without a syntax accept any value. Each class lists its attributes
+//! like a [`Template`], starting with the attribute of the class itself.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{object, schema::Schema};
+//!
+//! let schema = Schema::from_toml(
+//!     r#"
+//! [attributes]
+//! geofeed = "prefix"
+//! url = { regex = "https://\\S+" }
+//! ticket-ref = { list = { regex = "TICKET-[0-9]+" } }
+//! mnt-by = { list = "free-text" }
+//!
+//! [[classes.geofeed.attributes]]
+//! name = "geofeed"
+//! requirement = "mandatory"
+//! cardinality = "single"
+//! keys = { primary = true, lookup = true }
+//!
+//! [[classes.geofeed.attributes]]
+//! name = "url"
+//! requirement = "mandatory"
+//! cardinality = "single"
+//!
+//! [[classes.geofeed.attributes]]
+//! name = "ticket-ref"
+//! requirement = "optional"
+//! cardinality = "multiple"
+//!
+//! [[classes.geofeed.attributes]]
+//! name = "mnt-by"
+//! requirement = "mandatory"
+//! cardinality = "multiple"
+//! keys = { inverse = true }
+//! "#,
+//! )?;
+//! let validator = schema.compile()?;
+//!
+//! let geofeed = object! {
+//!     "geofeed": "192.0.2.0/24";
+//!     "url": "https://example.com/geofeed.csv";
+//!     "ticket-ref": "TICKET-1, TICKET-2";
+//!     "mnt-by": "MAINT-EXAMPLE";
+//! };
+//! geofeed.validate_with(&validator)?;
+//!
+//! let invalid = object! {
+//!     "geofeed": "192.0.2.0/24";
+//!     "url": "ftp://example.com/geofeed.csv";
+//! };
+//! let errors = invalid.validate_with(&validator).unwrap_err();
+//! assert_eq!(errors.iter_errors().count(), 1);
+//! assert_eq!(errors.object_errors()[0].to_string(), "missing mandatory attribute `mnt-by`");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    policy::{DefaultPolicy, Filter, Peering, Policy},
+    spec::{AttributeError, InvalidValueError, ObjectError, Rfc2622, Validator},
+    template::{self, AttributeTemplate, Cardinality, Keys, Requirement, Template},
+    types::{
+        self, AddressPrefixRange, AsNumber, AsSetName, Community, Date, EmailAddress,
+        FilterSetName, Ipv4Range, NicHandle, PeeringSetName, Prefix, RouteSetName, TypeError,
+    },
+    Attribute, Object,
+};
+
+/// A declarative description of object classes and the syntax of their attributes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    /// The syntax of attribute values by attribute name.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, Syntax>,
+    /// The classes of objects by class name.
+    #[serde(default)]
+    pub classes: BTreeMap<String, ClassSchema>,
+    /// Whether objects of classes not described by the schema are accepted.
+    #[serde(default)]
+    pub allow_unknown_classes: bool,
+}
+
+impl Schema {
+    /// Parse a schema from TOML.
+    ///
+    /// # Errors
+    /// Returns a [`SchemaError`] if the input is not a valid schema.
+    pub fn from_toml(s: &str) -> Result<Self, SchemaError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Parse a schema from JSON.
+    ///
+    /// # Errors
+    /// Returns a [`SchemaError`] if the input is not a valid schema.
+    pub fn from_json(s: &str) -> Result<Self, SchemaError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Compile the schema into a validator.
+    ///
+    /// # Errors
+    /// Returns a [`SchemaError`] if a regular expression is invalid or a class does not start
+    /// with the attribute of the class itself.
+    pub fn compile(&self) -> Result<SchemaValidator, SchemaError> {
+        let syntaxes = self
+            .attributes
+            .iter()
+            .map(|(name, syntax)| {
+                let compiled =
+                    CompiledSyntax::new(syntax).map_err(|source| SchemaError::Regex {
+                        attribute: name.clone(),
+                        source,
+                    })?;
+                Ok((name.to_ascii_lowercase(), compiled))
+            })
+            .collect::<Result<_, SchemaError>>()?;
+
+        let templates = self
+            .classes
+            .iter()
+            .map(|(class, schema)| {
+                let starts_with_class = schema
+                    .attributes
+                    .first()
+                    .is_some_and(|attribute| attribute.name.eq_ignore_ascii_case(class));
+                if !starts_with_class {
+                    return Err(SchemaError::Class {
+                        class: class.clone(),
+                        message: format!("the first attribute must be `{class}`"),
+                    });
+                }
+
+                let attributes = schema
+                    .attributes
+                    .iter()
+                    .map(|attribute| AttributeTemplate {
+                        name: attribute.name.clone().into(),
+                        requirement: attribute.requirement,
+                        cardinality: attribute.cardinality,
+                        keys: attribute.keys,
+                    })
+                    .collect();
+                let template = Template::with_attributes(class.clone(), attributes);
+                Ok(if schema.ordered {
+                    template.ordered()
+                } else {
+                    template
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SchemaValidator {
+            syntaxes,
+            templates,
+            allow_unknown_classes: self.allow_unknown_classes,
+        })
+    }
+}
+
+/// The attributes of an object class.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClassSchema {
+    /// The attributes of the class, starting with the attribute of the class itself.
+    pub attributes: Vec<AttributeSchema>,
+    /// Whether attributes must appear in the order they are listed in.
+    #[serde(default)]
+    pub ordered: bool,
+}
+
+/// An attribute of an object class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeSchema {
+    /// The name of the attribute.
+    pub name: String,
+    /// Whether the attribute must be present.
+    pub requirement: Requirement,
+    /// How often the attribute may appear.
+    pub cardinality: Cardinality,
+    /// The kinds of key the attribute is used as.
+    #[serde(default)]
+    pub keys: Keys,
+}
+
+/// The syntax of an attribute value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Syntax {
+    /// A primitive type, e.g. `"as-number"`.
+    Primitive(Primitive),
+    /// A regular expression matching the entire value, e.g. `{ regex = "TICKET-[0-9]+" }`.
+    Regex {
+        /// The regular expression.
+        regex: String,
+    },
+    /// A comma separated list of values of another syntax, e.g. `{ list = "as-number" }`.
+    List {
+        /// The syntax of each item of the list.
+        list: Box<Syntax>,
+    },
+}
+
+/// The primitive types an attribute value can be checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Primitive {
+    /// Any value.
+    FreeText,
+    /// An [`AsNumber`].
+    AsNumber,
+    /// A [`Prefix`] of either address family.
+    Prefix,
+    /// An IPv4 [`Prefix`].
+    Ipv4Prefix,
+    /// An IPv6 [`Prefix`].
+    Ipv6Prefix,
+    /// An [`AddressPrefixRange`].
+    AddressPrefixRange,
+    /// An [`Ipv4Range`].
+    Ipv4Range,
+    /// A [`Date`].
+    Date,
+    /// An [`EmailAddress`].
+    Email,
+    /// A [`NicHandle`].
+    NicHandle,
+    /// An [`AsSetName`].
+    AsSetName,
+    /// A [`RouteSetName`].
+    RouteSetName,
+    /// A [`FilterSetName`].
+    FilterSetName,
+    /// A [`PeeringSetName`].
+    PeeringSetName,
+    /// A [`Community`].
+    Community,
+    /// An `import` [`Policy`].
+    Import,
+    /// An `export` [`Policy`].
+    Export,
+    /// An `mp-import` [`Policy`].
+    MpImport,
+    /// An `mp-export` [`Policy`].
+    MpExport,
+    /// A `default` [`DefaultPolicy`].
+    Default,
+    /// An `mp-default` [`DefaultPolicy`].
+    MpDefault,
+    /// A [`Filter`].
+    Filter,
+    /// A [`Peering`].
+    Peering,
+}
+
+impl Primitive {
+    fn check(self, value: &str) -> Result<(), TypeError> {
+        fn parse<T: std::str::FromStr<Err = TypeError>>(value: &str) -> Result<(), TypeError> {
+            value.parse::<T>().map(drop)
+        }
+
+        match self {
+            Self::FreeText => Ok(()),
+            Self::AsNumber => parse::<AsNumber>(value),
+            Self::Prefix => parse::<Prefix>(value),
+            Self::Ipv4Prefix => match value.parse::<Prefix>()? {
+                prefix if prefix.is_ipv4() => Ok(()),
+                _ => Err(TypeError::new("prefix", value, 0, "must be an IPv4 prefix")),
+            },
+            Self::Ipv6Prefix => match value.parse::<Prefix>()? {
+                prefix if prefix.is_ipv6() => Ok(()),
+                _ => Err(TypeError::new("prefix", value, 0, "must be an IPv6 prefix")),
+            },
+            Self::AddressPrefixRange => parse::<AddressPrefixRange>(value),
+            Self::Ipv4Range => parse::<Ipv4Range>(value),
+            Self::Date => parse::<Date>(value),
+            Self::Email => parse::<EmailAddress>(value),
+            Self::NicHandle => parse::<NicHandle>(value),
+            Self::AsSetName => parse::<AsSetName>(value),
+            Self::RouteSetName => parse::<RouteSetName>(value),
+            Self::FilterSetName => parse::<FilterSetName>(value),
+            Self::PeeringSetName => parse::<PeeringSetName>(value),
+            Self::Community => parse::<Community>(value),
+            Self::Import => Policy::parse_import(value).map(drop),
+            Self::Export => Policy::parse_export(value).map(drop),
+            Self::MpImport => Policy::parse_mp_import(value).map(drop),
+            Self::MpExport => Policy::parse_mp_export(value).map(drop),
+            Self::Default => DefaultPolicy::parse(value).map(drop),
+            Self::MpDefault => DefaultPolicy::parse_mp(value).map(drop),
+            Self::Filter => Filter::parse(value).map(drop),
+            Self::Peering => Peering::parse(value).map(drop),
+        }
+    }
+}
+
+/// A [`Syntax`] with its regular expressions compiled.
+#[derive(Debug, Clone)]
+enum CompiledSyntax {
+    Primitive(Primitive),
+    Regex(String, regex::Regex),
+    List(Box<CompiledSyntax>),
+}
+
+impl CompiledSyntax {
+    fn new(syntax: &Syntax) -> Result<Self, regex::Error> {
+        Ok(match syntax {
+            Syntax::Primitive(primitive) => Self::Primitive(*primitive),
+            Syntax::Regex { regex } => {
+                Self::Regex(regex.clone(), regex::Regex::new(&format!("^(?:{regex})$"))?)
+            }
+            Syntax::List { list } => Self::List(Box::new(Self::new(list)?)),
+        })
+    }
+
+    fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            Self::Primitive(primitive) => primitive.check(value).map_err(|e| e.to_string()),
+            Self::Regex(pattern, regex) => {
+                if regex.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(format!("`{value}` does not match `{pattern}`"))
+                }
+            }
+            Self::List(syntax) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .try_for_each(|item| syntax.check(item)),
+        }
+    }
+}
+
+/// A validator compiled from a [`Schema`].
+///
+/// Attribute names and values are validated like [`Rfc2622`], values of attributes with a
+/// [`Syntax`] must match it. Objects must match the class they are of, as if it were a
+/// [`Template`].
+#[derive(Debug, Clone)]
+pub struct SchemaValidator {
+    syntaxes: HashMap<String, CompiledSyntax>,
+    templates: Vec<Template>,
+    allow_unknown_classes: bool,
+}
+
+impl SchemaValidator {
+    /// The templates of the classes described by the schema.
+    #[must_use]
+    pub fn templates(&self) -> &[Template] {
+        &self.templates
+    }
+}
+
+impl Validator for SchemaValidator {
+    fn check_attribute(&self, attribute: &Attribute<'_>) -> Result<(), AttributeError> {
+        Rfc2622.check_attribute(attribute)?;
+
+        if let Some(syntax) = self.syntaxes.get(&attribute.name.to_ascii_lowercase()) {
+            syntax
+                .check(&types::content(&attribute.value))
+                .map_err(|message| InvalidValueError::new(&attribute.value, message))?;
+        }
+        Ok(())
+    }
+
+    fn check_object(&self, object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+        let is_unknown = object
+            .first()
+            .is_some_and(|first| template::find(&self.templates, &first.name).is_none());
+        if is_unknown && self.allow_unknown_classes {
+            return Ok(());
+        }
+        template::validate(&self.templates, object)
+    }
+}
+
+/// A schema could not be parsed or compiled.
+#[derive(thiserror::Error, Debug)]
+pub enum SchemaError {
+    /// The schema is not valid TOML or does not match the expected structure.
+    #[error("invalid TOML schema: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The schema is not valid JSON or does not match the expected structure.
+    #[error("invalid JSON schema: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The regular expression of an attribute syntax is invalid.
+    #[error("invalid regular expression for attribute `{attribute}`: {source}")]
+    Regex {
+        /// The attribute the regular expression belongs to.
+        attribute: String,
+        /// Why the regular expression is invalid.
+        source: regex::Error,
+    },
+    /// A class of the schema is invalid.
+    #[error("invalid class `{class}`: {message}")]
+    Class {
+        /// The name of the class.
+        class: String,
+        /// Context about why the class is invalid.
+        message: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    const SCHEMA: &str = r#"
+allow_unknown_classes = true
+
+[attributes]
+ticket-ref = { list = { regex = "TICKET-[0-9]+" } }
+origin = "as-number"
+route = "ipv4-prefix"
+
+[[classes.route.attributes]]
+name = "route"
+requirement = "mandatory"
+cardinality = "single"
+keys = { primary = true }
+
+[[classes.route.attributes]]
+name = "origin"
+requirement = "mandatory"
+cardinality = "single"
+keys = { primary = true, inverse = true }
+
+[[classes.route.attributes]]
+name = "ticket-ref"
+requirement = "optional"
+cardinality = "multiple"
+"#;
+
+    fn validator() -> SchemaValidator {
+        Schema::from_toml(SCHEMA).unwrap().compile().unwrap()
+    }
+
+    #[test]
+    fn schema_from_json_equals_toml() {
+        let json = r#"{
+            "allow_unknown_classes": true,
+            "attributes": {
+                "ticket-ref": { "list": { "regex": "TICKET-[0-9]+" } },
+                "origin": "as-number",
+                "route": "ipv4-prefix"
+            },
+            "classes": {
+                "route": {
+                    "attributes": [
+                        { "name": "route", "requirement": "mandatory", "cardinality": "single",
+                          "keys": { "primary": true } },
+                        { "name": "origin", "requirement": "mandatory", "cardinality": "single",
+                          "keys": { "primary": true, "inverse": true } },
+                        { "name": "ticket-ref", "requirement": "optional", "cardinality": "multiple" }
+                    ]
+                }
+            }
+        }"#;
+        assert_eq!(
+            Schema::from_json(json).unwrap(),
+            Schema::from_toml(SCHEMA).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "ticket-ref": "TICKET-1,TICKET-2";
+        },
+        0,
+        0,
+    )]
+    #[case(
+        object! {
+            "route": "2001:db8::/32";
+            "origin": "65536";
+            "ticket-ref": "TICKET-1, CHANGE-2";
+        },
+        3,
+        0,
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "remarks": "Not in the schema";
+        },
+        0,
+        2,
+    )]
+    #[case(
+        object! {
+            "geofeed": "192.0.2.0/24";
+            "origin": "AS65536";
+        },
+        0,
+        0,
+    )]
+    fn schema_validator_validates_object(
+        #[case] object: Object,
+        #[case] attribute_errors: usize,
+        #[case] object_errors: usize,
+    ) {
+        match object.validate_with(&validator()) {
+            Ok(()) => assert_eq!((attribute_errors, object_errors), (0, 0)),
+            Err(error) => assert_eq!(
+                (error.iter_errors().count(), error.object_errors().len()),
+                (attribute_errors, object_errors)
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case("[attributes]\nurl = { regex = \"(\" }", "invalid regular expression")]
+    #[case(
+        "[[classes.route.attributes]]\nname = \"origin\"\nrequirement = \"mandatory\"\ncardinality = \"single\"",
+        "the first attribute must be `route`"
+    )]
+    #[case("[attributes]\norigin = \"asn\"", "invalid TOML schema")]
+    fn schema_invalid_is_error(#[case] schema: &str, #[case] expected: &str) {
+        let error = Schema::from_toml(schema)
+            .and_then(|schema| schema.compile())
+            .unwrap_err();
+        assert!(
+            error.to_string().contains(expected),
+            "expected `{expected}` in `{error}`"
+        );
+    }
+}
@@ -0,0 +1,804 @@
+//! Serialize user defined types into an [`Object`] using [Serde](https://serde.rs).
+//!
+//! The reverse of the [`de`](crate::de) module: a type implementing [`Serialize`] can be turned
+//! into an [`Object`] using [`to_object`], or directly into RPSL using [`to_string`].
+//!
+//! ```rust
+//! # use rpsl::ser::to_string;
+//! # use serde::Serialize;
+//! #[derive(Serialize)]
+//! struct Route {
+//!     route: String,
+//!     descr: Option<String>,
+//!     origin: String,
+//!     #[serde(rename = "mnt-by")]
+//!     mnt_by: Vec<String>,
+//!     source: &'static str,
+//! }
+//!
+//! let route = Route {
+//!     route: "193.254.30.0/24".to_string(),
+//!     descr: None,
+//!     origin: "AS12312".to_string(),
+//!     mnt_by: vec!["MAINT-AS3257".to_string(), "MAINT-AS12312".to_string()],
+//!     source: "RIPE",
+//! };
+//! assert_eq!(
+//!     to_string(&route)?,
+//!     concat!(
+//!         "route:          193.254.30.0/24\n",
+//!         "origin:         AS12312\n",
+//!         "mnt-by:         MAINT-AS3257\n",
+//!         "mnt-by:         MAINT-AS12312\n",
+//!         "source:         RIPE\n",
+//!         "\n",
+//!     )
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Mapping
+//!
+//! Each field of a struct, or entry of a map, becomes an attribute named after the field, in
+//! the order they are serialized.
+//!
+//! - A scalar, like a string, an integer or a unit enum variant, becomes a single attribute.
+//!   Strings containing newlines become a value spanning multiple lines.
+//! - A sequence, like a [`Vec`], becomes one attribute per element.
+//! - [`None`], unit values and empty sequences are omitted.
+//!
+//! Nested structs and maps cannot be represented in RPSL and result in an [`Error`], as do field
+//! names and map keys that are not valid attribute names according to RFC 2622.
+
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::{spec::Rfc2622, Attribute, Name, Object, Value};
+
+/// Serialize the given value into an [`Object`].
+///
+/// # Errors
+/// Returns an [`Error`] if the value cannot be represented as an object.
+pub fn to_object<T>(value: &T) -> Result<Object<'static>, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// Serialize the given value into RPSL.
+///
+/// # Errors
+/// Returns an [`Error`] if the value cannot be represented as an object.
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize + ?Sized,
+{
+    to_object(value).map(|object| object.to_string())
+}
+
+/// A Serde [`Serializer`](serde::Serializer) that turns structs and maps into an [`Object`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+macro_rules! unsupported {
+    ($error:expr; $($method:ident($($arg:ty),*))*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                Err($error)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Object<'static>;
+    type Error = Error;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = ObjectSerializer;
+    type SerializeStruct = ObjectSerializer;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    unsupported! {
+        Error::UnsupportedObject;
+        serialize_bool(bool) serialize_i8(i8) serialize_i16(i16) serialize_i32(i32)
+        serialize_i64(i64) serialize_u8(u8) serialize_u16(u16) serialize_u32(u32)
+        serialize_u64(u64) serialize_f32(f32) serialize_f64(f64) serialize_char(char)
+        serialize_str(&str) serialize_bytes(&[u8]) serialize_none() serialize_unit()
+        serialize_unit_struct(&'static str)
+        serialize_unit_variant(&'static str, u32, &'static str)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(ObjectSerializer::new(len.unwrap_or_default()))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(ObjectSerializer::new(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::UnsupportedObject)
+    }
+}
+
+/// Collects the fields of a struct or entries of a map as attributes.
+#[doc(hidden)]
+pub struct ObjectSerializer {
+    attributes: Vec<Attribute<'static>>,
+    next_name: Option<String>,
+}
+
+impl ObjectSerializer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            attributes: Vec::with_capacity(capacity),
+            next_name: None,
+        }
+    }
+
+    fn serialize_attributes<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), Error> {
+        Rfc2622::validate_name(&Name::new(name)).map_err(|error| Error::InvalidName {
+            name: name.to_string(),
+            message: error.message,
+        })?;
+        value
+            .serialize(AttributeSerializer {
+                name,
+                attributes: &mut self.attributes,
+            })
+            .map_err(|error| Error::Attribute {
+                name: name.to_string(),
+                error: Box::new(error),
+            })
+    }
+}
+
+impl ser::SerializeStruct for ObjectSerializer {
+    type Ok = Object<'static>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.serialize_attributes(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Object::new(self.attributes))
+    }
+}
+
+impl ser::SerializeMap for ObjectSerializer {
+    type Ok = Object<'static>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        let mut lines = Vec::new();
+        key.serialize(LineSerializer { lines: &mut lines })?;
+        match <[String; 1]>::try_from(lines) {
+            Ok([name]) => {
+                self.next_name = Some(name);
+                Ok(())
+            }
+            Err(_) => Err(Error::KeyNotSingleLine),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let name = self.next_name.take().ok_or(Error::ValueBeforeKey)?;
+        self.serialize_attributes(&name, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Object::new(self.attributes))
+    }
+}
+
+/// Serializes the value of a field into zero or more attributes with the same name.
+struct AttributeSerializer<'s> {
+    name: &'s str,
+    attributes: &'s mut Vec<Attribute<'static>>,
+}
+
+impl AttributeSerializer<'_> {
+    fn push(self, value: &str) {
+        let value: Value = value.split('\n').collect::<Vec<_>>().into();
+        self.attributes
+            .push(Attribute::new(Name::new(self.name), value));
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($ty:ty))*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                self.push(&v.to_string());
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'s> ser::Serializer for AttributeSerializer<'s> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = AttributeSeqSerializer<'s>;
+    type SerializeTuple = AttributeSeqSerializer<'s>;
+    type SerializeTupleStruct = AttributeSeqSerializer<'s>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_display! {
+        serialize_bool(bool) serialize_i8(i8) serialize_i16(i16) serialize_i32(i32)
+        serialize_i64(i64) serialize_i128(i128) serialize_u8(u8) serialize_u16(u16)
+        serialize_u32(u32) serialize_u64(u64) serialize_u128(u128) serialize_f32(f32)
+        serialize_f64(f64) serialize_char(char)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.push(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::Bytes)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.push(variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedAttribute)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(AttributeSeqSerializer {
+            name: self.name,
+            attributes: self.attributes,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::UnsupportedAttribute)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::UnsupportedAttribute)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::UnsupportedAttribute)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::UnsupportedAttribute)
+    }
+}
+
+/// Serializes each element of a sequence into a separate attribute.
+#[doc(hidden)]
+pub struct AttributeSeqSerializer<'s> {
+    name: &'s str,
+    attributes: &'s mut Vec<Attribute<'static>>,
+}
+
+impl AttributeSeqSerializer<'_> {
+    fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(AttributeSerializer {
+            name: self.name,
+            attributes: self.attributes,
+        })
+    }
+}
+
+impl ser::SerializeSeq for AttributeSeqSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for AttributeSeqSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for AttributeSeqSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+/// Serializes a scalar into its lines, used for map keys.
+struct LineSerializer<'s> {
+    lines: &'s mut Vec<String>,
+}
+
+impl ser::Serializer for LineSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_display! {
+        serialize_bool(bool) serialize_i8(i8) serialize_i16(i16) serialize_i32(i32)
+        serialize_i64(i64) serialize_u8(u8) serialize_u16(u16) serialize_u32(u32)
+        serialize_u64(u64) serialize_f32(f32) serialize_f64(f64) serialize_char(char)
+        serialize_str(&str)
+    }
+
+    unsupported! {
+        Error::KeyNotString;
+        serialize_bytes(&[u8]) serialize_none() serialize_unit()
+        serialize_unit_struct(&'static str)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.push(variant);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::KeyNotString)
+    }
+}
+
+impl LineSerializer<'_> {
+    fn push(self, value: &str) {
+        self.lines.extend(value.split('\n').map(ToOwned::to_owned));
+    }
+}
+
+/// An error that can occur when serializing into an [`Object`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A custom error reported by the value being serialized.
+    #[error("{0}")]
+    Custom(String),
+    /// The value of an attribute cannot be serialized.
+    #[error("`{name}`: {error}")]
+    Attribute {
+        /// The name of the attribute.
+        name: String,
+        /// Why the value cannot be serialized.
+        error: Box<Error>,
+    },
+    /// The name of an attribute is not a valid attribute name.
+    #[error("invalid attribute name `{name}`: {message}")]
+    InvalidName {
+        /// The invalid name.
+        name: String,
+        /// Why the name is invalid.
+        message: String,
+    },
+    /// The value is neither a struct nor a map.
+    #[error("only structs and maps can be serialized into an object")]
+    UnsupportedObject,
+    /// The value of an attribute is a nested structure.
+    #[error("nested structures cannot be serialized into an attribute")]
+    UnsupportedAttribute,
+    /// The value of an attribute is a byte array.
+    #[error("bytes cannot be serialized into an attribute")]
+    Bytes,
+    /// A map key is not a string.
+    #[error("map keys must be strings")]
+    KeyNotString,
+    /// A map key spans several lines.
+    #[error("map keys must be serialized as a single line")]
+    KeyNotSingleLine,
+    /// A map value was serialized without a key.
+    #[error("value serialized before key")]
+    ValueBeforeKey,
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{de::from_object, object};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "kebab-case")]
+    struct Ipv6Route {
+        route6: String,
+        descr: Option<String>,
+        origin: String,
+        #[serde(default)]
+        member_of: Vec<String>,
+        mnt_by: Vec<String>,
+        source: String,
+    }
+
+    #[test]
+    fn serialize_struct() {
+        let route = Ipv6Route {
+            route6: "2001:db8::/32".to_string(),
+            descr: Some("Documentation".to_string()),
+            origin: "AS64496".to_string(),
+            member_of: vec![],
+            mnt_by: vec!["MAINT-AS64496".to_string(), "MAINT-AS3257".to_string()],
+            source: "RIPE".to_string(),
+        };
+        assert_eq!(
+            to_object(&route).unwrap(),
+            object! {
+                "route6": "2001:db8::/32";
+                "descr": "Documentation";
+                "origin": "AS64496";
+                "mnt-by": "MAINT-AS64496";
+                "mnt-by": "MAINT-AS3257";
+                "source": "RIPE";
+            }
+        );
+    }
+
+    #[test]
+    /// Objects serialized from a type deserialize back into the same type.
+    fn serialize_roundtrip() {
+        let route = Ipv6Route {
+            route6: "2001:db8::/32".to_string(),
+            descr: None,
+            origin: "AS64496".to_string(),
+            member_of: vec!["AS64496:RS-DOCUMENTATION".to_string()],
+            mnt_by: vec!["MAINT-AS64496".to_string()],
+            source: "RIPE".to_string(),
+        };
+        let object = to_object(&route).unwrap();
+        assert_eq!(from_object::<Ipv6Route, _>(&object).unwrap(), route);
+    }
+
+    #[test]
+    fn serialize_multi_line_string() {
+        #[derive(Serialize)]
+        struct Remarks {
+            remarks: &'static str,
+        }
+
+        let object = to_object(&Remarks {
+            remarks: "Locations\nLA1 - CoreSite One Wilshire",
+        })
+        .unwrap();
+        assert_eq!(
+            object,
+            object! {
+                "remarks": "Locations", "LA1 - CoreSite One Wilshire";
+            }
+        );
+    }
+
+    #[test]
+    fn serialize_map_and_scalars() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Status {
+            Assigned,
+        }
+
+        #[derive(Serialize)]
+        struct Fields {
+            #[serde(rename = "local-as")]
+            local_as: u32,
+            status: Status,
+            unit: (),
+        }
+
+        assert_eq!(
+            to_object(&Fields {
+                local_as: 3257,
+                status: Status::Assigned,
+                unit: (),
+            })
+            .unwrap(),
+            object! {
+                "local-as": "3257";
+                "status": "assigned";
+            }
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert("address", vec!["Packet Street 6", "Internet"]);
+        map.insert("role", vec!["ACME Company"]);
+        assert_eq!(
+            to_object(&map).unwrap(),
+            object! {
+                "address": "Packet Street 6";
+                "address": "Internet";
+                "role": "ACME Company";
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(
+        vec!["not", "an", "object"],
+        "only structs and maps can be serialized into an object"
+    )]
+    #[case(
+        BTreeMap::from([("nested", BTreeMap::from([("key", "value")]))]),
+        "`nested`: nested structures cannot be serialized into an attribute"
+    )]
+    #[case(
+        BTreeMap::from([("", "value")]),
+        "invalid attribute name ``: must be at least two characters long"
+    )]
+    #[case(
+        BTreeMap::from([("mnt-by: X", "value")]),
+        "invalid attribute name `mnt-by: X`: may only contain ASCII letters, digits, '-' or '_'"
+    )]
+    #[case(
+        BTreeMap::from([("mnt by", "value")]),
+        "invalid attribute name `mnt by`: may only contain ASCII letters, digits, '-' or '_'"
+    )]
+    fn serialize_unsupported_is_expected_err<T: Serialize>(
+        #[case] value: T,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(to_object(&value).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn serialize_to_string() {
+        #[derive(Serialize)]
+        struct Role {
+            role: &'static str,
+            address: [&'static str; 2],
+        }
+
+        assert_eq!(
+            to_string(&Role {
+                role: "ACME Company",
+                address: ["Packet Street 6", "Internet"],
+            })
+            .unwrap(),
+            concat!(
+                "role:           ACME Company\n",
+                "address:        Packet Street 6\n",
+                "address:        Internet\n",
+                "\n",
+            )
+        );
+    }
+}
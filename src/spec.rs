@@ -20,20 +20,116 @@
 //! ## Validation vs conversion
 //!
 //! - Use [`Object::validate`](crate::Object::validate) to keep an object as-is while checking it
-//!   against a specification. This collects all attribute failures so you can report everything
-//!   that is invalid at once.
+//!   against a specification. This collects all attribute and object failures so you can report
+//!   everything that is invalid at once.
 //! - Use [`Object::into_spec`](crate::Object::into_spec) to convert into a typed object. This
-//!   validates as it converts and returns the first [`AttributeError`] encountered.
+//!   validates as it converts and stops at the first [`AttributeError`] encountered. Rules
+//!   concerning the object as a whole are not checked.
+//! - Use [`Object::into_validated`](crate::Object::into_validated) to convert into a typed
+//!   object after checking it like `validate`, including the rules concerning the object as a
+//!   whole.
 //!
 //! ## Errors
 //!
 //! The primary error type is [`AttributeError`], which wraps [`InvalidNameError`] and
 //! [`InvalidValueError`]. These errors explain why a single attribute failed validation.
+//! Rules spanning several attributes, such as an attribute that must appear exactly once, are
+//! reported as [`ObjectError`]s.
 //!
-//! Implement [`Specification`] to define custom rules by validating each attribute.
+//! [`Rfc2622Templates`] additionally validates objects against the templates of their class, see
+//! the [`template`] module. [`RipeDb`] enforces the templates and syntax rules of the RIPE
+//! Database, while [`Rfc4012`] checks the syntax of routing policies including the multiprotocol
+//! attributes of `RPSLng`. [`Irrd`] reproduces the strict validation of `IRRd` 4.
+//!
+//! Implement [`Specification`] to define custom rules by validating each attribute and,
+//! optionally, the object as a whole:
+//!
+//! ```rust
+//! # use rpsl::{object, Attribute, Object, spec::{AttributeError, ObjectError, Specification}};
+//! #[derive(Debug, Clone, Copy)]
+//! struct SingleOrigin;
+//!
+//! impl Specification for SingleOrigin {
+//!     fn validate_attribute(_attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+//!         Ok(())
+//!     }
+//!
+//!     fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+//!         if object.get("origin").len() == 1 {
+//!             Ok(())
+//!         } else {
+//!             Err(vec![ObjectError::new("route must have exactly one origin")])
+//!         }
+//!     }
+//! }
+//!
+//! let route = object! {
+//!     "route": "192.0.2.0/24";
+//!     "origin": "AS65536";
+//!     "origin": "AS65537";
+//! };
+//! let errors = route.validate::<SingleOrigin>().unwrap_err();
+//! assert_eq!(errors.object_errors().len(), 1);
+//! ```
+//!
+//! ## Combining specifications
+//!
+//! [`All`] and [`Any`] combine several specifications into one, and [`Custom`] turns a [`Rule`]
+//! into a specification. For example, to require everything [`Rfc2622`] checks as well as an
+//! `org` attribute in every object:
+//!
+//! ```rust
+//! # use rpsl::{object, Object, spec::{All, Custom, ObjectError, Rfc2622, Rule}};
+//! #[derive(Debug, Clone, Copy)]
+//! struct HasOrg;
+//!
+//! impl Rule for HasOrg {
+//!     fn validate_object(object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+//!         if object.get("org").is_empty() {
+//!             Err(vec![ObjectError::new("object must have an `org` attribute")])
+//!         } else {
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//!
+//! type Corporate = All<(Rfc2622, Custom<HasOrg>)>;
+//!
+//! let role = object! {
+//!     "role": "ACME Company";
+//!     "org": "ORG-ACME1-RIPE";
+//! };
+//! assert!(role.validate::<Corporate>().is_ok());
+//!
+//! let person = object! {
+//!     "person": "John Doe";
+//!     "d!ne": "invalid name";
+//! };
+//! let errors = person.validate::<Corporate>().unwrap_err();
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors.object_errors().len(), 1);
+//! ```
+//!
+//! ## Selecting a specification at runtime
+//!
+//! When the rules to validate against are only known at runtime, for example from
+//! configuration, use a [`Validator`] with [`Object::validate_with`](crate::Object::validate_with).
+//! Every [`Specification`] is a validator, and [`Builtin`] selects one of the specifications of
+//! this crate by name.
 
 use super::attribute::{Attribute, Name, Value};
-use std::fmt::Debug;
+use crate::{
+    policy::{self, DefaultPolicy, Filter, Peering, Policy},
+    resolve::Member,
+    template,
+    types::{self, AsNumber, EmailAddress, Prefix, TypeError},
+    Object,
+};
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    str::FromStr,
+};
 
 /// Defines how parsed attributes should be validated for a given specification.
 pub trait Specification: Debug + Clone + Copy {
@@ -43,6 +139,17 @@ pub trait Specification: Debug + Clone + Copy {
     /// Returns an [`AttributeError`] when the attribute name or value does not satisfy
     /// the specification's rules.
     fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError>;
+
+    /// Validate an object as a whole according to the specification.
+    ///
+    /// This is called after each attribute has been checked by [`Specification::validate_attribute`]
+    /// and allows rules involving several attributes. By default, no object level rules apply.
+    ///
+    /// # Errors
+    /// Returns every [`ObjectError`] describing a rule the object does not satisfy.
+    fn validate_object(_object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        Ok(())
+    }
 }
 
 /// Default specification after parsing, does not perform any validation.
@@ -60,7 +167,9 @@ impl Specification for Raw {
 pub struct Rfc2622;
 
 impl Rfc2622 {
-    fn validate_name<Spec: Specification>(name: &Name<Spec>) -> Result<(), InvalidNameError> {
+    pub(crate) fn validate_name<Spec: Specification>(
+        name: &Name<Spec>,
+    ) -> Result<(), InvalidNameError> {
         if name.len() < 2 {
             return Err(InvalidNameError::new(
                 name,
@@ -145,6 +254,646 @@ impl Specification for Rfc2622 {
     }
 }
 
+/// Validation rules matching RFC 2622, enforcing the templates of the classes it defines.
+///
+/// In addition to the attribute checks of [`Rfc2622`], objects must be of a class listed in
+/// [`template::RFC2622`] and contain the attributes required by its template. Deviating from
+/// RFC 2622, `descr` may be repeated, see [`template::RFC2622`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rfc2622Templates;
+
+impl Specification for Rfc2622Templates {
+    fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+        Rfc2622::validate_name(&attribute.name)?;
+        Rfc2622::validate_value(&attribute.value)?;
+        Ok(())
+    }
+
+    fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        template::validate(template::RFC2622, object)
+    }
+}
+
+/// Validation rules of the RIPE Database.
+///
+/// In addition to the attribute checks of [`Rfc2622`], objects must be of a class listed in
+/// [`template::RIPE`] and match its template. The values of attributes with a RIPE specific
+/// syntax are checked as well:
+///
+/// - `aut-num` and `origin` must be AS numbers, `route` and `route6` IPv4 and IPv6 prefixes.
+/// - `e-mail`, `abuse-mailbox` and `upd-to` must be email addresses.
+/// - `country` must be an ISO 3166 country code, `EU` or `ZZ`.
+/// - `org-type` must be one of the organisation types assigned by the RIPE NCC.
+/// - `status` must be one of the values allowed for `inetnum`, `inet6num` or `aut-num` objects.
+/// - `source` must be `RIPE`, or `RIPE-NONAUTH` for `aut-num`, `as-set`, `route` and `route6`
+///   objects. Objects mirrored from other registries using the `-GRS` suffix are rejected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RipeDb;
+
+impl RipeDb {
+    const COUNTRIES: &[&str] = &[
+        "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX",
+        "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ",
+        "BR", "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK",
+        "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+        "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "EU", "FI", "FJ", "FK", "FM", "FO",
+        "FR", "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR",
+        "GS", "GT", "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM",
+        "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM",
+        "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU",
+        "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP",
+        "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG",
+        "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL",
+        "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB",
+        "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS", "ST",
+        "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+        "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG",
+        "VI", "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW", "ZZ",
+    ];
+
+    const ORG_TYPES: &[&str] = &[
+        "IANA",
+        "RIR",
+        "NIR",
+        "LIR",
+        "WHITEPAGES",
+        "DIRECT_ASSIGNMENT",
+        "OTHER",
+    ];
+
+    const NONAUTH_CLASSES: &[&str] = &["aut-num", "as-set", "route", "route6"];
+
+    /// The values of the `status` attribute allowed for a class.
+    fn statuses(class: &str) -> Option<&'static [&'static str]> {
+        match class {
+            "inetnum" => Some(&[
+                "ALLOCATED PA",
+                "ALLOCATED-ASSIGNED PA",
+                "ALLOCATED UNSPECIFIED",
+                "LIR-PARTITIONED PA",
+                "SUB-ALLOCATED PA",
+                "ASSIGNED PA",
+                "ASSIGNED PI",
+                "ASSIGNED ANYCAST",
+                "LEGACY",
+            ]),
+            "inet6num" => Some(&[
+                "ALLOCATED-BY-RIR",
+                "ALLOCATED-ASSIGNED PA",
+                "ALLOCATED-BY-LIR",
+                "AGGREGATED-BY-LIR",
+                "ASSIGNED",
+                "ASSIGNED ANYCAST",
+                "ASSIGNED PI",
+            ]),
+            "aut-num" => Some(&["ASSIGNED", "LEGACY", "OTHER"]),
+            _ => None,
+        }
+    }
+
+    fn validate_syntax<Spec: Specification>(
+        attribute: &Attribute<'_, Spec>,
+    ) -> Result<(), InvalidValueError> {
+        let value = &attribute.value;
+        let one_of = |allowed: &[&str], kind: &str| {
+            let content = types::content(value).to_ascii_uppercase();
+            if allowed.contains(&content.as_str()) {
+                Ok(())
+            } else {
+                Err(InvalidValueError::new(
+                    value,
+                    format!("`{content}` is not a valid {kind}"),
+                ))
+            }
+        };
+
+        match attribute.name.to_ascii_lowercase().as_str() {
+            "aut-num" | "origin" => types::parse_value::<AsNumber, _>(value).map(drop),
+            "route" | "route6" => {
+                let prefix = types::parse_value::<Prefix, _>(value)?;
+                match (
+                    attribute.name.eq_ignore_ascii_case("route"),
+                    prefix.is_ipv4(),
+                ) {
+                    (true, false) => Err(InvalidValueError::new(value, "must be an IPv4 prefix")),
+                    (false, true) => Err(InvalidValueError::new(value, "must be an IPv6 prefix")),
+                    _ => Ok(()),
+                }
+            }
+            "e-mail" | "abuse-mailbox" | "upd-to" => {
+                types::parse_value::<EmailAddress, _>(value).map(drop)
+            }
+            "country" => one_of(Self::COUNTRIES, "country code"),
+            "org-type" => one_of(Self::ORG_TYPES, "organisation type"),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_source(class: &str, source: &str) -> Result<(), String> {
+        if source.eq_ignore_ascii_case("RIPE") {
+            Ok(())
+        } else if source.eq_ignore_ascii_case("RIPE-NONAUTH") {
+            if Self::NONAUTH_CLASSES.contains(&class) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "`{source}` is not a valid source for class `{class}`"
+                ))
+            }
+        } else if source.to_ascii_uppercase().ends_with("-GRS") {
+            Err(format!(
+                "`{source}` is mirrored from another registry and cannot be submitted"
+            ))
+        } else {
+            Err(format!("`{source}` is not a valid source, expected `RIPE`"))
+        }
+    }
+}
+
+impl Specification for RipeDb {
+    fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+        Rfc2622::validate_name(&attribute.name)?;
+        Rfc2622::validate_value(&attribute.value)?;
+        Self::validate_syntax(attribute)?;
+        Ok(())
+    }
+
+    fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        let mut errors = template::validate(template::RIPE, object)
+            .err()
+            .unwrap_or_default();
+
+        let class = object
+            .first()
+            .map(|attribute| attribute.name.to_ascii_lowercase())
+            .unwrap_or_default();
+        for (index, attribute) in object.iter().enumerate() {
+            let content = types::content(&attribute.value);
+            if attribute.name.eq_ignore_ascii_case("status") {
+                if let Some(statuses) = Self::statuses(&class) {
+                    if !statuses.contains(&content.to_ascii_uppercase().as_str()) {
+                        errors.push(ObjectError::at(
+                            index,
+                            format!("`{content}` is not a valid status for class `{class}`"),
+                        ));
+                    }
+                }
+            } else if attribute.name.eq_ignore_ascii_case("source") {
+                if let Err(message) = Self::validate_source(&class, &content) {
+                    errors.push(ObjectError::at(index, message));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Validation rules matching RFC 4012, the multiprotocol extensions to RPSL (`RPSLng`).
+///
+/// In addition to the attribute checks of [`Rfc2622`], the values of the policy attributes are
+/// parsed using the [`policy`] module, including the `afi` lists of `mp-import`, `mp-export` and
+/// `mp-default`. `route` must contain an IPv4 and `route6` an IPv6 prefix, and the
+/// `mp-members` of a `route-set` must be address prefix ranges of either family or references
+/// to other sets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rfc4012;
+
+impl Rfc4012 {
+    fn validate_syntax<Spec: Specification>(
+        attribute: &Attribute<'_, Spec>,
+    ) -> Result<(), InvalidValueError> {
+        let value = &attribute.value;
+        let parse = |parser: fn(&str) -> Result<(), TypeError>| {
+            parser(&policy::text(value)).map_err(|e| InvalidValueError::new(value, e.to_string()))
+        };
+
+        match attribute.name.to_ascii_lowercase().as_str() {
+            "route" => parse(|text| match text.parse::<Prefix>()? {
+                prefix if prefix.is_ipv4() => Ok(()),
+                _ => Err(TypeError::new("prefix", text, 0, "must be an IPv4 prefix")),
+            }),
+            "route6" => parse(|text| match text.parse::<Prefix>()? {
+                prefix if prefix.is_ipv6() => Ok(()),
+                _ => Err(TypeError::new("prefix", text, 0, "must be an IPv6 prefix")),
+            }),
+            "import" => parse(|text| Policy::parse_import(text).map(drop)),
+            "export" => parse(|text| Policy::parse_export(text).map(drop)),
+            "default" => parse(|text| DefaultPolicy::parse(text).map(drop)),
+            "mp-import" => parse(|text| Policy::parse_mp_import(text).map(drop)),
+            "mp-export" => parse(|text| Policy::parse_mp_export(text).map(drop)),
+            "mp-default" => parse(|text| DefaultPolicy::parse_mp(text).map(drop)),
+            "mp-filter" => parse(|text| Filter::parse(text).map(drop)),
+            "mp-peering" => parse(|text| Peering::parse(text).map(drop)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Specification for Rfc4012 {
+    fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+        Rfc2622::validate_name(&attribute.name)?;
+        Rfc2622::validate_value(&attribute.value)?;
+        Self::validate_syntax(attribute)?;
+        Ok(())
+    }
+
+    fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        let is_route_set = object
+            .first()
+            .is_some_and(|attribute| attribute.name.eq_ignore_ascii_case("route-set"));
+        if !is_route_set {
+            return Ok(());
+        }
+
+        let errors: Vec<ObjectError> = object
+            .iter()
+            .enumerate()
+            .filter(|(_, attribute)| attribute.name.eq_ignore_ascii_case("mp-members"))
+            .flat_map(|(index, attribute)| {
+                types::content(&attribute.value)
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|member| !member.is_empty())
+                    .filter_map(|member| member.parse::<Member>().err())
+                    .map(move |error| ObjectError::at(index, error.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Validation rules matching the strict validation mode of `IRRd` 4.
+///
+/// Attributes are validated like [`Rfc4012`], additionally requiring `aut-num` and `origin` to
+/// be AS numbers. Objects must be of a class listed in [`template::IRRD`] and match its
+/// template, which rejects unknown attributes and requires `mnt-by` for every class. The prefix
+/// of a `route` or `route6` object must not have host bits set.
+///
+/// The `rpki-ov-state` annotation `IRRd` adds to `route` and `route6` objects is accepted with
+/// one of the values `valid`, `invalid` or `not_found`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Irrd;
+
+impl Irrd {
+    const RPKI_OV_STATES: &[&str] = &["valid", "invalid", "not_found"];
+
+    fn validate_syntax<Spec: Specification>(
+        attribute: &Attribute<'_, Spec>,
+    ) -> Result<(), InvalidValueError> {
+        let value = &attribute.value;
+        match attribute.name.to_ascii_lowercase().as_str() {
+            "aut-num" | "origin" => types::parse_value::<AsNumber, _>(value).map(drop),
+            "rpki-ov-state" => {
+                let state = types::content(value);
+                if Self::RPKI_OV_STATES
+                    .iter()
+                    .any(|valid| valid.eq_ignore_ascii_case(&state))
+                {
+                    Ok(())
+                } else {
+                    Err(InvalidValueError::new(
+                        value,
+                        "expected one of `valid`, `invalid` or `not_found`",
+                    ))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Specification for Irrd {
+    fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+        Rfc2622::validate_name(&attribute.name)?;
+        Rfc2622::validate_value(&attribute.value)?;
+        Rfc4012::validate_syntax(attribute)?;
+        Self::validate_syntax(attribute)?;
+        Ok(())
+    }
+
+    fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        let mut errors = template::validate(template::IRRD, object)
+            .err()
+            .unwrap_or_default();
+
+        if let Some(first) = object.first().filter(|attribute| {
+            attribute.name.eq_ignore_ascii_case("route")
+                || attribute.name.eq_ignore_ascii_case("route6")
+        }) {
+            if let Ok(prefix) = types::parse_value::<Prefix, _>(&first.value) {
+                if prefix.has_host_bits() {
+                    errors.push(ObjectError::at(
+                        0,
+                        format!(
+                            "prefix `{prefix}` has host bits set, expected `{}`",
+                            prefix.network()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let object: Object<Rfc4012> = object.as_specification();
+        if let Err(rpslng) = Rfc4012::validate_object(&object) {
+            errors.extend(rpslng);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Requires an object to satisfy every specification of a tuple, e.g. `All<(Rfc2622, Irrd)>`.
+///
+/// Each attribute is reported with the error of the first specification rejecting it, while
+/// the object errors of all specifications are reported together.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct All<Specs>(PhantomData<Specs>);
+
+/// Requires an object to satisfy at least one specification of a tuple, e.g.
+/// `Any<(RipeDb, Irrd)>`.
+///
+/// Each specification validates the object as a whole, i.e. all of its attributes and its object
+/// rules. If none accepts the object, the errors of the first specification are reported. Its
+/// attribute errors are reported as [`ObjectError`]s if another specification accepts the
+/// attribute.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Any<Specs>(PhantomData<Specs>);
+
+macro_rules! impl_combinators {
+    ($($spec:ident),+) => {
+        impl<$($spec: Specification),+> Specification for All<($($spec,)+)> {
+            fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+                $($spec::validate_attribute(&attribute.as_specification())?;)+
+                Ok(())
+            }
+
+            fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+                let mut errors = Vec::new();
+                $(
+                    if let Err(spec_errors) = $spec::validate_object(&object.as_specification()) {
+                        errors.extend(spec_errors);
+                    }
+                )+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+
+        impl<$($spec: Specification),+> Specification for Any<($($spec,)+)> {
+            fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+                let mut first = None;
+                $(
+                    match $spec::validate_attribute(&attribute.as_specification()) {
+                        Ok(()) => return Ok(()),
+                        Err(error) => {
+                            first.get_or_insert(error);
+                        }
+                    }
+                )+
+                first.map_or(Ok(()), Err)
+            }
+
+            fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+                let mut first = None;
+                $(
+                    let errors = validate_as::<$spec, Self>(object);
+                    if errors.0.is_empty() && errors.1.is_empty() {
+                        return Ok(());
+                    }
+                    first.get_or_insert(errors);
+                )+
+                let Some((attribute_errors, object_errors)) = first else {
+                    return Ok(());
+                };
+
+                // Attributes rejected by every specification are already reported as such.
+                let mut errors: Vec<_> = attribute_errors
+                    .into_iter()
+                    .filter(|(index, _)| Self::validate_attribute(&object[*index]).is_ok())
+                    .map(|(index, error)| ObjectError::at(index, error.to_string()))
+                    .collect();
+                errors.extend(object_errors);
+                Err(errors)
+            }
+        }
+    };
+}
+
+/// Validate the attributes and the object rules of an object according to a specification.
+fn validate_as<Spec: Specification, Source: Specification>(
+    object: &Object<'_, Source>,
+) -> (Vec<(usize, AttributeError)>, Vec<ObjectError>) {
+    let object: Object<Spec> = object.as_specification();
+    let attribute_errors = object
+        .iter()
+        .enumerate()
+        .filter_map(|(index, attribute)| {
+            Spec::validate_attribute(attribute)
+                .err()
+                .map(|error| (index, error))
+        })
+        .collect();
+    let object_errors = Spec::validate_object(&object).err().unwrap_or_default();
+    (attribute_errors, object_errors)
+}
+
+impl_combinators!(A, B);
+impl_combinators!(A, B, C);
+impl_combinators!(A, B, C, D);
+impl_combinators!(A, B, C, D, E);
+impl_combinators!(A, B, C, D, E, F);
+
+/// A custom validation rule operating on [`Raw`] attributes and objects.
+///
+/// Unlike a [`Specification`], a rule does not need to be generic over the specification of the
+/// object it validates and only has to implement the checks it cares about. Use [`Custom`] to turn
+/// a rule into a specification, for example to combine it with others using [`All`].
+pub trait Rule: Debug + Clone + Copy {
+    /// Validate a single attribute according to the rule. By default, every attribute is valid.
+    ///
+    /// # Errors
+    /// Returns an [`AttributeError`] when the attribute does not satisfy the rule.
+    fn validate_attribute(_attribute: &Attribute<'_>) -> Result<(), AttributeError> {
+        Ok(())
+    }
+
+    /// Validate an object as a whole according to the rule. By default, every object is valid.
+    ///
+    /// # Errors
+    /// Returns every [`ObjectError`] describing why the object does not satisfy the rule.
+    fn validate_object(_object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+        Ok(())
+    }
+}
+
+/// A specification validating objects according to a custom [`Rule`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Custom<R>(PhantomData<R>);
+
+impl<R: Rule> Specification for Custom<R> {
+    fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+        R::validate_attribute(&attribute.as_specification())
+    }
+
+    fn validate_object(object: &Object<'_, Self>) -> Result<(), Vec<ObjectError>> {
+        R::validate_object(&object.as_specification())
+    }
+}
+
+/// A set of validation rules that can be selected at runtime.
+///
+/// Unlike [`Specification`], which is a type parameter known at compile time, a validator is a
+/// value and can be used as a trait object, for example to validate objects according to a
+/// specification picked from configuration using [`Object::validate_with`]. Every
+/// [`Specification`] is a validator, see [`Builtin`] for the specifications of this crate.
+pub trait Validator: Debug {
+    /// Validate a single attribute.
+    ///
+    /// # Errors
+    /// Returns an [`AttributeError`] when the attribute name or value does not satisfy the rules.
+    fn check_attribute(&self, attribute: &Attribute<'_>) -> Result<(), AttributeError>;
+
+    /// Validate an object as a whole, after each of its attributes has been validated.
+    ///
+    /// # Errors
+    /// Returns every [`ObjectError`] describing a rule the object does not satisfy.
+    fn check_object(&self, _object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+        Ok(())
+    }
+}
+
+impl<Spec: Specification> Validator for Spec {
+    fn check_attribute(&self, attribute: &Attribute<'_>) -> Result<(), AttributeError> {
+        Spec::validate_attribute(&attribute.as_specification())
+    }
+
+    fn check_object(&self, object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+        Spec::validate_object(&object.as_specification())
+    }
+}
+
+/// The specifications of this crate, selectable at runtime.
+///
+/// Each variant can be parsed from its name, such as `rfc2622` or `ripe`, and validates like
+/// the specification of the same name.
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use rpsl::{object, spec::Builtin};
+///
+/// let spec: Builtin = "irrd".parse()?;
+/// let route = object! {
+///     "route": "192.0.2.0/24";
+///     "origin": "AS65536";
+///     "source": "EXAMPLE";
+/// };
+/// assert!(route.validate_with(&spec).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Builtin {
+    /// Validates like [`Raw`].
+    Raw,
+    /// Validates like [`Rfc2622`].
+    Rfc2622,
+    /// Validates like [`Rfc2622Templates`].
+    Rfc2622Templates,
+    /// Validates like [`Rfc4012`].
+    Rfc4012,
+    /// Validates like [`RipeDb`].
+    RipeDb,
+    /// Validates like [`Irrd`].
+    Irrd,
+}
+
+impl Builtin {
+    /// All built-in specifications.
+    pub const ALL: [Self; 6] = [
+        Self::Raw,
+        Self::Rfc2622,
+        Self::Rfc2622Templates,
+        Self::Rfc4012,
+        Self::RipeDb,
+        Self::Irrd,
+    ];
+
+    /// The name the specification is parsed from.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Rfc2622 => "rfc2622",
+            Self::Rfc2622Templates => "rfc2622-templates",
+            Self::Rfc4012 => "rfc4012",
+            Self::RipeDb => "ripe",
+            Self::Irrd => "irrd",
+        }
+    }
+
+    fn validator(self) -> &'static dyn Validator {
+        match self {
+            Self::Raw => &Raw,
+            Self::Rfc2622 => &Rfc2622,
+            Self::Rfc2622Templates => &Rfc2622Templates,
+            Self::Rfc4012 => &Rfc4012,
+            Self::RipeDb => &RipeDb,
+            Self::Irrd => &Irrd,
+        }
+    }
+}
+
+impl Validator for Builtin {
+    fn check_attribute(&self, attribute: &Attribute<'_>) -> Result<(), AttributeError> {
+        self.validator().check_attribute(attribute)
+    }
+
+    fn check_object(&self, object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+        self.validator().check_object(object)
+    }
+}
+
+impl FromStr for Builtin {
+    type Err = UnknownSpecificationError;
+
+    /// Parse a specification from its name, compared case insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|spec| spec.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownSpecificationError(s.to_string()))
+    }
+}
+
+impl fmt::Display for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The name of a specification is not one of the [`Builtin`] specifications.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown specification `{0}`, expected one of raw, rfc2622, rfc2622-templates, rfc4012, ripe or irrd")]
+pub struct UnknownSpecificationError(String);
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 /// An invalid attribute was encountered during validation.
 pub enum AttributeError {
@@ -186,7 +935,10 @@ pub struct InvalidValueError {
 }
 
 impl InvalidValueError {
-    fn new<Spec: Specification>(value: &Value<Spec>, message: impl Into<String>) -> Self {
+    pub(crate) fn new<Spec: Specification>(
+        value: &Value<Spec>,
+        message: impl Into<String>,
+    ) -> Self {
         Self {
             value: value.clone().into_owned().into_raw(),
             message: message.into(),
@@ -194,13 +946,42 @@ impl InvalidValueError {
     }
 }
 
+/// The object violates a rule of the specification that spans more than a single attribute.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct ObjectError {
+    /// The index of the attribute the error refers to, if it concerns a specific attribute.
+    pub index: Option<usize>,
+    /// Context about why the object is invalid.
+    pub message: String,
+}
+
+impl ObjectError {
+    /// Create an error concerning the object as a whole.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            index: None,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error concerning the attribute at `index`.
+    pub fn at(index: usize, message: impl Into<String>) -> Self {
+        Self {
+            index: Some(index),
+            message: message.into(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use proptest::prelude::*;
     use rstest::*;
 
-    use crate::{spec::Rfc2622, Attribute, Name, Value};
+    use super::*;
+    use crate::{object, Name, Object, Value};
 
     #[rstest]
     #[case("aut-num", "AS3257")]
@@ -219,6 +1000,270 @@ mod tests {
         assert!(Rfc2622::validate_name(&n).is_err());
     }
 
+    #[test]
+    fn rfc2622_templates_validates_attributes_and_template() {
+        let route = object! {
+            "route": "192.0.2.0/24";
+            "descr": "Example route";
+            "origin": "AS65536";
+            "1nvalid": "attribute";
+            "tech-c": "EXAMPLE-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "changed": "noc@example.com 20240101";
+            "source": "RIPE";
+        };
+        let error = route.validate::<Rfc2622Templates>().unwrap_err();
+        assert_eq!(error.iter_errors().count(), 1);
+        assert_eq!(
+            error.object_errors(),
+            [ObjectError::at(
+                3,
+                "unknown attribute `1nvalid` in class `route`"
+            )]
+        );
+    }
+
+    #[test]
+    fn rfc4012_mixed_aut_num_is_valid() {
+        let aut_num = object! {
+            "aut-num": "AS65536";
+            "as-name": "EXAMPLE-AS";
+            "import": "from AS65537 accept AS65537";
+            "export": "to AS65537 announce AS65536";
+            "mp-import": "afi ipv6.unicast from AS65537 accept {2001:db8::/32^+}";
+            "mp-export": "afi ipv4.unicast, ipv6.unicast to AS65537 announce AS-EXAMPLE";
+            "mp-default": "afi ipv6 to AS65537 networks ANY";
+            "source": "TEST";
+        };
+        aut_num.validate::<Rfc4012>().unwrap();
+    }
+
+    #[rstest]
+    #[case("route", "2001:db8::/32")]
+    #[case("route6", "192.0.2.0/24")]
+    #[case("mp-import", "afi ipv5 from AS65537 accept ANY")]
+    #[case("import", "afi ipv6 from AS65537 accept ANY")]
+    #[case("mp-default", "to AS65537 accept ANY")]
+    #[case("mp-filter", "{2001:db8::/129}")]
+    #[case("mp-peering", "AS65536 at")]
+    fn rfc4012_invalid_attribute_is_error(#[case] name: &str, #[case] value: &str) {
+        let attribute = Attribute::new(name, value);
+        assert!(matches!(
+            attribute.validate::<Rfc4012>(),
+            Err(AttributeError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn rfc4012_route_set_mp_members_are_validated() {
+        let route_set = object! {
+            "route-set": "RS-EXAMPLE";
+            "mp-members": "192.0.2.0/24^+, 2001:db8::/32^48, RS-OTHER";
+            "mp-members": "AS65536, 2001:db8::/129";
+            "source": "TEST";
+        };
+        let error = route_set.validate::<Rfc4012>().unwrap_err();
+        assert_eq!(error.object_errors().len(), 1);
+        assert_eq!(error.object_errors()[0].index, Some(2));
+    }
+
+    #[test]
+    fn irrd_route_with_rpki_ov_state_is_valid() {
+        let route = object! {
+            "route6": "2001:db8::/32";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "rpki-ov-state": "not_found";
+            "source": "EXAMPLE";
+        };
+        route.validate::<Irrd>().unwrap();
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "192.0.2.1/24";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "EXAMPLE";
+        },
+        "prefix `192.0.2.1/24` has host bits set, expected `192.0.2.0/24`",
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "source": "EXAMPLE";
+        },
+        "missing mandatory attribute `mnt-by`",
+    )]
+    #[case(
+        object! {
+            "as-set": "AS-EXAMPLE";
+            "org": "ORG-EXAMPLE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "EXAMPLE";
+        },
+        "unknown attribute `org` in class `as-set`",
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "rpki-ov-state": "unknown";
+            "source": "EXAMPLE";
+        },
+        "expected one of `valid`, `invalid` or `not_found`",
+    )]
+    #[case(
+        object! {
+            "aut-num": "AS65536";
+            "as-name": "EXAMPLE";
+            "mp-import": "afi ipv6 from AS65537 announce ANY";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "EXAMPLE";
+        },
+        "invalid mp-import policy",
+    )]
+    fn irrd_invalid_object_is_error(#[case] object: Object, #[case] reason: &str) {
+        let error = object.validate::<Irrd>().unwrap_err();
+        let reasons: Vec<String> = error
+            .iter_errors()
+            .map(ToString::to_string)
+            .chain(error.object_errors().iter().map(ToString::to_string))
+            .collect();
+        assert!(
+            reasons.iter().any(|r| r.contains(reason)),
+            "expected `{reason}` in {reasons:?}"
+        );
+    }
+
+    #[rstest]
+    #[case("raw", Builtin::Raw)]
+    #[case("RFC2622", Builtin::Rfc2622)]
+    #[case("rfc2622-templates", Builtin::Rfc2622Templates)]
+    #[case("rfc4012", Builtin::Rfc4012)]
+    #[case("ripe", Builtin::RipeDb)]
+    #[case("irrd", Builtin::Irrd)]
+    fn builtin_from_str(#[case] name: &str, #[case] expected: Builtin) {
+        let spec: Builtin = name.parse().unwrap();
+        assert_eq!(spec, expected);
+        assert_eq!(spec.to_string().parse::<Builtin>().unwrap(), spec);
+    }
+
+    #[test]
+    fn builtin_unknown_is_error() {
+        assert_eq!(
+            "arin".parse::<Builtin>().unwrap_err(),
+            UnknownSpecificationError("arin".to_string())
+        );
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct HasOrg;
+
+    impl Rule for HasOrg {
+        fn validate_object(object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+            if object.get("org").is_empty() {
+                Err(vec![ObjectError::new("missing `org`")])
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct HasMntBy;
+
+    impl Rule for HasMntBy {
+        fn validate_object(object: &Object<'_>) -> Result<(), Vec<ObjectError>> {
+            if object.get("mnt-by").is_empty() {
+                Err(vec![ObjectError::new("missing `mnt-by`")])
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn all_aggregates_errors_of_every_specification() {
+        let object = object! {
+            "aut-num": "AS65536";
+            "import": "from AS65537 invalid";
+        };
+        let errors = object
+            .validate::<All<(Rfc4012, Custom<HasOrg>, Custom<HasMntBy>)>>()
+            .unwrap_err();
+        assert_eq!(errors.iter_errors().count(), 1);
+        assert_eq!(
+            errors.object_errors(),
+            [
+                ObjectError::new("missing `org`"),
+                ObjectError::new("missing `mnt-by`")
+            ]
+        );
+    }
+
+    #[test]
+    fn any_is_valid_if_one_specification_accepts() {
+        let object = object! {
+            "role": "ACME Company";
+            "d!ne": "invalid name";
+            "mnt-by": "MAINT-ACME";
+        };
+        assert!(object.validate::<Any<(Rfc2622, Raw)>>().is_ok());
+        assert!(object
+            .validate::<Any<(Custom<HasOrg>, Custom<HasMntBy>)>>()
+            .is_ok());
+    }
+
+    #[test]
+    fn any_reports_errors_of_first_specification() {
+        let object = object! {
+            "role": "ACME Company";
+            "d!ne": "invalid name";
+        };
+        let errors = object
+            .validate::<Any<(Custom<HasOrg>, Custom<HasMntBy>)>>()
+            .unwrap_err();
+        assert!(errors.iter_errors().next().is_none());
+        assert_eq!(errors.object_errors(), [ObjectError::new("missing `org`")]);
+
+        let errors = object.validate::<Any<(Rfc2622, Rfc4012)>>().unwrap_err();
+        assert_eq!(
+            errors
+                .iter_indexed()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>(),
+            [1]
+        );
+        assert!(errors.object_errors().is_empty());
+    }
+
+    #[test]
+    fn any_does_not_mix_specifications() {
+        // Custom<HasOrg> accepts the attributes and Rfc2622 the object rules, but neither
+        // accepts the object as a whole.
+        let object = object! {
+            "role": "ACME Company";
+            "d!ne": "invalid name";
+        };
+        let errors = object
+            .validate::<Any<(Rfc2622, Custom<HasOrg>)>>()
+            .unwrap_err();
+        assert!(errors.iter_errors().next().is_none());
+        assert_eq!(
+            errors.object_errors(),
+            [ObjectError::at(
+                1,
+                Rfc2622::validate_attribute(&object[1].as_specification())
+                    .unwrap_err()
+                    .to_string()
+            )]
+        );
+    }
+
     proptest! {
         #[test]
         fn rfc2622_attribute_name_non_letter_first_char_is_error(char in r"[^A-Za-z]") {
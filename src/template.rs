@@ -0,0 +1,1626 @@
+//! Templates describing the attributes of RPSL object classes.
+//!
+//! Registries publish a template for each object class they support, listing every attribute
+//! as mandatory or optional, single or multiple valued, and whether it is used as a primary,
+//! lookup or inverse key. A [`Template`] holds this information and validates objects against
+//! it, reporting missing mandatory attributes, repeated single valued attributes, unknown
+//! attributes and attributes out of order.
+//!
+//! Templates can be defined in code or parsed from the output of `whois -t <class>`:
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::{object, template::Template};
+//!
+//! let template: Template = "
+//! route:          [mandatory]  [single]     [primary/lookup key]
+//! descr:          [optional]   [multiple]   [ ]
+//! origin:         [mandatory]  [single]     [primary key]
+//! mnt-by:         [mandatory]  [multiple]   [inverse key]
+//! source:         [mandatory]  [single]     [ ]
+//! "
+//! .parse()?;
+//!
+//! let route = object! {
+//!     "route": "192.0.2.0/24";
+//!     "origin": "AS65536";
+//!     "origin": "AS65537";
+//!     "source": "RIPE";
+//! };
+//! let errors = template.validate(&route).unwrap_err();
+//! assert_eq!(errors[0].to_string(), "attribute `origin` must not appear more than once");
+//! assert_eq!(errors[1].to_string(), "missing mandatory attribute `mnt-by`");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The templates of the classes defined in RFC 2622 are available as [`RFC2622`] and enforced by
+//! the [`Rfc2622Templates`](crate::spec::Rfc2622Templates) specification, those of the RIPE
+//! Database as [`RIPE`] and enforced by [`RipeDb`](crate::spec::RipeDb), and those of `IRRd` as
+//! [`IRRD`] and enforced by [`Irrd`](crate::spec::Irrd).
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    spec::{ObjectError, Specification},
+    Object,
+};
+
+/// Whether an attribute must be present in an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Requirement {
+    /// The attribute must be present at least once.
+    Mandatory,
+    /// The attribute may be omitted.
+    Optional,
+    /// The attribute is maintained by the registry and may be omitted.
+    Generated,
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mandatory => "mandatory",
+            Self::Optional => "optional",
+            Self::Generated => "generated",
+        })
+    }
+}
+
+/// How often an attribute may appear in an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Cardinality {
+    /// The attribute may appear at most once.
+    Single,
+    /// The attribute may appear any number of times.
+    Multiple,
+}
+
+impl fmt::Display for Cardinality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Single => "single",
+            Self::Multiple => "multiple",
+        })
+    }
+}
+
+/// The kinds of key an attribute is used as.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Keys {
+    /// The attribute is part of the primary key of the object.
+    pub primary: bool,
+    /// Objects can be looked up by the value of the attribute.
+    pub lookup: bool,
+    /// Objects can be looked up by the objects the attribute refers to.
+    pub inverse: bool,
+}
+
+impl Keys {
+    /// Returns `true` if the attribute is not used as any key.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !(self.primary || self.lookup || self.inverse)
+    }
+}
+
+impl fmt::Display for Keys {
+    /// Display the keys as shown by `whois -t`, such as `primary/lookup key`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds: Vec<&str> = [
+            (self.primary, "primary"),
+            (self.lookup, "lookup"),
+            (self.inverse, "inverse"),
+        ]
+        .into_iter()
+        .filter_map(|(is_key, kind)| is_key.then_some(kind))
+        .collect();
+
+        if kinds.is_empty() {
+            f.write_str(" ")
+        } else {
+            write!(f, "{} key", kinds.join("/"))
+        }
+    }
+}
+
+/// The template of a single attribute within a class.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeTemplate {
+    /// The name of the attribute.
+    pub name: Cow<'static, str>,
+    /// Whether the attribute must be present.
+    pub requirement: Requirement,
+    /// How often the attribute may appear.
+    pub cardinality: Cardinality,
+    /// The kinds of key the attribute is used as.
+    pub keys: Keys,
+}
+
+impl AttributeTemplate {
+    const fn with_requirement(name: &'static str, requirement: Requirement) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            requirement,
+            cardinality: Cardinality::Single,
+            keys: Keys {
+                primary: false,
+                lookup: false,
+                inverse: false,
+            },
+        }
+    }
+
+    /// A mandatory, single valued attribute.
+    #[must_use]
+    pub const fn mandatory(name: &'static str) -> Self {
+        Self::with_requirement(name, Requirement::Mandatory)
+    }
+
+    /// An optional, single valued attribute.
+    #[must_use]
+    pub const fn optional(name: &'static str) -> Self {
+        Self::with_requirement(name, Requirement::Optional)
+    }
+
+    /// A generated, single valued attribute.
+    #[must_use]
+    pub const fn generated(name: &'static str) -> Self {
+        Self::with_requirement(name, Requirement::Generated)
+    }
+
+    /// Allow the attribute to appear multiple times.
+    #[must_use]
+    pub const fn multiple(mut self) -> Self {
+        self.cardinality = Cardinality::Multiple;
+        self
+    }
+
+    /// Mark the attribute as part of the primary key.
+    #[must_use]
+    pub const fn primary_key(mut self) -> Self {
+        self.keys.primary = true;
+        self
+    }
+
+    /// Mark the attribute as a lookup key.
+    #[must_use]
+    pub const fn lookup_key(mut self) -> Self {
+        self.keys.lookup = true;
+        self
+    }
+
+    /// Mark the attribute as an inverse key.
+    #[must_use]
+    pub const fn inverse_key(mut self) -> Self {
+        self.keys.inverse = true;
+        self
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+impl fmt::Display for AttributeTemplate {
+    /// Display the attribute template as a line of `whois -t` output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<16}{:<13}{:<13}[{}]",
+            format!("{}:", self.name),
+            format!("[{}]", self.requirement),
+            format!("[{}]", self.cardinality),
+            self.keys,
+        )
+    }
+}
+
+/// The template of an object class.
+///
+/// The attribute of the class itself, such as `route` for the `route` class, is expected to be
+/// the first attribute of the template.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Template {
+    class: Cow<'static, str>,
+    attributes: Cow<'static, [AttributeTemplate]>,
+    ordered: bool,
+}
+
+impl Template {
+    /// Create a template for a class from a static list of attribute templates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rpsl::template::{AttributeTemplate, Template};
+    /// static AS_SET_ATTRIBUTES: &[AttributeTemplate] = &[
+    ///     AttributeTemplate::mandatory("as-set").primary_key().lookup_key(),
+    ///     AttributeTemplate::optional("members").multiple(),
+    ///     AttributeTemplate::mandatory("mnt-by").multiple().inverse_key(),
+    ///     AttributeTemplate::mandatory("source"),
+    /// ];
+    /// static AS_SET: Template = Template::new("as-set", AS_SET_ATTRIBUTES);
+    /// assert_eq!(AS_SET.class(), "as-set");
+    /// ```
+    #[must_use]
+    pub const fn new(class: &'static str, attributes: &'static [AttributeTemplate]) -> Self {
+        Self {
+            class: Cow::Borrowed(class),
+            attributes: Cow::Borrowed(attributes),
+            ordered: false,
+        }
+    }
+
+    /// Create a template for a class from attribute templates created at runtime.
+    #[must_use]
+    pub fn with_attributes(
+        class: impl Into<Cow<'static, str>>,
+        attributes: Vec<AttributeTemplate>,
+    ) -> Self {
+        Self {
+            class: class.into(),
+            attributes: Cow::Owned(attributes),
+            ordered: false,
+        }
+    }
+
+    /// Require attributes to appear in the order they are listed in the template.
+    ///
+    /// By default, only the class attribute is required to come first.
+    #[must_use]
+    pub const fn ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    /// The class the template describes.
+    #[must_use]
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    /// The templates of the attributes of the class.
+    #[must_use]
+    pub fn attributes(&self) -> &[AttributeTemplate] {
+        &self.attributes
+    }
+
+    /// The template of an attribute of the class, compared case insensitively.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&AttributeTemplate> {
+        self.attributes
+            .iter()
+            .find(|template| template.matches(name))
+    }
+
+    /// Iterate over the attributes forming the primary key of the class.
+    pub fn primary_keys(&self) -> impl Iterator<Item = &AttributeTemplate> {
+        self.attributes
+            .iter()
+            .filter(|template| template.keys.primary)
+    }
+
+    /// Iterate over the attributes objects of the class can be looked up by.
+    pub fn lookup_keys(&self) -> impl Iterator<Item = &AttributeTemplate> {
+        self.attributes
+            .iter()
+            .filter(|template| template.keys.lookup)
+    }
+
+    /// Iterate over the attributes used for inverse lookups.
+    pub fn inverse_keys(&self) -> impl Iterator<Item = &AttributeTemplate> {
+        self.attributes
+            .iter()
+            .filter(|template| template.keys.inverse)
+    }
+
+    /// Validate an object against the template.
+    ///
+    /// # Errors
+    /// Returns an [`ObjectError`] for the object not starting with the class attribute, for each
+    /// unknown attribute, each repeated occurrence of a single valued attribute, each attribute
+    /// out of order if the template is [ordered](Template::ordered) and each missing mandatory
+    /// attribute.
+    pub fn validate<Spec: Specification>(
+        &self,
+        object: &Object<'_, Spec>,
+    ) -> Result<(), Vec<ObjectError>> {
+        let mut errors = Vec::new();
+
+        match object.first() {
+            Some(first) if self.class.eq_ignore_ascii_case(&first.name) => {}
+            Some(_) => errors.push(ObjectError::at(
+                0,
+                format!("object must start with the `{}` attribute", self.class),
+            )),
+            None => errors.push(ObjectError::new("object must not be empty")),
+        }
+
+        let mut occurrences = vec![0usize; self.attributes.len()];
+        let mut furthest = 0;
+        for (index, attribute) in object.iter().enumerate() {
+            let Some(position) = self
+                .attributes
+                .iter()
+                .position(|template| template.matches(&attribute.name))
+            else {
+                errors.push(ObjectError::at(
+                    index,
+                    format!(
+                        "unknown attribute `{}` in class `{}`",
+                        attribute.name, self.class
+                    ),
+                ));
+                continue;
+            };
+            let template = &self.attributes[position];
+
+            occurrences[position] += 1;
+            if template.cardinality == Cardinality::Single && occurrences[position] > 1 {
+                errors.push(ObjectError::at(
+                    index,
+                    format!(
+                        "attribute `{}` must not appear more than once",
+                        template.name
+                    ),
+                ));
+            }
+
+            if self.ordered && position < furthest {
+                errors.push(ObjectError::at(
+                    index,
+                    format!(
+                        "attribute `{}` must appear before `{}`",
+                        template.name, self.attributes[furthest].name
+                    ),
+                ));
+            }
+            furthest = furthest.max(position);
+        }
+
+        for (template, count) in self.attributes.iter().zip(occurrences) {
+            if template.requirement == Requirement::Mandatory && count == 0 {
+                errors.push(ObjectError::new(format!(
+                    "missing mandatory attribute `{}`",
+                    template.name
+                )));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    /// Display the template in the format of `whois -t`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for attribute in self.attributes.iter() {
+            writeln!(f, "{attribute}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Template {
+    type Err = ParseTemplateError;
+
+    /// Parse a template from the output of `whois -t`.
+    ///
+    /// Empty lines and comments starting with `%` are skipped, the first attribute determines
+    /// the class of the template.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut attributes = Vec::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+            let error = |message: &str| ParseTemplateError {
+                line: index + 1,
+                message: message.to_string(),
+            };
+
+            let (name, rest) = line
+                .split_once(':')
+                .ok_or_else(|| error("expected an attribute name followed by `:`"))?;
+            let mut fields = rest
+                .split(']')
+                .filter_map(|field| field.trim().strip_prefix('['));
+
+            let requirement = match fields.next().map(str::trim) {
+                Some("mandatory") => Requirement::Mandatory,
+                Some("optional") => Requirement::Optional,
+                Some("generated") => Requirement::Generated,
+                _ => {
+                    return Err(error(
+                        "expected `[mandatory]`, `[optional]` or `[generated]`",
+                    ))
+                }
+            };
+            let cardinality = match fields.next().map(str::trim) {
+                Some("single") => Cardinality::Single,
+                Some("multiple") => Cardinality::Multiple,
+                _ => return Err(error("expected `[single]` or `[multiple]`")),
+            };
+            let mut keys = Keys::default();
+            if let Some(kinds) = fields
+                .next()
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+            {
+                let kinds = kinds
+                    .strip_suffix("key")
+                    .ok_or_else(|| error("expected a key such as `[primary/lookup key]`"))?;
+                for kind in kinds.split('/').map(str::trim) {
+                    match kind {
+                        "primary" => keys.primary = true,
+                        "lookup" => keys.lookup = true,
+                        "inverse" => keys.inverse = true,
+                        _ => return Err(error("unknown kind of key")),
+                    }
+                }
+            }
+
+            attributes.push(AttributeTemplate {
+                name: Cow::Owned(name.trim().to_string()),
+                requirement,
+                cardinality,
+                keys,
+            });
+        }
+
+        let class = attributes
+            .first()
+            .map(|attribute| attribute.name.clone())
+            .ok_or(ParseTemplateError {
+                line: 0,
+                message: "template must contain at least one attribute".to_string(),
+            })?;
+        Ok(Self::with_attributes(class, attributes))
+    }
+}
+
+/// A template could not be parsed.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid template on line {line}: {message}")]
+pub struct ParseTemplateError {
+    /// The line the error occurred on, starting at 1.
+    pub line: usize,
+    /// Context about why the template is invalid.
+    pub message: String,
+}
+
+/// Find the template of a class, compared case insensitively.
+#[must_use]
+pub fn find<'t>(templates: &'t [Template], class: &str) -> Option<&'t Template> {
+    templates
+        .iter()
+        .find(|template| template.class.eq_ignore_ascii_case(class))
+}
+
+/// Validate an object against the template for its class.
+///
+/// # Errors
+/// Returns an [`ObjectError`] if the class of the object has no template, or the errors returned
+/// by [`Template::validate`].
+pub fn validate<Spec: Specification>(
+    templates: &[Template],
+    object: &Object<'_, Spec>,
+) -> Result<(), Vec<ObjectError>> {
+    let Some(first) = object.first() else {
+        return Err(vec![ObjectError::new("object must not be empty")]);
+    };
+    match find(templates, &first.name) {
+        Some(template) => template.validate(object),
+        None => Err(vec![ObjectError::at(
+            0,
+            format!("unknown object class `{}`", first.name),
+        )]),
+    }
+}
+
+mod rfc2622 {
+    use super::AttributeTemplate as A;
+
+    pub(super) static MNTNER: &[A] = &[
+        A::mandatory("mntner").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("upd-to").multiple().inverse_key(),
+        A::optional("mnt-nfy").multiple().inverse_key(),
+        A::mandatory("auth").multiple(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PERSON: &[A] = &[
+        A::mandatory("person").lookup_key(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::mandatory("address").multiple(),
+        A::mandatory("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple().lookup_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROLE: &[A] = &[
+        A::mandatory("role").lookup_key(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::optional("trouble").multiple(),
+        A::mandatory("address").multiple(),
+        A::mandatory("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple().lookup_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE: &[A] = &[
+        A::mandatory("route").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::mandatory("origin").primary_key().inverse_key(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("inject").multiple(),
+        A::optional("components"),
+        A::optional("aggr-bndry"),
+        A::optional("aggr-mtd"),
+        A::optional("export-comps"),
+        A::optional("holes").multiple(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AUT_NUM: &[A] = &[
+        A::mandatory("aut-num").primary_key().lookup_key(),
+        A::mandatory("as-name").lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("import").multiple(),
+        A::optional("export").multiple(),
+        A::optional("default").multiple(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AS_SET: &[A] = &[
+        A::mandatory("as-set").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE_SET: &[A] = &[
+        A::mandatory("route-set").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static FILTER_SET: &[A] = &[
+        A::mandatory("filter-set").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::mandatory("filter"),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static RTR_SET: &[A] = &[
+        A::mandatory("rtr-set").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PEERING_SET: &[A] = &[
+        A::mandatory("peering-set").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::mandatory("peering").multiple(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INET_RTR: &[A] = &[
+        A::mandatory("inet-rtr").primary_key().lookup_key(),
+        A::mandatory("descr").multiple(),
+        A::optional("alias").multiple(),
+        A::mandatory("local-as").inverse_key(),
+        A::mandatory("ifaddr").multiple().lookup_key(),
+        A::optional("peer").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::mandatory("changed").multiple(),
+        A::mandatory("source"),
+    ];
+}
+
+mod ripe {
+    use super::AttributeTemplate as A;
+
+    pub(super) static AS_BLOCK: &[A] = &[
+        A::mandatory("as-block").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AS_SET: &[A] = &[
+        A::mandatory("as-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AUT_NUM: &[A] = &[
+        A::mandatory("aut-num").primary_key().lookup_key(),
+        A::mandatory("as-name"),
+        A::optional("descr").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("import-via").multiple(),
+        A::optional("import").multiple(),
+        A::optional("mp-import").multiple(),
+        A::optional("export-via").multiple(),
+        A::optional("export").multiple(),
+        A::optional("mp-export").multiple(),
+        A::optional("default").multiple(),
+        A::optional("mp-default").multiple(),
+        A::optional("remarks").multiple(),
+        A::optional("org").inverse_key(),
+        A::optional("sponsoring-org").inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("abuse-c").inverse_key(),
+        A::generated("status"),
+        A::optional("notify").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static DOMAIN: &[A] = &[
+        A::mandatory("domain").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("zone-c").multiple().inverse_key(),
+        A::mandatory("nserver").multiple().inverse_key(),
+        A::optional("ds-rdata").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static FILTER_SET: &[A] = &[
+        A::mandatory("filter-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("filter"),
+        A::optional("mp-filter"),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INET6NUM: &[A] = &[
+        A::mandatory("inet6num").primary_key().lookup_key(),
+        A::mandatory("netname").lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("country").multiple(),
+        A::optional("geofeed"),
+        A::optional("geoloc"),
+        A::optional("language").multiple(),
+        A::optional("org").inverse_key(),
+        A::optional("sponsoring-org").inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("abuse-c").inverse_key(),
+        A::mandatory("status"),
+        A::optional("assignment-size"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::optional("mnt-routes").multiple().inverse_key(),
+        A::optional("mnt-domains").multiple().inverse_key(),
+        A::optional("mnt-irt").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INETNUM: &[A] = &[
+        A::mandatory("inetnum").primary_key().lookup_key(),
+        A::mandatory("netname").lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("country").multiple(),
+        A::optional("geofeed"),
+        A::optional("geoloc"),
+        A::optional("language").multiple(),
+        A::optional("org").inverse_key(),
+        A::optional("sponsoring-org").inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("abuse-c").inverse_key(),
+        A::mandatory("status"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::optional("mnt-routes").multiple().inverse_key(),
+        A::optional("mnt-domains").multiple().inverse_key(),
+        A::optional("mnt-irt").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INET_RTR: &[A] = &[
+        A::mandatory("inet-rtr").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("alias").multiple(),
+        A::mandatory("local-as").inverse_key(),
+        A::mandatory("ifaddr").multiple().lookup_key(),
+        A::optional("interface").multiple(),
+        A::optional("peer").multiple(),
+        A::optional("mp-peer").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static IRT: &[A] = &[
+        A::mandatory("irt").primary_key().lookup_key(),
+        A::mandatory("address").multiple(),
+        A::optional("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple().lookup_key(),
+        A::optional("abuse-mailbox").multiple().inverse_key(),
+        A::optional("signature").multiple(),
+        A::optional("encryption").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("auth").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("irt-nfy").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static KEY_CERT: &[A] = &[
+        A::mandatory("key-cert").primary_key().lookup_key(),
+        A::generated("method"),
+        A::generated("owner").multiple(),
+        A::generated("fingerpr").inverse_key(),
+        A::mandatory("certif").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static MNTNER: &[A] = &[
+        A::mandatory("mntner").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("upd-to").multiple().inverse_key(),
+        A::optional("mnt-nfy").multiple().inverse_key(),
+        A::mandatory("auth").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ORGANISATION: &[A] = &[
+        A::mandatory("organisation").primary_key().lookup_key(),
+        A::mandatory("org-name").lookup_key(),
+        A::mandatory("org-type"),
+        A::optional("descr").multiple(),
+        A::optional("remarks").multiple(),
+        A::mandatory("address").multiple(),
+        A::optional("country"),
+        A::optional("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple().lookup_key(),
+        A::optional("geoloc"),
+        A::optional("language").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("abuse-c").inverse_key(),
+        A::optional("ref-nfy").multiple().inverse_key(),
+        A::mandatory("mnt-ref").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PEERING_SET: &[A] = &[
+        A::mandatory("peering-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("peering").multiple(),
+        A::optional("mp-peering").multiple(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PERSON: &[A] = &[
+        A::mandatory("person").lookup_key(),
+        A::mandatory("address").multiple(),
+        A::mandatory("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::optional("e-mail").multiple().lookup_key(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static POEM: &[A] = &[
+        A::mandatory("poem").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("form").inverse_key(),
+        A::mandatory("text").multiple(),
+        A::optional("author").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static POETIC_FORM: &[A] = &[
+        A::mandatory("poetic-form").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROLE: &[A] = &[
+        A::mandatory("role").lookup_key(),
+        A::mandatory("address").multiple(),
+        A::optional("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple().lookup_key(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::optional("abuse-mailbox").inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE: &[A] = &[
+        A::mandatory("route").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("origin").primary_key().inverse_key(),
+        A::optional("pingable").multiple(),
+        A::optional("ping-hdl").multiple().inverse_key(),
+        A::optional("holes").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("inject").multiple(),
+        A::optional("aggr-mtd"),
+        A::optional("aggr-bndry"),
+        A::optional("export-comps"),
+        A::optional("components"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::optional("mnt-routes").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE6: &[A] = &[
+        A::mandatory("route6").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("origin").primary_key().inverse_key(),
+        A::optional("pingable").multiple(),
+        A::optional("ping-hdl").multiple().inverse_key(),
+        A::optional("holes").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("inject").multiple(),
+        A::optional("aggr-mtd"),
+        A::optional("aggr-bndry"),
+        A::optional("export-comps"),
+        A::optional("components"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::optional("mnt-routes").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE_SET: &[A] = &[
+        A::mandatory("route-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static RTR_SET: &[A] = &[
+        A::mandatory("rtr-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("org").multiple().inverse_key(),
+        A::mandatory("tech-c").multiple().inverse_key(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("notify").multiple().inverse_key(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("mnt-lower").multiple().inverse_key(),
+        A::generated("created"),
+        A::generated("last-modified"),
+        A::mandatory("source"),
+    ];
+}
+
+mod irrd {
+    use super::AttributeTemplate as A;
+
+    pub(super) static AS_BLOCK: &[A] = &[
+        A::mandatory("as-block").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AS_SET: &[A] = &[
+        A::mandatory("as-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static AUT_NUM: &[A] = &[
+        A::mandatory("aut-num").primary_key().lookup_key(),
+        A::mandatory("as-name"),
+        A::optional("descr").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("import").multiple(),
+        A::optional("mp-import").multiple(),
+        A::optional("import-via").multiple(),
+        A::optional("export").multiple(),
+        A::optional("mp-export").multiple(),
+        A::optional("export-via").multiple(),
+        A::optional("default").multiple(),
+        A::optional("mp-default").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static DOMAIN: &[A] = &[
+        A::mandatory("domain").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("zone-c").multiple().inverse_key(),
+        A::optional("nserver").multiple(),
+        A::optional("sub-dom").multiple(),
+        A::optional("dom-net").multiple(),
+        A::optional("refer"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static FILTER_SET: &[A] = &[
+        A::mandatory("filter-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("filter"),
+        A::optional("mp-filter"),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INET_RTR: &[A] = &[
+        A::mandatory("inet-rtr").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("alias").multiple(),
+        A::mandatory("local-as"),
+        A::mandatory("ifaddr").multiple(),
+        A::optional("interface").multiple(),
+        A::optional("peer").multiple(),
+        A::optional("mp-peer").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("rs-in"),
+        A::optional("rs-out"),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INET6NUM: &[A] = &[
+        A::mandatory("inet6num").primary_key().lookup_key(),
+        A::mandatory("netname"),
+        A::optional("descr").multiple(),
+        A::mandatory("country").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("rev-srv").multiple(),
+        A::mandatory("status"),
+        A::optional("geofeed"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static INETNUM: &[A] = &[
+        A::mandatory("inetnum").primary_key().lookup_key(),
+        A::mandatory("netname"),
+        A::optional("descr").multiple(),
+        A::mandatory("country").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("rev-srv").multiple(),
+        A::mandatory("status"),
+        A::optional("geofeed"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static KEY_CERT: &[A] = &[
+        A::mandatory("key-cert").primary_key().lookup_key(),
+        A::generated("method"),
+        A::generated("owner").multiple(),
+        A::generated("fingerpr"),
+        A::mandatory("certif").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static MNTNER: &[A] = &[
+        A::mandatory("mntner").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("upd-to").multiple(),
+        A::optional("mnt-nfy").multiple(),
+        A::mandatory("auth").multiple(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PEERING_SET: &[A] = &[
+        A::mandatory("peering-set").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::optional("peering").multiple(),
+        A::optional("mp-peering").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static PERSON: &[A] = &[
+        A::mandatory("person").lookup_key(),
+        A::mandatory("address").multiple(),
+        A::mandatory("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROLE: &[A] = &[
+        A::mandatory("role").lookup_key(),
+        A::optional("trouble").multiple(),
+        A::mandatory("address").multiple(),
+        A::mandatory("phone").multiple(),
+        A::optional("fax-no").multiple(),
+        A::mandatory("e-mail").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::mandatory("nic-hdl").primary_key().lookup_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE: &[A] = &[
+        A::mandatory("route").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("origin").primary_key().inverse_key(),
+        A::optional("holes").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("inject").multiple(),
+        A::optional("aggr-bndry"),
+        A::optional("aggr-mtd"),
+        A::optional("export-comps"),
+        A::optional("components"),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("geoidx").multiple(),
+        A::optional("roa-uri"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::generated("rpki-ov-state"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE_SET: &[A] = &[
+        A::mandatory("route-set").primary_key().lookup_key(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("descr").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static ROUTE6: &[A] = &[
+        A::mandatory("route6").primary_key().lookup_key(),
+        A::optional("descr").multiple(),
+        A::mandatory("origin").primary_key().inverse_key(),
+        A::optional("holes").multiple(),
+        A::optional("member-of").multiple().inverse_key(),
+        A::optional("inject").multiple(),
+        A::optional("aggr-bndry"),
+        A::optional("aggr-mtd"),
+        A::optional("export-comps"),
+        A::optional("components"),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("geoidx").multiple(),
+        A::optional("roa-uri"),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::generated("rpki-ov-state"),
+        A::mandatory("source"),
+    ];
+
+    pub(super) static RTR_SET: &[A] = &[
+        A::mandatory("rtr-set").primary_key().lookup_key(),
+        A::optional("members").multiple(),
+        A::optional("mp-members").multiple(),
+        A::optional("mbrs-by-ref").multiple().inverse_key(),
+        A::optional("descr").multiple(),
+        A::optional("admin-c").multiple().inverse_key(),
+        A::optional("tech-c").multiple().inverse_key(),
+        A::optional("remarks").multiple(),
+        A::optional("notify").multiple(),
+        A::mandatory("mnt-by").multiple().inverse_key(),
+        A::optional("changed").multiple(),
+        A::mandatory("source"),
+    ];
+}
+
+/// The templates of the object classes defined in RFC 2622.
+///
+/// Unlike RFC 2622, which defines `descr` as single valued, these templates allow `descr` to be
+/// repeated, as registries commonly describe objects using several `descr` attributes.
+pub static RFC2622: &[Template] = &[
+    Template::new("mntner", rfc2622::MNTNER),
+    Template::new("person", rfc2622::PERSON),
+    Template::new("role", rfc2622::ROLE),
+    Template::new("route", rfc2622::ROUTE),
+    Template::new("aut-num", rfc2622::AUT_NUM),
+    Template::new("as-set", rfc2622::AS_SET),
+    Template::new("route-set", rfc2622::ROUTE_SET),
+    Template::new("filter-set", rfc2622::FILTER_SET),
+    Template::new("rtr-set", rfc2622::RTR_SET),
+    Template::new("peering-set", rfc2622::PEERING_SET),
+    Template::new("inet-rtr", rfc2622::INET_RTR),
+];
+
+/// The templates of the object classes of the RIPE Database.
+pub static RIPE: &[Template] = &[
+    Template::new("as-block", ripe::AS_BLOCK),
+    Template::new("as-set", ripe::AS_SET),
+    Template::new("aut-num", ripe::AUT_NUM),
+    Template::new("domain", ripe::DOMAIN),
+    Template::new("filter-set", ripe::FILTER_SET),
+    Template::new("inet6num", ripe::INET6NUM),
+    Template::new("inetnum", ripe::INETNUM),
+    Template::new("inet-rtr", ripe::INET_RTR),
+    Template::new("irt", ripe::IRT),
+    Template::new("key-cert", ripe::KEY_CERT),
+    Template::new("mntner", ripe::MNTNER),
+    Template::new("organisation", ripe::ORGANISATION),
+    Template::new("peering-set", ripe::PEERING_SET),
+    Template::new("person", ripe::PERSON),
+    Template::new("poem", ripe::POEM),
+    Template::new("poetic-form", ripe::POETIC_FORM),
+    Template::new("role", ripe::ROLE),
+    Template::new("route", ripe::ROUTE),
+    Template::new("route6", ripe::ROUTE6),
+    Template::new("route-set", ripe::ROUTE_SET),
+    Template::new("rtr-set", ripe::RTR_SET),
+];
+
+/// The templates of the object classes supported by `IRRd` 4.
+pub static IRRD: &[Template] = &[
+    Template::new("as-block", irrd::AS_BLOCK),
+    Template::new("as-set", irrd::AS_SET),
+    Template::new("aut-num", irrd::AUT_NUM),
+    Template::new("domain", irrd::DOMAIN),
+    Template::new("filter-set", irrd::FILTER_SET),
+    Template::new("inet-rtr", irrd::INET_RTR),
+    Template::new("inet6num", irrd::INET6NUM),
+    Template::new("inetnum", irrd::INETNUM),
+    Template::new("key-cert", irrd::KEY_CERT),
+    Template::new("mntner", irrd::MNTNER),
+    Template::new("peering-set", irrd::PEERING_SET),
+    Template::new("person", irrd::PERSON),
+    Template::new("role", irrd::ROLE),
+    Template::new("route", irrd::ROUTE),
+    Template::new("route-set", irrd::ROUTE_SET),
+    Template::new("route6", irrd::ROUTE6),
+    Template::new("rtr-set", irrd::RTR_SET),
+];
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    const ROUTE_TEMPLATE: &str = "\
+route:          [mandatory]  [single]     [primary/lookup key]
+descr:          [optional]   [multiple]   [ ]
+origin:         [mandatory]  [single]     [primary/inverse key]
+member-of:      [optional]   [multiple]   [inverse key]
+mnt-by:         [mandatory]  [multiple]   [inverse key]
+created:        [generated]  [single]     [ ]
+source:         [mandatory]  [single]     [ ]
+";
+
+    #[test]
+    fn template_parsed_from_whois_output_displays_identically() {
+        let template: Template = ROUTE_TEMPLATE.parse().unwrap();
+        assert_eq!(template.class(), "route");
+        assert_eq!(template.attributes().len(), 7);
+        assert_eq!(
+            template.attribute("ORIGIN"),
+            Some(
+                &AttributeTemplate::mandatory("origin")
+                    .primary_key()
+                    .inverse_key()
+            )
+        );
+        assert_eq!(template.to_string(), ROUTE_TEMPLATE);
+    }
+
+    #[rstest]
+    #[case("route: [mandatory]", 1)]
+    #[case("route: [mandatory] [single] [primary]", 1)]
+    #[case("\n% comment\nroute [mandatory] [single]", 3)]
+    #[case("% comment only", 0)]
+    fn template_invalid_is_error(#[case] template: &str, #[case] line: usize) {
+        assert_eq!(template.parse::<Template>().unwrap_err().line, line);
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "descr": "Example";
+            "descr": "Route";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        },
+        vec![],
+    )]
+    #[case(
+        object! {
+            "origin": "AS65536";
+            "route": "192.0.2.0/24";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        },
+        vec![ObjectError::at(0, "object must start with the `route` attribute")],
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "origin": "AS65537";
+            "geofeed": "https://example.com/geofeed.csv";
+            "source": "RIPE";
+        },
+        vec![
+            ObjectError::at(2, "attribute `origin` must not appear more than once"),
+            ObjectError::at(3, "unknown attribute `geofeed` in class `route`"),
+            ObjectError::new("missing mandatory attribute `mnt-by`"),
+        ],
+    )]
+    fn template_validate_returns_expected_errors(
+        #[case] object: Object,
+        #[case] expected: Vec<ObjectError>,
+    ) {
+        let template: Template = ROUTE_TEMPLATE.parse().unwrap();
+        assert_eq!(
+            template.validate(&object).err().unwrap_or_default(),
+            expected
+        );
+    }
+
+    #[test]
+    fn template_ordered_reports_attributes_out_of_order() {
+        let template = ROUTE_TEMPLATE.parse::<Template>().unwrap().ordered();
+        let route = object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "source": "RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+        };
+        assert_eq!(
+            template.validate(&route).unwrap_err(),
+            vec![ObjectError::at(
+                3,
+                "attribute `mnt-by` must appear before `source`"
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case("route", true)]
+    #[case("AUT-NUM", true)]
+    #[case("organisation", false)]
+    fn rfc2622_templates_contain_class(#[case] class: &str, #[case] expected: bool) {
+        assert_eq!(find(RFC2622, class).is_some(), expected);
+    }
+
+    #[test]
+    fn rfc2622_templates_accept_repeated_descr() {
+        let object = object! {
+            "mntner": "MAINT-EXAMPLE";
+            "descr": "Example maintainer";
+            "descr": "Second line of description";
+            "admin-c": "EX1-RIPE";
+            "upd-to": "noc@example.com";
+            "auth": "CRYPT-PW abcdefghijklm";
+            "mnt-by": "MAINT-EXAMPLE";
+            "changed": "noc@example.com 20240101";
+            "source": "RIPE";
+        };
+        assert_eq!(validate(RFC2622, &object), Ok(()));
+    }
+
+    #[test]
+    fn validate_unknown_class_is_error() {
+        let object = object! {
+            "poem": "POEM-EXAMPLE";
+        };
+        assert_eq!(
+            validate(RFC2622, &object).unwrap_err(),
+            vec![ObjectError::at(0, "unknown object class `poem`")]
+        );
+    }
+}
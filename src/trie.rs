@@ -0,0 +1,616 @@
+//! A prefix trie for address space lookups.
+//!
+//! A [`PrefixTrie`] maps IPv4 and IPv6 [`AddressRange`]s to values, such as the keys of
+//! `route`, `route6`, `inetnum` and `inet6num` objects, and looks them up using the query modes
+//! of whois servers, see [`Match`].
+//!
+//! Ranges do not need to be aligned to a prefix, as allowed by `inetnum` objects, e.g.
+//! `192.0.2.0 - 192.0.2.127`. Each range is stored at the smallest prefix covering it.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::trie::{Match, PrefixTrie};
+//!
+//! let mut trie = PrefixTrie::new();
+//! trie.insert("192.0.0.0/16".parse()?, "AS65536");
+//! trie.insert("192.0.2.0/24".parse()?, "AS65537");
+//! trie.insert("192.0.2.0 - 192.0.2.127".parse()?, "AS65538");
+//!
+//! let query = "192.0.2.0/25".parse()?;
+//! let values = |mode| -> Vec<_> {
+//!     trie.lookup(&query, mode).into_iter().map(|(_, value)| *value).collect()
+//! };
+//! assert_eq!(values(Match::Exact), ["AS65538"]);
+//! assert_eq!(values(Match::LessSpecific), ["AS65537"]);
+//! assert_eq!(values(Match::AllLessSpecific), ["AS65536", "AS65537", "AS65538"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{cmp::Reverse, fmt, net::IpAddr, str::FromStr};
+
+use winnow::{
+    ascii::space0,
+    combinator::{alt, separated_pair},
+    error::{StrContext, StrContextValue},
+    Parser,
+};
+
+use crate::types::{self, Ipv4Range, Prefix, TypeError};
+
+/// An inclusive range of IPv4 or IPv6 addresses, e.g. `192.0.2.0 - 192.0.2.127` or
+/// `2001:db8::/32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressRange {
+    ipv4: bool,
+    /// The bits of the first address, aligned to the most significant bit.
+    start: u128,
+    /// The bits of the last address, aligned to the most significant bit.
+    end: u128,
+}
+
+impl AddressRange {
+    /// Create a range from its first and last address.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the addresses are of different families or the start of the
+    /// range is after its end.
+    pub fn new(start: IpAddr, end: IpAddr) -> Result<Self, TypeError> {
+        let input = format!("{start} - {end}");
+        if start.is_ipv4() != end.is_ipv4() {
+            return Err(TypeError::new(
+                "address range",
+                &input,
+                input.len() - end.to_string().len(),
+                "expected an address of the same family as the range start",
+            ));
+        }
+        if start > end {
+            return Err(TypeError::new(
+                "address range",
+                &input,
+                0,
+                format!("range start {start} is after its end {end}"),
+            ));
+        }
+        Ok(Self {
+            ipv4: start.is_ipv4(),
+            start: types::address_bits(start),
+            end: types::address_bits(end),
+        })
+    }
+
+    /// The first address of the range.
+    #[must_use]
+    pub fn start(&self) -> IpAddr {
+        types::address_from_bits(self.start, self.ipv4)
+    }
+
+    /// The last address of the range.
+    #[must_use]
+    pub fn end(&self) -> IpAddr {
+        types::address_from_bits(self.end, self.ipv4)
+    }
+
+    /// Whether this is a range of IPv4 addresses.
+    #[must_use]
+    pub const fn is_ipv4(&self) -> bool {
+        self.ipv4
+    }
+
+    /// Whether the other range is equal to or within this range.
+    #[must_use]
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.ipv4 == other.ipv4 && self.start <= other.start && other.end <= self.end
+    }
+
+    /// The prefix covering exactly this range, or `None` if the range is not aligned to a prefix.
+    #[must_use]
+    pub fn prefix(&self) -> Option<Prefix> {
+        let key = self.key();
+        let prefix = Prefix::new(self.start(), key.length).ok()?;
+        (Self::from(prefix) == *self).then_some(prefix)
+    }
+
+    /// The smallest prefix covering the range, at which it is stored in a trie.
+    fn key(&self) -> Key {
+        let max = if self.ipv4 { 32 } else { 128 };
+        #[allow(clippy::cast_possible_truncation)]
+        let length = ((self.start ^ self.end).leading_zeros() as u8).min(max);
+        Key {
+            bits: self.start & mask(length),
+            length,
+        }
+    }
+
+    /// The number of addresses in the range minus one, ordering ranges by size.
+    const fn size(&self) -> u128 {
+        self.end - self.start
+    }
+}
+
+impl From<Prefix> for AddressRange {
+    fn from(prefix: Prefix) -> Self {
+        let start = types::address_bits(prefix.network().address());
+        let host = !mask(prefix.length());
+        let host = if prefix.is_ipv4() {
+            host & mask(32)
+        } else {
+            host
+        };
+        Self {
+            ipv4: prefix.is_ipv4(),
+            start,
+            end: start | host,
+        }
+    }
+}
+
+impl From<Ipv4Range> for AddressRange {
+    fn from(range: Ipv4Range) -> Self {
+        Self {
+            ipv4: true,
+            start: types::address_bits(range.start().into()),
+            end: types::address_bits(range.end().into()),
+        }
+    }
+}
+
+impl FromStr for AddressRange {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = alt((
+            separated_pair(types::ip_address, (space0, '-', space0), types::ip_address),
+            types::prefix.map(|prefix| {
+                let range = Self::from(prefix);
+                (range.start(), range.end())
+            }),
+        ))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a prefix or range, e.g. 192.0.2.0 - 192.0.2.127",
+        )));
+        let (start, end) = types::complete(range, "address range", s)?;
+        Self::new(start, end).map_err(|e| TypeError::new("address range", s, 0, e.message()))
+    }
+}
+
+impl fmt::Display for AddressRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.prefix() {
+            Some(prefix) => write!(f, "{prefix}"),
+            None => write!(f, "{} - {}", self.start(), self.end()),
+        }
+    }
+}
+
+/// The query modes of a lookup, named after the corresponding flags of whois servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Match {
+    /// The entries of exactly the range (`-x`).
+    Exact,
+    /// The entries of the smallest ranges containing the range, excluding exact matches (`-l`).
+    LessSpecific,
+    /// The entries of all ranges containing the range, including exact matches (`-L`).
+    AllLessSpecific,
+    /// The entries of the largest ranges within the range, excluding exact matches (`-m`).
+    MoreSpecific,
+    /// The entries of all ranges within the range, excluding exact matches (`-M`).
+    AllMoreSpecific,
+}
+
+/// A trie mapping IPv4 and IPv6 address ranges to values.
+///
+/// Multiple values can be stored for the same range, e.g. `route` objects of the same prefix
+/// originated by different ASes.
+#[derive(Debug, Clone)]
+pub struct PrefixTrie<T> {
+    ipv4: Tree<T>,
+    ipv6: Tree<T>,
+    len: usize,
+}
+
+impl<T> Default for PrefixTrie<T> {
+    fn default() -> Self {
+        Self {
+            ipv4: Tree::default(),
+            ipv6: Tree::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> PrefixTrie<T> {
+    /// Create an empty trie.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value for the range.
+    pub fn insert(&mut self, range: AddressRange, value: T) {
+        let tree = self.tree_mut(range.ipv4);
+        let index = tree.node(range.key());
+        tree.nodes[index].entries.push((range, value));
+        self.len += 1;
+    }
+
+    /// Remove a value of the range, returning whether it was found.
+    pub fn remove(&mut self, range: &AddressRange, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let tree = self.tree_mut(range.ipv4);
+        let Some(index) = tree.find(range.key()) else {
+            return false;
+        };
+        let entries = &mut tree.nodes[index].entries;
+        let Some(position) = entries
+            .iter()
+            .position(|(other, other_value)| other == range && other_value == value)
+        else {
+            return false;
+        };
+        entries.swap_remove(position);
+        self.len -= 1;
+        true
+    }
+
+    /// The number of values in the trie.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the trie contains no values.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The entries matching the range in the query mode.
+    ///
+    /// Less specific entries are ordered from the largest to the smallest range, more specific
+    /// entries by the start of their range, and larger ranges before the ranges within them.
+    #[must_use]
+    pub fn lookup(&self, range: &AddressRange, mode: Match) -> Vec<(&AddressRange, &T)> {
+        let tree = if range.ipv4 { &self.ipv4 } else { &self.ipv6 };
+        let key = range.key();
+        let mut entries: Vec<_> = match mode {
+            Match::Exact => tree
+                .find(key)
+                .into_iter()
+                .flat_map(|index| tree.entries(index))
+                .filter(|(other, _)| *other == range)
+                .collect(),
+            Match::LessSpecific | Match::AllLessSpecific => tree
+                .path(key)
+                .into_iter()
+                .flat_map(|index| tree.entries(index))
+                .filter(|(other, _)| other.contains(range))
+                .filter(|(other, _)| mode == Match::AllLessSpecific || *other != range)
+                .collect(),
+            Match::MoreSpecific | Match::AllMoreSpecific => tree
+                .subtree(key)
+                .into_iter()
+                .flat_map(|index| tree.descendants(index))
+                .flat_map(|index| tree.entries(index))
+                .filter(|(other, _)| range.contains(other) && *other != range)
+                .collect(),
+        };
+        match mode {
+            Match::Exact => {}
+            Match::LessSpecific | Match::AllLessSpecific => {
+                entries.sort_by_key(|(other, _)| Reverse(other.size()));
+                if mode == Match::LessSpecific {
+                    let smallest = entries.last().map(|(other, _)| other.size());
+                    entries.retain(|(other, _)| Some(other.size()) == smallest);
+                }
+            }
+            Match::MoreSpecific | Match::AllMoreSpecific => {
+                entries.sort_by_key(|(other, _)| (other.start, Reverse(other.end)));
+                if mode == Match::MoreSpecific {
+                    let mut outer: Option<&AddressRange> = None;
+                    entries.retain(|(other, _)| {
+                        if outer.is_some_and(|outer| outer.contains(other) && outer != *other) {
+                            return false;
+                        }
+                        outer = Some(other);
+                        true
+                    });
+                }
+            }
+        }
+        entries
+    }
+
+    fn tree_mut(&mut self, ipv4: bool) -> &mut Tree<T> {
+        if ipv4 {
+            &mut self.ipv4
+        } else {
+            &mut self.ipv6
+        }
+    }
+}
+
+/// A prefix of the bits of an address, aligned to the most significant bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Key {
+    bits: u128,
+    length: u8,
+}
+
+impl Key {
+    /// Whether the other key is equal to or more specific than this key.
+    const fn contains(&self, other: &Self) -> bool {
+        self.length <= other.length && (self.bits ^ other.bits) & mask(self.length) == 0
+    }
+
+    /// The longest key containing both keys.
+    fn common(&self, other: &Self) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let length = ((self.bits ^ other.bits).leading_zeros() as u8)
+            .min(self.length)
+            .min(other.length);
+        Self {
+            bits: self.bits & mask(length),
+            length,
+        }
+    }
+
+    /// The bit at the index, counting from the most significant bit.
+    fn bit(&self, index: u8) -> usize {
+        usize::from((self.bits >> (127 - index)) & 1 == 1)
+    }
+}
+
+/// A path compressed binary trie of a single address family.
+///
+/// The first node is the root of the trie, covering all addresses.
+#[derive(Debug, Clone)]
+struct Tree<T> {
+    nodes: Vec<Node<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    key: Key,
+    children: [Option<usize>; 2],
+    entries: Vec<(AddressRange, T)>,
+}
+
+impl<T> Default for Tree<T> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::new(Key { bits: 0, length: 0 })],
+        }
+    }
+}
+
+impl<T> Node<T> {
+    const fn new(key: Key) -> Self {
+        Self {
+            key,
+            children: [None, None],
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    fn push(&mut self, key: Key) -> usize {
+        self.nodes.push(Node::new(key));
+        self.nodes.len() - 1
+    }
+
+    /// The index of the node of the key, creating it if needed.
+    fn node(&mut self, key: Key) -> usize {
+        let mut index = 0;
+        loop {
+            let node = &self.nodes[index];
+            if node.key == key {
+                return index;
+            }
+            let bit = key.bit(node.key.length);
+            let Some(child) = node.children[bit] else {
+                let leaf = self.push(key);
+                self.nodes[index].children[bit] = Some(leaf);
+                return leaf;
+            };
+            let child_key = self.nodes[child].key;
+            if child_key.contains(&key) {
+                index = child;
+                continue;
+            }
+            let (inserted, branch) = if key.contains(&child_key) {
+                let inserted = self.push(key);
+                (inserted, inserted)
+            } else {
+                let common = child_key.common(&key);
+                let branch = self.push(common);
+                let inserted = self.push(key);
+                self.nodes[branch].children[key.bit(common.length)] = Some(inserted);
+                (inserted, branch)
+            };
+            let length = self.nodes[branch].key.length;
+            self.nodes[branch].children[child_key.bit(length)] = Some(child);
+            self.nodes[index].children[bit] = Some(branch);
+            return inserted;
+        }
+    }
+
+    /// The index of the node of the key, if it exists.
+    fn find(&self, key: Key) -> Option<usize> {
+        self.path(key)
+            .last()
+            .copied()
+            .filter(|index| self.nodes[*index].key == key)
+    }
+
+    /// The indexes of the nodes containing the key, starting at the root.
+    fn path(&self, key: Key) -> Vec<usize> {
+        let mut path = vec![0];
+        let mut index = 0;
+        while self.nodes[index].key.length < key.length {
+            match self.nodes[index].children[key.bit(self.nodes[index].key.length)] {
+                Some(child) if self.nodes[child].key.contains(&key) => {
+                    path.push(child);
+                    index = child;
+                }
+                _ => break,
+            }
+        }
+        path
+    }
+
+    /// The index of the largest node within the key.
+    fn subtree(&self, key: Key) -> Option<usize> {
+        let mut index = 0;
+        loop {
+            let node = &self.nodes[index];
+            if key.contains(&node.key) {
+                return Some(index);
+            }
+            if !node.key.contains(&key) {
+                return None;
+            }
+            index = node.children[key.bit(node.key.length)]?;
+        }
+    }
+
+    /// The indexes of the node and all nodes below it.
+    fn descendants(&self, index: usize) -> Vec<usize> {
+        let mut descendants = Vec::new();
+        let mut pending = vec![index];
+        while let Some(index) = pending.pop() {
+            descendants.push(index);
+            pending.extend(self.nodes[index].children.iter().flatten());
+        }
+        descendants
+    }
+
+    fn entries(&self, index: usize) -> impl Iterator<Item = (&AddressRange, &T)> {
+        self.nodes[index]
+            .entries
+            .iter()
+            .map(|(range, value)| (range, value))
+    }
+}
+
+/// The bit mask of a prefix of the length, aligned to the most significant bit of a `u128`.
+const fn mask(length: u8) -> u128 {
+    match u128::MAX.checked_shl(128 - length as u32) {
+        Some(mask) => mask,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("192.0.2.0/24", "192.0.2.0", "192.0.2.255", "192.0.2.0/24")]
+    #[case("192.0.2.1/24", "192.0.2.0", "192.0.2.255", "192.0.2.0/24")]
+    #[case("0.0.0.0/0", "0.0.0.0", "255.255.255.255", "0.0.0.0/0")]
+    #[case("192.0.2.0 - 192.0.2.255", "192.0.2.0", "192.0.2.255", "192.0.2.0/24")]
+    #[case(
+        "192.0.2.0-192.0.2.100",
+        "192.0.2.0",
+        "192.0.2.100",
+        "192.0.2.0 - 192.0.2.100"
+    )]
+    #[case(
+        "2001:db8::/32",
+        "2001:db8::",
+        "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff",
+        "2001:db8::/32"
+    )]
+    #[case(
+        "2001:db8::1 - 2001:db8::1",
+        "2001:db8::1",
+        "2001:db8::1",
+        "2001:db8::1/128"
+    )]
+    fn address_range_from_str(
+        #[case] s: &str,
+        #[case] start: IpAddr,
+        #[case] end: IpAddr,
+        #[case] display: &str,
+    ) {
+        let range: AddressRange = s.parse().unwrap();
+        assert_eq!((range.start(), range.end()), (start, end));
+        assert_eq!(range.to_string(), display);
+    }
+
+    #[rstest]
+    #[case("192.0.2.255 - 192.0.2.0", 0)]
+    #[case("192.0.2.0 - 2001:db8::", 0)]
+    #[case("192.0.2.0 -", 9)]
+    #[case("192.0.2.0/33", 10)]
+    fn address_range_invalid_is_err(#[case] s: &str, #[case] offset: usize) {
+        assert_eq!(s.parse::<AddressRange>().unwrap_err().offset(), offset);
+    }
+
+    fn trie() -> PrefixTrie<&'static str> {
+        let mut trie = PrefixTrie::new();
+        for (range, value) in [
+            ("0.0.0.0/0", "default"),
+            ("192.0.0.0/16", "a"),
+            ("192.0.2.0/24", "b"),
+            ("192.0.2.0/24", "c"),
+            ("192.0.2.0/25", "d"),
+            ("192.0.2.64/26", "e"),
+            ("192.0.2.128 - 192.0.2.223", "f"),
+            ("192.0.2.192/28", "g"),
+            ("198.51.100.0/24", "h"),
+            ("2001:db8::/32", "i"),
+            ("2001:db8::/48", "j"),
+        ] {
+            trie.insert(range.parse().unwrap(), value);
+        }
+        trie
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", Match::Exact, &["b", "c"])]
+    #[case("192.0.2.0/23", Match::Exact, &[])]
+    #[case("192.0.2.0/24", Match::LessSpecific, &["a"])]
+    #[case("192.0.2.0/24", Match::AllLessSpecific, &["default", "a", "b", "c"])]
+    #[case("192.0.2.192/29", Match::LessSpecific, &["g"])]
+    #[case("192.0.2.192/29", Match::AllLessSpecific, &["default", "a", "b", "c", "f", "g"])]
+    #[case("192.0.2.0/24", Match::MoreSpecific, &["d", "f"])]
+    #[case("192.0.2.0/24", Match::AllMoreSpecific, &["d", "e", "f", "g"])]
+    #[case("192.0.0.0/5", Match::MoreSpecific, &["a", "h"])]
+    #[case("192.0.2.128 - 192.0.2.255", Match::AllMoreSpecific, &["f", "g"])]
+    #[case("0.0.0.0/0", Match::LessSpecific, &[])]
+    #[case("2001:db8::/64", Match::AllLessSpecific, &["i", "j"])]
+    #[case("::/0", Match::MoreSpecific, &["i"])]
+    fn prefix_trie_lookup(
+        #[case] range: AddressRange,
+        #[case] mode: Match,
+        #[case] expected: &[&str],
+    ) {
+        let values: Vec<_> = trie()
+            .lookup(&range, mode)
+            .into_iter()
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn prefix_trie_remove() {
+        let mut trie = trie();
+        let range = "192.0.2.0/24".parse().unwrap();
+
+        assert_eq!(trie.len(), 11);
+        assert!(trie.remove(&range, &"b"));
+        assert!(!trie.remove(&range, &"b"));
+        assert!(!trie.remove(&"192.0.2.0/23".parse().unwrap(), &"b"));
+        assert_eq!(trie.len(), 10);
+        assert_eq!(trie.lookup(&range, Match::Exact), [(&range, &"c")]);
+    }
+}
@@ -0,0 +1,1352 @@
+//! Typed values of the primitive types defined by RFC 2622.
+//!
+//! Attribute values are plain text, this module parses them into typed values such as
+//! [`AsNumber`], [`Prefix`] or [`AddressPrefixRange`]. Each type implements [`FromStr`],
+//! returning a [`TypeError`] pointing at the offending part of the input.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use rpsl::types::{AddressPrefixRange, AsNumber, AsSetName, RangeOperator};
+//!
+//! let origin: AsNumber = "AS65536".parse()?;
+//! assert_eq!(origin.get(), 65536);
+//!
+//! let range: AddressPrefixRange = "192.0.2.0/24^24-28".parse()?;
+//! assert_eq!(range.operator(), RangeOperator::Range(24, 28));
+//! assert!(range.contains(&"192.0.2.128/25".parse()?));
+//!
+//! let set: AsSetName = "AS65536:AS-CUSTOMERS".parse()?;
+//! assert_eq!(set.components().collect::<Vec<_>>(), vec!["AS65536", "AS-CUSTOMERS"]);
+//!
+//! let err = "192.0.2.0/33".parse::<AddressPrefixRange>().unwrap_err();
+//! assert_eq!(err.offset(), 10);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Enforcing types in a specification
+//!
+//! A [`Specification`] can enforce the type of an attribute's value using [`parse_value`],
+//! which parses the content of a [`Value`] and returns an [`InvalidValueError`] on failure.
+//!
+//! ```rust
+//! use rpsl::{
+//!     spec::{AttributeError, Specification},
+//!     types::{parse_value, AsNumber},
+//!     Attribute,
+//! };
+//!
+//! #[derive(Debug, Clone, Copy)]
+//! struct TypedOrigin;
+//!
+//! impl Specification for TypedOrigin {
+//!     fn validate_attribute(attribute: &Attribute<'_, Self>) -> Result<(), AttributeError> {
+//!         if attribute.name == "origin" {
+//!             parse_value::<AsNumber, _>(&attribute.value)?;
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//! ```
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use winnow::{
+    ascii::{dec_uint, space0, Caseless},
+    combinator::{alt, cut_err, eof, opt, peek, preceded, separated, separated_pair, terminated},
+    error::{ContextError, ErrMode, StrContext, StrContextValue},
+    stream::AsChar,
+    token::take_while,
+    ModalResult, Parser,
+};
+
+use crate::{
+    spec::{InvalidValueError, Specification},
+    Value,
+};
+
+/// Parse the content of a value into a typed value.
+///
+/// Comments are ignored and values spanning multiple lines are joined by spaces.
+///
+/// # Errors
+/// Returns an [`InvalidValueError`] describing why the value is not of the expected type.
+pub fn parse_value<T, Spec>(value: &Value<'_, Spec>) -> Result<T, InvalidValueError>
+where
+    T: FromStr<Err = TypeError>,
+    Spec: Specification,
+{
+    content(value)
+        .parse()
+        .map_err(|e: TypeError| InvalidValueError::new(value, e.to_string()))
+}
+
+/// The content of a value with comments removed, joining multiple lines by spaces.
+pub(crate) fn content<Spec: Specification>(value: &Value<'_, Spec>) -> String {
+    value
+        .with_content()
+        .into_iter()
+        .map(|line| {
+            line.split_once('#')
+                .map_or(line, |(content, _)| content)
+                .trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// An autonomous system number, e.g. `AS65536`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsNumber(u32);
+
+impl AsNumber {
+    /// Create an AS number.
+    #[must_use]
+    pub const fn new(number: u32) -> Self {
+        Self(number)
+    }
+
+    /// The number of the autonomous system.
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for AsNumber {
+    fn from(number: u32) -> Self {
+        Self(number)
+    }
+}
+
+impl FromStr for AsNumber {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        complete(as_number, "AS number", s)
+    }
+}
+
+impl fmt::Display for AsNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AS{}", self.0)
+    }
+}
+
+/// An IPv4 or IPv6 address prefix, e.g. `192.0.2.0/24`.
+///
+/// RFC 2622 defines IPv4 prefixes only, IPv6 prefixes are added by RFC 4012.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Prefix {
+    address: IpAddr,
+    length: u8,
+}
+
+impl Prefix {
+    /// Create a prefix from an address and the length of its network part.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the length exceeds the size of the address.
+    pub fn new(address: IpAddr, length: u8) -> Result<Self, TypeError> {
+        let max = max_length(address);
+        if length > max {
+            let input = format!("{address}/{length}");
+            return Err(TypeError::new(
+                "prefix",
+                &input,
+                input.len() - length.to_string().len(),
+                format!("prefix length must not exceed {max}"),
+            ));
+        }
+        Ok(Self { address, length })
+    }
+
+    /// The address of the prefix.
+    #[must_use]
+    pub const fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The length of the network part of the prefix.
+    #[must_use]
+    pub const fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// The maximum length of a prefix of this address family, 32 for IPv4 and 128 for IPv6.
+    #[must_use]
+    pub const fn max_length(&self) -> u8 {
+        max_length(self.address)
+    }
+
+    /// Whether this is an IPv4 prefix.
+    #[must_use]
+    pub const fn is_ipv4(&self) -> bool {
+        self.address.is_ipv4()
+    }
+
+    /// Whether this is an IPv6 prefix.
+    #[must_use]
+    pub const fn is_ipv6(&self) -> bool {
+        self.address.is_ipv6()
+    }
+
+    /// The prefix with all bits of the address beyond its length set to zero.
+    #[must_use]
+    pub fn network(&self) -> Self {
+        let bits = address_bits(self.address) & self.mask();
+        Self {
+            address: address_from_bits(bits, self.address.is_ipv4()),
+            length: self.length,
+        }
+    }
+
+    /// Whether the address has bits set beyond the length of the prefix.
+    #[must_use]
+    pub fn has_host_bits(&self) -> bool {
+        self.network() != *self
+    }
+
+    /// Whether the other prefix is equal to or more specific than this prefix.
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && other.length >= self.length
+            && address_bits(other.address) & self.mask() == address_bits(self.address) & self.mask()
+    }
+
+    /// The bit mask of the network part, aligned to the most significant bit of a `u128`.
+    fn mask(&self) -> u128 {
+        u128::MAX
+            .checked_shl(u32::from(128 - self.length))
+            .unwrap_or(0)
+    }
+}
+
+impl FromStr for Prefix {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        complete(prefix, "prefix", s)
+    }
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.length)
+    }
+}
+
+/// An address prefix range, a prefix followed by an optional range operator, e.g. `192.0.2.0/24^+`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressPrefixRange {
+    prefix: Prefix,
+    operator: RangeOperator,
+}
+
+impl AddressPrefixRange {
+    /// Create an address prefix range.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the lengths of the operator are shorter than the prefix,
+    /// exceed the size of its address or the end of the range is before its start.
+    pub fn new(prefix: Prefix, operator: RangeOperator) -> Result<Self, TypeError> {
+        let invalid = |message: String| {
+            let input = format!("{prefix}{operator}");
+            let offset = prefix.to_string().len();
+            Err(TypeError::new(
+                "address prefix range",
+                &input,
+                offset,
+                message,
+            ))
+        };
+
+        if let RangeOperator::Exact(n) | RangeOperator::Range(n, _) = operator {
+            if n < prefix.length {
+                return invalid(format!(
+                    "range must not start before the prefix length {}",
+                    prefix.length
+                ));
+            }
+        }
+        if let RangeOperator::Exact(m) | RangeOperator::Range(_, m) = operator {
+            if m > prefix.max_length() {
+                return invalid(format!("range must not exceed {}", prefix.max_length()));
+            }
+        }
+        if let RangeOperator::Range(n, m) = operator {
+            if n > m {
+                return invalid(format!("range end {m} is before its start {n}"));
+            }
+        }
+
+        Ok(Self { prefix, operator })
+    }
+
+    /// Create the address prefix range matching the lengths of the operator that are valid for
+    /// the prefix, as done when applying an operator to the members of a set.
+    ///
+    /// For example, `^16-24` applied to `192.0.2.0/24` results in `192.0.2.0/24`. Returns `None`
+    /// if none of the lengths are valid for the prefix, such as `^16` for `192.0.2.0/24`.
+    #[must_use]
+    pub fn clamped(prefix: Prefix, operator: RangeOperator) -> Option<Self> {
+        let length = prefix.length;
+        let max = prefix.max_length();
+        let (start, end) = match operator {
+            RangeOperator::None => return Some(Self::from(prefix)),
+            RangeOperator::LessExcl => (length.checked_add(1)?, max),
+            RangeOperator::LessIncl => (length, max),
+            RangeOperator::Exact(n) => (n, n),
+            RangeOperator::Range(n, m) => (n, m),
+        };
+        let (start, end) = (start.max(length), end.min(max));
+        let operator = match (start, end) {
+            _ if start > end => return None,
+            (start, end) if start == length && end == length => RangeOperator::None,
+            (start, end) if start == length && end == max => RangeOperator::LessIncl,
+            (start, end) if start == length + 1 && end == max => RangeOperator::LessExcl,
+            (start, end) if start == end => RangeOperator::Exact(start),
+            (start, end) => RangeOperator::Range(start, end),
+        };
+        Some(Self { prefix, operator })
+    }
+
+    /// The prefix the range is based on.
+    #[must_use]
+    pub const fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// The range operator applied to the prefix.
+    #[must_use]
+    pub const fn operator(&self) -> RangeOperator {
+        self.operator
+    }
+
+    /// The prefix lengths matched by the range, which is empty for `^-` applied to a host route.
+    #[must_use]
+    pub fn lengths(&self) -> RangeInclusive<u8> {
+        let length = self.prefix.length;
+        let max = self.prefix.max_length();
+        match self.operator {
+            RangeOperator::None => length..=length,
+            RangeOperator::LessExcl => length + 1..=max,
+            RangeOperator::LessIncl => length..=max,
+            RangeOperator::Exact(n) => n..=n,
+            RangeOperator::Range(n, m) => n..=m,
+        }
+    }
+
+    /// Whether the prefix is matched by the range.
+    #[must_use]
+    pub fn contains(&self, prefix: &Prefix) -> bool {
+        self.prefix.contains(prefix) && self.lengths().contains(&prefix.length)
+    }
+}
+
+impl From<Prefix> for AddressPrefixRange {
+    fn from(prefix: Prefix) -> Self {
+        Self {
+            prefix,
+            operator: RangeOperator::None,
+        }
+    }
+}
+
+impl FromStr for AddressPrefixRange {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, operator) = complete((prefix, range_operator), "address prefix range", s)?;
+        Self::new(prefix, operator).map_err(|e| TypeError {
+            input: s.to_string(),
+            ..e
+        })
+    }
+}
+
+impl fmt::Display for AddressPrefixRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.prefix, self.operator)
+    }
+}
+
+/// A range operator, selecting more specifics of a prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RangeOperator {
+    /// No operator, only the prefix itself.
+    #[default]
+    None,
+    /// `^-`, the exclusive more specifics of the prefix.
+    LessExcl,
+    /// `^+`, the inclusive more specifics of the prefix.
+    LessIncl,
+    /// `^n`, the more specifics of the prefix of length `n`.
+    Exact(u8),
+    /// `^n-m`, the more specifics of the prefix of length `n` to `m`.
+    Range(u8, u8),
+}
+
+impl RangeOperator {
+    /// Apply this operator to another operator, as done when a range operator is applied to a
+    /// set containing address prefix ranges.
+    ///
+    /// As defined by RFC 2622, an operator overrides `^-` and `^+` as well as the operator
+    /// of a prefix without one, while `^n` and `^n-m` are only overridden by a more
+    /// specific `^n` or `^n-m`.
+    #[must_use]
+    pub fn apply_to(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::None, other) => other,
+            (outer, Self::None | Self::LessExcl | Self::LessIncl) => outer,
+            (Self::Exact(n) | Self::Range(n, _), Self::Exact(m) | Self::Range(m, _)) if n >= m => {
+                self
+            }
+            (_, other) => other,
+        }
+    }
+}
+
+impl fmt::Display for RangeOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => Ok(()),
+            Self::LessExcl => write!(f, "^-"),
+            Self::LessIncl => write!(f, "^+"),
+            Self::Exact(n) => write!(f, "^{n}"),
+            Self::Range(n, m) => write!(f, "^{n}-{m}"),
+        }
+    }
+}
+
+/// An inclusive range of IPv4 addresses, e.g. `192.0.2.0 - 192.0.2.255`, as used by `inetnum`.
+///
+/// A range can also be parsed from a prefix, which is converted into the range it covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4Range {
+    start: Ipv4Addr,
+    end: Ipv4Addr,
+}
+
+impl Ipv4Range {
+    /// Create a range from its first and last address.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the start of the range is after its end.
+    pub fn new(start: Ipv4Addr, end: Ipv4Addr) -> Result<Self, TypeError> {
+        if start > end {
+            let input = format!("{start} - {end}");
+            return Err(TypeError::new(
+                "IPv4 range",
+                &input,
+                0,
+                format!("range start {start} is after its end {end}"),
+            ));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// The first address of the range.
+    #[must_use]
+    pub const fn start(&self) -> Ipv4Addr {
+        self.start
+    }
+
+    /// The last address of the range.
+    #[must_use]
+    pub const fn end(&self) -> Ipv4Addr {
+        self.end
+    }
+}
+
+impl FromStr for Ipv4Range {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = alt((
+            separated_pair(ipv4_address, (space0, '-', space0), ipv4_address),
+            prefix.verify(Prefix::is_ipv4).map(|prefix| {
+                let IpAddr::V4(start) = prefix.network().address else {
+                    unreachable!("verified to be an IPv4 prefix");
+                };
+                let host_mask = u32::MAX.checked_shr(u32::from(prefix.length)).unwrap_or(0);
+                (start, Ipv4Addr::from(u32::from(start) | host_mask))
+            }),
+        ))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a range, e.g. 192.0.2.0 - 192.0.2.255",
+        )));
+        let (start, end) = complete(range, "IPv4 range", s)?;
+        Self::new(start, end).map_err(|e| TypeError {
+            input: s.to_string(),
+            ..e
+        })
+    }
+}
+
+impl fmt::Display for Ipv4Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.start, self.end)
+    }
+}
+
+/// A calendar date in the `YYYYMMDD` format, e.g. `20240131`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date.
+    ///
+    /// # Errors
+    /// Returns a [`TypeError`] if the month or day does not exist.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, TypeError> {
+        let date = Self { year, month, day };
+        if !(1..=12).contains(&month) {
+            return Err(TypeError::new(
+                "date",
+                &date.to_string(),
+                4,
+                "month must be between 01 and 12",
+            ));
+        }
+        let days = match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if !(1..=days).contains(&day) {
+            return Err(TypeError::new(
+                "date",
+                &date.to_string(),
+                6,
+                format!("day must be between 01 and {days}"),
+            ));
+        }
+        Ok(date)
+    }
+
+    /// The year of the date.
+    #[must_use]
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// The month of the date, from 1 to 12.
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month, starting at 1.
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl FromStr for Date {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = complete(date, "date", s)?;
+        Self::new(year, month, day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// An email address, e.g. `noc@example.net`.
+///
+/// Only the common `local-part@domain` form of RFC 822 addresses is supported.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EmailAddress(String);
+
+impl EmailAddress {
+    /// The email address as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The part of the address before the `@`.
+    #[must_use]
+    pub fn local_part(&self) -> &str {
+        self.0.rsplit_once('@').map_or("", |(local, _)| local)
+    }
+
+    /// The domain of the address.
+    #[must_use]
+    pub fn domain(&self) -> &str {
+        self.0.rsplit_once('@').map_or("", |(_, domain)| domain)
+    }
+}
+
+impl FromStr for EmailAddress {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        complete(email_address.take(), "email address", s).map(|s| Self(s.to_string()))
+    }
+}
+
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A NIC handle uniquely identifying a `person` or `role` object, e.g. `JD1-RIPE`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NicHandle(String);
+
+impl NicHandle {
+    /// The NIC handle as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for NicHandle {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        complete(object_name, "NIC handle", s).map(|s| Self(s.to_string()))
+    }
+}
+
+impl fmt::Display for NicHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A BGP community as defined by RFC 1997, e.g. `65536:10` or `no_export`.
+///
+/// Communities are written as a pair of 16 bit numbers separated by a colon, a single 32 bit
+/// number or the name of a well-known community.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Community(u32);
+
+impl Community {
+    /// The `internet` community, which all routes belong to.
+    pub const INTERNET: Self = Self(0);
+    /// The well-known `no_export` community.
+    pub const NO_EXPORT: Self = Self(0xFFFF_FF01);
+    /// The well-known `no_advertise` community.
+    pub const NO_ADVERTISE: Self = Self(0xFFFF_FF02);
+    /// The well-known `no_export_subconfed` community.
+    pub const NO_EXPORT_SUBCONFED: Self = Self(0xFFFF_FF03);
+
+    const NAMED: [(Self, &'static str); 4] = [
+        (Self::INTERNET, "internet"),
+        (Self::NO_EXPORT, "no_export"),
+        (Self::NO_ADVERTISE, "no_advertise"),
+        (Self::NO_EXPORT_SUBCONFED, "no_export_subconfed"),
+    ];
+
+    /// Create a community from its 32 bit value.
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Create a community from its upper and lower 16 bits, e.g. `65535:10`.
+    #[must_use]
+    pub const fn from_parts(high: u16, low: u16) -> Self {
+        Self((high as u32) << 16 | low as u32)
+    }
+
+    /// The 32 bit value of the community.
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for Community {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl FromStr for Community {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((community, _)) = Self::NAMED
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+        {
+            return Ok(*community);
+        }
+        let community = alt((
+            separated_pair(dec_uint, ':', dec_uint).map(|(high, low)| Self::from_parts(high, low)),
+            dec_uint.map(Self),
+        ))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a community, e.g. 65535:10, 4294901770 or no_export",
+        )));
+        complete(community, "community", s)
+    }
+}
+
+impl fmt::Display for Community {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Self::NAMED.iter().find(|(community, _)| community == self) {
+            Some((_, name)) => f.write_str(name),
+            None => write!(f, "{}:{}", self.0 >> 16, self.0 & 0xFFFF),
+        }
+    }
+}
+
+/// Define a type for the name of a set, which is hierarchical and starts with the given prefix.
+macro_rules! set_name {
+    ($(#[$meta:meta])* $ident:ident, $prefix:literal, $kind:literal) => {
+        $(#[$meta])*
+        ///
+        /// Hierarchical names consist of components separated by colons, each being an AS
+        #[doc = concat!("number or a name starting with `", $prefix, "`, of which there must be at least one.")]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $ident(String);
+
+        impl $ident {
+            /// The prefix of names of this kind of set.
+            pub const PREFIX: &'static str = $prefix;
+
+            /// The name as a string.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// The components of a hierarchical name.
+            pub fn components(&self) -> impl Iterator<Item = &str> {
+                self.0.split(':')
+            }
+
+            /// Whether the name is hierarchical, i.e. consists of more than one component.
+            #[must_use]
+            pub fn is_hierarchical(&self) -> bool {
+                self.0.contains(':')
+            }
+        }
+
+        impl FromStr for $ident {
+            type Err = TypeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                complete(set_name($prefix), $kind, s)
+                    .map(|s| Self(s.to_string()))
+            }
+        }
+
+        impl fmt::Display for $ident {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+set_name!(
+    /// The name of an `as-set`, e.g. `AS-EXAMPLE` or `AS65536:AS-CUSTOMERS`.
+    AsSetName,
+    "AS-",
+    "as-set name"
+);
+set_name!(
+    /// The name of a `route-set`, e.g. `RS-EXAMPLE` or `AS65536:RS-CUSTOMERS`.
+    RouteSetName,
+    "RS-",
+    "route-set name"
+);
+set_name!(
+    /// The name of a `filter-set`, e.g. `FLTR-EXAMPLE` or `AS65536:FLTR-BOGONS`.
+    FilterSetName,
+    "FLTR-",
+    "filter-set name"
+);
+set_name!(
+    /// The name of a `peering-set`, e.g. `PRNG-EXAMPLE` or `AS65536:PRNG-PEERS`.
+    PeeringSetName,
+    "PRNG-",
+    "peering-set name"
+);
+
+/// An error that can occur when parsing a typed value.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid {kind} `{input}` at offset {offset}: {message}")]
+pub struct TypeError {
+    kind: &'static str,
+    input: String,
+    offset: usize,
+    message: String,
+}
+
+impl TypeError {
+    pub(crate) fn new(
+        kind: &'static str,
+        input: &str,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            input: input.to_string(),
+            offset,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error from the context of a failed parser, at an offset into the input.
+    ///
+    /// If the parser failed due to a [`TypeError`] of a value it contains, that error is used
+    /// with its offset relative to the input.
+    pub(crate) fn from_context(
+        kind: &'static str,
+        input: &str,
+        offset: usize,
+        error: &ContextError,
+    ) -> Self {
+        if let Some(cause) = error.cause().and_then(|c| c.downcast_ref::<Self>()) {
+            return Self::new(kind, input, offset + cause.offset, cause.message.clone());
+        }
+
+        // Contexts are ordered from the innermost parser outwards, only those of the innermost
+        // parser adding any describe the error.
+        let mut label = None;
+        let mut expected = Vec::new();
+        for context in error.context() {
+            match context {
+                StrContext::Label(_) if label.is_some() || !expected.is_empty() => break,
+                StrContext::Label(inner) => label = Some(inner),
+                StrContext::Expected(value) => expected.push(value.to_string()),
+                _ => {}
+            }
+        }
+        let label = label.filter(|label| **label != kind);
+
+        let message = match (label, expected.is_empty()) {
+            (Some(label), false) => format!("invalid {label}, expected {}", expected.join(", ")),
+            (None, false) => format!("expected {}", expected.join(", ")),
+            (Some(label), true) => format!("invalid {label}"),
+            (None, true) if offset < input.len() => format!("unexpected `{}`", &input[offset..]),
+            (None, true) => "unexpected end of input".to_string(),
+        };
+        Self::new(kind, input, offset, message)
+    }
+
+    /// The kind of value that was expected, e.g. `AS number`.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        self.kind
+    }
+
+    /// The byte offset into the input at which parsing failed.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Why the input is invalid.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Parse the complete input using the parser.
+pub(crate) fn complete<'s, O>(
+    mut parser: impl Parser<&'s str, O, ErrMode<ContextError>>,
+    kind: &'static str,
+    s: &'s str,
+) -> Result<O, TypeError> {
+    parser
+        .parse(s)
+        .map_err(|e| TypeError::from_context(kind, s, e.offset(), e.inner()))
+}
+
+/// Parse an AS number, e.g. `AS65536`.
+pub(crate) fn as_number(input: &mut &str) -> ModalResult<AsNumber> {
+    preceded(Caseless("AS"), dec_uint)
+        .map(AsNumber)
+        .context(StrContext::Label("AS number"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "an AS number, e.g. AS65536",
+        )))
+        .parse_next(input)
+}
+
+/// Parse an IPv4 address, e.g. `192.0.2.1`.
+pub(crate) fn ipv4_address(input: &mut &str) -> ModalResult<Ipv4Addr> {
+    take_while(1.., |c: char| c.is_ascii_digit() || c == '.')
+        .parse_to()
+        .context(StrContext::Label("IPv4 address"))
+        .parse_next(input)
+}
+
+/// Parse an IPv4 or IPv6 address.
+pub(crate) fn ip_address(input: &mut &str) -> ModalResult<IpAddr> {
+    take_while(1.., |c: char| c.is_hex_digit() || c == '.' || c == ':')
+        .parse_to()
+        .context(StrContext::Label("address"))
+        .parse_next(input)
+}
+
+/// Parse an IPv4 or IPv6 prefix, e.g. `192.0.2.0/24`.
+pub(crate) fn prefix(input: &mut &str) -> ModalResult<Prefix> {
+    let address = ip_address(input)?;
+    '/'.context(StrContext::Expected(StrContextValue::CharLiteral('/')))
+        .parse_next(input)?;
+    dec_uint
+        .verify(|length: &u8| *length <= max_length(address))
+        .map(|length| Prefix { address, length })
+        .context(StrContext::Label("prefix length"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            if address.is_ipv4() {
+                "a length of at most 32"
+            } else {
+                "a length of at most 128"
+            },
+        )))
+        .parse_next(input)
+}
+
+/// Parse an optional range operator, e.g. `^+` or `^24-28`.
+pub(crate) fn range_operator(input: &mut &str) -> ModalResult<RangeOperator> {
+    opt(preceded(
+        '^',
+        cut_err(alt((
+            '-'.value(RangeOperator::LessExcl),
+            '+'.value(RangeOperator::LessIncl),
+            (dec_uint, opt(preceded('-', dec_uint))).map(|(n, m)| match m {
+                Some(m) => RangeOperator::Range(n, m),
+                None => RangeOperator::Exact(n),
+            }),
+        )))
+        .context(StrContext::Label("range operator"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "one of ^-, ^+, ^n or ^n-m",
+        ))),
+    ))
+    .map(Option::unwrap_or_default)
+    .parse_next(input)
+}
+
+/// Parse a date in the `YYYYMMDD` format into its year, month and day.
+fn date(input: &mut &str) -> ModalResult<(u16, u8, u8)> {
+    let digits = |count: usize| take_while(count, AsChar::is_dec_digit);
+    (
+        digits(4).parse_to(),
+        digits(2).parse_to(),
+        digits(2).parse_to(),
+    )
+        .context(StrContext::Label("date"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a date in the format YYYYMMDD",
+        )))
+        .parse_next(input)
+}
+
+/// Parse an email address of the form `local-part@domain`.
+fn email_address(input: &mut &str) -> ModalResult<()> {
+    let atom = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c);
+    let label = take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '-')
+        .verify(|label: &str| !label.starts_with('-') && !label.ends_with('-'));
+
+    take_while(1.., atom)
+        .context(StrContext::Label("local part"))
+        .parse_next(input)?;
+    '@'.context(StrContext::Expected(StrContextValue::CharLiteral('@')))
+        .parse_next(input)?;
+    let () = separated(1.., label, '.')
+        .context(StrContext::Label("domain"))
+        .parse_next(input)?;
+    Ok(())
+}
+
+/// Parse the name of an object, starting with a letter and ending with a letter or digit.
+fn object_name<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+    take_while(1.., |c: char| {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_'
+    })
+    .verify(|name: &str| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+    })
+    .context(StrContext::Label("name"))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "letters, digits, - and _, starting with a letter and ending with a letter or digit",
+    )))
+    .parse_next(input)
+}
+
+/// Parse a possibly hierarchical set name, with at least one component starting with the prefix.
+pub(crate) fn set_name<'s>(
+    prefix: &'static str,
+) -> impl Parser<&'s str, &'s str, ErrMode<ContextError>> {
+    move |input: &mut &'s str| {
+        let set_component = (
+            Caseless(prefix),
+            take_while(1.., |c: char| {
+                c.is_ascii_alphanumeric() || c == '-' || c == '_'
+            }),
+        )
+            .value(true);
+        let component = alt((
+            terminated(as_number, peek(alt((':'.void(), eof.void())))).value(false),
+            set_component,
+        ))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "components separated by :, each an AS number or a name starting with the set prefix",
+        )));
+
+        separated::<_, _, Vec<bool>, _, _, _, _>(1.., component, ':')
+            .verify(|is_set: &Vec<bool>| is_set.contains(&true))
+            .take()
+            .context(StrContext::Label("set name"))
+            .parse_next(input)
+    }
+}
+
+const fn max_length(address: IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// The bits of an address, aligned to the most significant bit of a `u128`.
+pub(crate) fn address_bits(address: IpAddr) -> u128 {
+    match address {
+        IpAddr::V4(address) => u128::from(u32::from(address)) << 96,
+        IpAddr::V6(address) => u128::from(address),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn address_from_bits(bits: u128, ipv4: bool) -> IpAddr {
+    if ipv4 {
+        IpAddr::V4(Ipv4Addr::from((bits >> 96) as u32))
+    } else {
+        IpAddr::V6(bits.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("AS65536", Ok(AsNumber::new(65536)))]
+    #[case("as0", Ok(AsNumber::new(0)))]
+    #[case("AS4294967295", Ok(AsNumber::new(u32::MAX)))]
+    #[case("AS4294967296", Err(2))]
+    #[case("AS+1", Err(2))]
+    #[case("65536", Err(0))]
+    #[case("AS", Err(2))]
+    #[case("AS1 ", Err(3))]
+    fn as_number_from_str(#[case] s: &str, #[case] expected: Result<AsNumber, usize>) {
+        assert_eq!(s.parse::<AsNumber>().map_err(|e| e.offset()), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", Ok("192.0.2.0/24"))]
+    #[case("2001:db8::/32", Ok("2001:db8::/32"))]
+    #[case("192.0.2.0/33", Err(10))]
+    #[case("2001:db8::/129", Err(11))]
+    #[case("192.0.2.0", Err(9))]
+    #[case("192.0.2/24", Err(0))]
+    fn prefix_from_str(#[case] s: &str, #[case] expected: Result<&str, usize>) {
+        assert_eq!(
+            s.parse::<Prefix>()
+                .map(|p| p.to_string())
+                .map_err(|e| e.offset())
+                .as_deref(),
+            expected.as_deref()
+        );
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", "192.0.2.0/24", true)]
+    #[case("192.0.2.0/24", "192.0.2.128/25", true)]
+    #[case("192.0.2.0/24", "192.0.3.0/25", false)]
+    #[case("192.0.2.0/24", "192.0.0.0/16", false)]
+    #[case("0.0.0.0/0", "198.51.100.0/24", true)]
+    #[case("0.0.0.0/0", "::/0", false)]
+    #[case("2001:db8::/32", "2001:db8:1::/48", true)]
+    fn prefix_contains(#[case] prefix: Prefix, #[case] other: Prefix, #[case] expected: bool) {
+        assert_eq!(prefix.contains(&other), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.1/24", "192.0.2.0/24")]
+    #[case("192.0.2.0/24", "192.0.2.0/24")]
+    #[case("2001:db8::1/32", "2001:db8::/32")]
+    #[case("255.255.255.255/0", "0.0.0.0/0")]
+    fn prefix_network(#[case] prefix: Prefix, #[case] expected: Prefix) {
+        assert_eq!(prefix.network(), expected);
+        assert_eq!(prefix.has_host_bits(), prefix != expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", RangeOperator::None, Some("192.0.2.0/24"))]
+    #[case("192.0.2.0/24", RangeOperator::Range(16, 24), Some("192.0.2.0/24"))]
+    #[case(
+        "192.0.2.0/24",
+        RangeOperator::Range(16, 28),
+        Some("192.0.2.0/24^24-28")
+    )]
+    #[case("192.0.2.0/24", RangeOperator::Range(25, 40), Some("192.0.2.0/24^-"))]
+    #[case("192.0.2.0/24", RangeOperator::Range(24, 32), Some("192.0.2.0/24^+"))]
+    #[case("192.0.2.0/24", RangeOperator::Range(26, 26), Some("192.0.2.0/24^26"))]
+    #[case("192.0.2.0/24", RangeOperator::Exact(16), None)]
+    #[case("192.0.2.1/32", RangeOperator::LessExcl, None)]
+    fn address_prefix_range_clamped(
+        #[case] prefix: Prefix,
+        #[case] operator: RangeOperator,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            AddressPrefixRange::clamped(prefix, operator)
+                .map(|range| range.to_string())
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", RangeOperator::None, 24..=24)]
+    #[case("192.0.2.0/24^-", RangeOperator::LessExcl, 25..=32)]
+    #[case("192.0.2.0/24^+", RangeOperator::LessIncl, 24..=32)]
+    #[case("192.0.2.0/24^26", RangeOperator::Exact(26), 26..=26)]
+    #[case("192.0.2.0/24^24-28", RangeOperator::Range(24, 28), 24..=28)]
+    #[case("2001:db8::/32^48", RangeOperator::Exact(48), 48..=48)]
+    fn address_prefix_range_from_str(
+        #[case] s: &str,
+        #[case] operator: RangeOperator,
+        #[case] lengths: RangeInclusive<u8>,
+    ) {
+        let range: AddressPrefixRange = s.parse().unwrap();
+        assert_eq!(range.operator(), operator);
+        assert_eq!(range.lengths(), lengths);
+        assert_eq!(range.to_string(), s);
+    }
+
+    #[rstest]
+    #[case(
+        "192.0.2.0/24^",
+        13,
+        "invalid range operator, expected one of ^-, ^+, ^n or ^n-m"
+    )]
+    #[case("192.0.2.0/24^33", 12, "range must not exceed 32")]
+    #[case(
+        "192.0.2.0/24^16",
+        12,
+        "range must not start before the prefix length 24"
+    )]
+    #[case("192.0.2.0/24^28-26", 12, "range end 26 is before its start 28")]
+    #[case("192.0.2.0/24^+ ", 14, "unexpected ` `")]
+    fn address_prefix_range_invalid_is_expected_err(
+        #[case] s: &str,
+        #[case] offset: usize,
+        #[case] message: &str,
+    ) {
+        let err = s.parse::<AddressPrefixRange>().unwrap_err();
+        assert_eq!((err.offset(), err.message()), (offset, message));
+        assert_eq!(
+            err.to_string(),
+            format!("invalid address prefix range `{s}` at offset {offset}: {message}")
+        );
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24^+", "192.0.2.0/24", true)]
+    #[case("192.0.2.0/24^+", "192.0.2.0/25", true)]
+    #[case("192.0.2.0/24^-", "192.0.2.0/24", false)]
+    #[case("192.0.2.0/24^-", "192.0.2.0/32", true)]
+    #[case("192.0.2.0/24^25-26", "192.0.2.64/26", true)]
+    #[case("192.0.2.0/24^25-26", "192.0.2.64/27", false)]
+    #[case("192.0.2.0/24", "192.0.3.0/24", false)]
+    fn address_prefix_range_contains(
+        #[case] range: AddressPrefixRange,
+        #[case] prefix: Prefix,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(range.contains(&prefix), expected);
+    }
+
+    #[rstest]
+    #[case(RangeOperator::LessIncl, RangeOperator::None, RangeOperator::LessIncl)]
+    #[case(RangeOperator::None, RangeOperator::LessExcl, RangeOperator::LessExcl)]
+    #[case(
+        RangeOperator::LessIncl,
+        RangeOperator::LessExcl,
+        RangeOperator::LessIncl
+    )]
+    #[case(
+        RangeOperator::Exact(28),
+        RangeOperator::LessIncl,
+        RangeOperator::Exact(28)
+    )]
+    #[case(
+        RangeOperator::LessExcl,
+        RangeOperator::Range(25, 28),
+        RangeOperator::Range(25, 28)
+    )]
+    #[case(
+        RangeOperator::Range(26, 30),
+        RangeOperator::Range(25, 28),
+        RangeOperator::Range(26, 30)
+    )]
+    #[case(
+        RangeOperator::Exact(24),
+        RangeOperator::Range(25, 28),
+        RangeOperator::Range(25, 28)
+    )]
+    fn range_operator_apply_to(
+        #[case] outer: RangeOperator,
+        #[case] inner: RangeOperator,
+        #[case] expected: RangeOperator,
+    ) {
+        assert_eq!(outer.apply_to(inner), expected);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0 - 192.0.2.255", Ok("192.0.2.0 - 192.0.2.255"))]
+    #[case("192.0.2.0-192.0.2.127", Ok("192.0.2.0 - 192.0.2.127"))]
+    #[case("192.0.2.0/25", Ok("192.0.2.0 - 192.0.2.127"))]
+    #[case("0.0.0.0/0", Ok("0.0.0.0 - 255.255.255.255"))]
+    #[case("192.0.2.1/32", Ok("192.0.2.1 - 192.0.2.1"))]
+    #[case("192.0.2.255 - 192.0.2.0", Err(0))]
+    #[case("2001:db8::/32", Err(0))]
+    fn ipv4_range_from_str(#[case] s: &str, #[case] expected: Result<&str, usize>) {
+        assert_eq!(
+            s.parse::<Ipv4Range>()
+                .map(|r| r.to_string())
+                .map_err(|e| e.offset())
+                .as_deref(),
+            expected.as_deref()
+        );
+    }
+
+    #[rstest]
+    #[case("20240131", Ok((2024, 1, 31)))]
+    #[case("20240229", Ok((2024, 2, 29)))]
+    #[case("20230229", Err(6))]
+    #[case("21000229", Err(6))]
+    #[case("20231301", Err(4))]
+    #[case("2024-01-31", Err(4))]
+    #[case("2024013", Err(6))]
+    fn date_from_str(#[case] s: &str, #[case] expected: Result<(u16, u8, u8), usize>) {
+        assert_eq!(
+            s.parse::<Date>()
+                .map(|d| (d.year(), d.month(), d.day()))
+                .map_err(|e| e.offset()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("noc@example.net", true)]
+    #[case("first.last+irr@sub.example.net", true)]
+    #[case("noc@localhost", true)]
+    #[case("noc@", false)]
+    #[case("@example.net", false)]
+    #[case("noc example.net", false)]
+    #[case("noc@example..net", false)]
+    #[case("noc@-example.net", false)]
+    fn email_address_from_str(#[case] s: &str, #[case] valid: bool) {
+        let parsed = s.parse::<EmailAddress>();
+        assert_eq!(parsed.is_ok(), valid);
+        if let Ok(email) = parsed {
+            assert_eq!(format!("{}@{}", email.local_part(), email.domain()), s);
+        }
+    }
+
+    #[rstest]
+    #[case("JD1-RIPE", true)]
+    #[case("AUTO-1", true)]
+    #[case("EX_1", true)]
+    #[case("1JD-RIPE", false)]
+    #[case("JD1-", false)]
+    #[case("JD 1", false)]
+    fn nic_handle_from_str(#[case] s: &str, #[case] valid: bool) {
+        assert_eq!(s.parse::<NicHandle>().is_ok(), valid);
+    }
+
+    #[rstest]
+    #[case("65535:10", Ok(Community::from_parts(65535, 10)))]
+    #[case("4294901770", Ok(Community::from_parts(65535, 10)))]
+    #[case("NO_EXPORT", Ok(Community::NO_EXPORT))]
+    #[case("internet", Ok(Community::INTERNET))]
+    #[case("65536:10", Err(5))]
+    #[case("65535:", Err(5))]
+    #[case("4294967296", Err(0))]
+    #[case("no-export", Err(0))]
+    fn community_from_str(#[case] s: &str, #[case] expected: Result<Community, usize>) {
+        assert_eq!(s.parse::<Community>().map_err(|e| e.offset()), expected);
+    }
+
+    #[rstest]
+    #[case(Community::from_parts(65535, 10), "65535:10")]
+    #[case(Community::new(0xFFFF_FF02), "no_advertise")]
+    fn community_display(#[case] community: Community, #[case] expected: &str) {
+        assert_eq!(community.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("AS-EXAMPLE", true)]
+    #[case("as-example_2", true)]
+    #[case("AS65536:AS-CUSTOMERS", true)]
+    #[case("AS-EXAMPLE:AS-CUSTOMERS:AS1", true)]
+    #[case("AS-", false)]
+    #[case("AS65536", false)]
+    #[case("AS65536:AS65537", false)]
+    #[case("EXAMPLE", false)]
+    #[case("AS-EXAMPLE:", false)]
+    #[case("AS-EXAMPLE:RS-EXAMPLE", false)]
+    #[case("AS-EX AMPLE", false)]
+    #[case("AS65536X:AS-EXAMPLE", false)]
+    fn as_set_name_from_str(#[case] s: &str, #[case] valid: bool) {
+        assert_eq!(s.parse::<AsSetName>().is_ok(), valid);
+    }
+
+    #[test]
+    fn set_names_of_other_kinds() {
+        assert!("AS65536:RS-CUSTOMERS".parse::<RouteSetName>().is_ok());
+        assert!("FLTR-BOGONS".parse::<FilterSetName>().is_ok());
+        assert!("PRNG-PEERS".parse::<PeeringSetName>().is_ok());
+        assert!("AS-EXAMPLE".parse::<RouteSetName>().is_err());
+    }
+
+    #[test]
+    fn parse_value_ignores_comments_and_joins_lines() {
+        let value: Value = vec!["AS65536:AS-CUSTOMERS # Customers"].into();
+        let name: AsSetName = parse_value(&value).unwrap();
+        assert_eq!(name.as_str(), "AS65536:AS-CUSTOMERS");
+
+        let value: Value = "AS-EXAMPLE:".into();
+        let err = parse_value::<AsSetName, _>(&value).unwrap_err();
+        assert_eq!(err.value, value);
+    }
+}
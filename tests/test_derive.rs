@@ -0,0 +1,123 @@
+#![allow(missing_docs)]
+#![cfg(feature = "derive")]
+use std::net::IpAddr;
+
+use rpsl::{
+    convert::{FromObject, FromObjectError, IntoObject},
+    object, parse_object,
+    spec::{AttributeError, InvalidValueError},
+    Name, Value,
+};
+
+#[derive(Debug, PartialEq, FromObject, IntoObject)]
+struct InetRouter {
+    #[rpsl(required)]
+    inet_rtr: String,
+    descr: Option<String>,
+    #[rpsl(required)]
+    local_as: u32,
+    #[rpsl(name = "ifaddr", multiple, required)]
+    interfaces: Vec<IpAddr>,
+    #[rpsl(multiple)]
+    member_of: Vec<String>,
+    #[rpsl(name = "mnt-by", multiple, required)]
+    maintainers: Vec<String>,
+    #[rpsl(required)]
+    source: String,
+}
+
+fn inet_rtr() -> InetRouter {
+    InetRouter {
+        inet_rtr: "rtr1.example.net".to_string(),
+        descr: Some("Example router".to_string()),
+        local_as: 64496,
+        interfaces: vec!["192.0.2.1".parse().unwrap(), "2001:db8::1".parse().unwrap()],
+        member_of: vec![],
+        maintainers: vec!["MAINT-EXAMPLE".to_string()],
+        source: "RIPE".to_string(),
+    }
+}
+
+#[test]
+fn derived_from_object() {
+    let object = parse_object(concat!(
+        "inet-rtr:       rtr1.example.net\n",
+        "descr:          Example\n",
+        "                router\n",
+        "local-as:       64496\n",
+        "ifaddr:         192.0.2.1\n",
+        "ifaddr:         2001:db8::1\n",
+        "mnt-by:         MAINT-EXAMPLE\n",
+        "remarks:        Not part of the model\n",
+        "source:         RIPE\n",
+        "\n",
+    ))
+    .unwrap();
+    assert_eq!(InetRouter::from_object(&object).unwrap(), inet_rtr());
+}
+
+#[test]
+fn derived_into_object() {
+    assert_eq!(
+        inet_rtr().into_object(),
+        object! {
+            "inet-rtr": "rtr1.example.net";
+            "descr": "Example router";
+            "local-as": "64496";
+            "ifaddr": "192.0.2.1";
+            "ifaddr": "2001:db8::1";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        }
+    );
+}
+
+#[test]
+/// Typed models round trip through RPSL.
+fn derived_roundtrip() {
+    let rpsl = inet_rtr().into_object().to_string();
+    let object = parse_object(&rpsl).unwrap();
+    assert_eq!(InetRouter::from_object(&object).unwrap(), inet_rtr());
+}
+
+#[test]
+fn derived_from_object_missing_required_is_err() {
+    let object = object! {
+        "inet-rtr": "rtr1.example.net";
+        "local-as": "64496";
+        "ifaddr": "192.0.2.1";
+        "source": "RIPE";
+    };
+    assert_eq!(
+        InetRouter::from_object(&object).unwrap_err(),
+        FromObjectError::MissingAttribute(Name::new("mnt-by"))
+    );
+}
+
+#[test]
+fn derived_from_object_invalid_value_is_err() {
+    let object = object! {
+        "inet-rtr": "rtr1.example.net";
+        "local-as": "AS64496";
+        "ifaddr": "192.0.2.1";
+        "mnt-by": "MAINT-EXAMPLE";
+        "source": "RIPE";
+    };
+    let FromObjectError::InvalidAttribute { index, error } =
+        InetRouter::from_object(&object).unwrap_err()
+    else {
+        unreachable!("expected an invalid attribute");
+    };
+    assert_eq!(index, 1);
+    let AttributeError::InvalidValue(InvalidValueError { value, .. }) = error else {
+        unreachable!("expected an invalid value");
+    };
+    assert_eq!(value, Value::new_single("AS64496"));
+}
+
+#[test]
+fn derive_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,253 @@
+#![allow(missing_docs)]
+//! A corpus of objects accepted and rejected by the RIPE Database.
+use rpsl::{
+    parse_object,
+    spec::{AttributeError, RipeDb},
+};
+use rstest::*;
+
+#[rstest]
+#[case::organisation(
+    "organisation:   ORG-EA1-RIPE
+org-name:       Example Association
+org-type:       OTHER
+address:        Packet Street 6
+address:        Frankfurt am Main
+country:        DE
+e-mail:         noc@example.com
+abuse-c:        EA1-RIPE
+mnt-ref:        EXAMPLE-MNT
+mnt-by:         EXAMPLE-MNT
+created:        2024-01-01T00:00:00Z
+last-modified:  2024-01-01T00:00:00Z
+source:         RIPE
+
+"
+)]
+#[case::irt(
+    "irt:            IRT-EXAMPLE
+address:        Packet Street 6
+e-mail:         irt@example.com
+abuse-mailbox:  abuse@example.com
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+auth:           SSO noc@example.com
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+"
+)]
+#[case::domain(
+    "domain:         2.0.192.in-addr.arpa
+descr:          Reverse delegation
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+zone-c:         EA1-RIPE
+nserver:        ns1.example.com
+nserver:        ns2.example.com
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+"
+)]
+#[case::as_block(
+    "as-block:       AS65536 - AS65551
+descr:          Documentation AS numbers
+mnt-by:         RIPE-DBM-MNT
+source:         RIPE
+
+"
+)]
+#[case::poem(
+    "poem:           POEM-RPSL
+form:           FORM-HAIKU
+text:           Attributes align
+text:           each value after a colon
+text:           routes find their way home
+author:         EA1-RIPE
+mnt-by:         LIM-MNT
+source:         RIPE
+
+"
+)]
+#[case::inetnum(
+    "inetnum:        192.0.2.0 - 192.0.2.255
+netname:        EXAMPLE-NET
+country:        NL
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+status:         ASSIGNED PA
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+"
+)]
+#[case::inet6num(
+    "inet6num:       2001:db8::/32
+netname:        EXAMPLE-NET6
+country:        EU
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+status:         ALLOCATED-BY-RIR
+mnt-by:         RIPE-NCC-HM-MNT
+source:         RIPE
+
+"
+)]
+#[case::route6_nonauth(
+    "route6:         2001:db8::/48
+origin:         AS65536
+mnt-by:         EXAMPLE-MNT
+source:         RIPE-NONAUTH
+
+"
+)]
+#[case::aut_num(
+    "aut-num:        AS65536
+as-name:        EXAMPLE-AS
+import:         from AS65537 accept ANY
+mp-export:      afi ipv6.unicast to AS65537 announce AS65536
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+status:         ASSIGNED
+mnt-by:         EXAMPLE-MNT
+source:         ripe
+
+"
+)]
+fn ripe_db_accepts_object(#[case] rpsl: &str) {
+    let object = parse_object(rpsl).unwrap();
+    object.validate::<RipeDb>().unwrap();
+}
+
+#[rstest]
+#[case::unknown_class(
+    "geofeed:        192.0.2.0/24
+source:         RIPE
+
+",
+    "unknown object class `geofeed`"
+)]
+#[case::missing_org_type(
+    "organisation:   ORG-EA1-RIPE
+org-name:       Example Association
+address:        Packet Street 6
+e-mail:         noc@example.com
+mnt-ref:        EXAMPLE-MNT
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "missing mandatory attribute `org-type`"
+)]
+#[case::invalid_org_type(
+    "organisation:   ORG-EA1-RIPE
+org-name:       Example Association
+org-type:       ISP
+address:        Packet Street 6
+e-mail:         noc@example.com
+mnt-ref:        EXAMPLE-MNT
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "`ISP` is not a valid organisation type"
+)]
+#[case::invalid_country(
+    "inetnum:        192.0.2.0 - 192.0.2.255
+netname:        EXAMPLE-NET
+country:        XX
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+status:         ASSIGNED PA
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "`XX` is not a valid country code"
+)]
+#[case::inet6num_status_on_inetnum(
+    "inetnum:        192.0.2.0 - 192.0.2.255
+netname:        EXAMPLE-NET
+country:        NL
+admin-c:        EA1-RIPE
+tech-c:         EA1-RIPE
+status:         ALLOCATED-BY-RIR
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "`ALLOCATED-BY-RIR` is not a valid status for class `inetnum`"
+)]
+#[case::repeated_poem_maintainer(
+    "poem:           POEM-RPSL
+form:           FORM-HAIKU
+text:           Attributes align
+mnt-by:         LIM-MNT
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "attribute `mnt-by` must not appear more than once"
+)]
+#[case::ipv6_route(
+    "route:          2001:db8::/48
+origin:         AS65536
+mnt-by:         EXAMPLE-MNT
+source:         RIPE
+
+",
+    "must be an IPv4 prefix"
+)]
+#[case::changed(
+    "as-block:       AS65536 - AS65551
+changed:        noc@example.com 20240101
+mnt-by:         RIPE-DBM-MNT
+source:         RIPE
+
+",
+    "unknown attribute `changed` in class `as-block`"
+)]
+#[case::grs_source(
+    "as-block:       AS65536 - AS65551
+mnt-by:         RIPE-DBM-MNT
+source:         ARIN-GRS
+
+",
+    "`ARIN-GRS` is mirrored from another registry and cannot be submitted"
+)]
+#[case::nonauth_mntner(
+    "mntner:         EXAMPLE-MNT
+admin-c:        EA1-RIPE
+upd-to:         noc@example.com
+auth:           SSO noc@example.com
+mnt-by:         EXAMPLE-MNT
+source:         RIPE-NONAUTH
+
+",
+    "`RIPE-NONAUTH` is not a valid source for class `mntner`"
+)]
+#[case::other_registry(
+    "mntner:         EXAMPLE-MNT
+admin-c:        EA1-RIPE
+upd-to:         noc@example.com
+auth:           SSO noc@example.com
+mnt-by:         EXAMPLE-MNT
+source:         RADB
+
+",
+    "`RADB` is not a valid source, expected `RIPE`"
+)]
+fn ripe_db_rejects_object(#[case] rpsl: &str, #[case] reason: &str) {
+    let object = parse_object(rpsl).unwrap();
+    let error = object.validate::<RipeDb>().unwrap_err();
+    let reasons: Vec<String> = error
+        .iter_errors()
+        .map(|error| match error {
+            AttributeError::InvalidName(error) => error.message.clone(),
+            AttributeError::InvalidValue(error) => error.message.clone(),
+        })
+        .chain(error.object_errors().iter().map(ToString::to_string))
+        .collect();
+    assert_eq!(reasons, [reason]);
+}
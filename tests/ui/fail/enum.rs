@@ -0,0 +1,8 @@
+use rpsl::convert::FromObject;
+
+#[derive(FromObject)]
+enum Class {
+    Mntner,
+}
+
+fn main() {}
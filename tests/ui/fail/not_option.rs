@@ -0,0 +1,8 @@
+use rpsl::convert::FromObject;
+
+#[derive(FromObject)]
+struct Mntner {
+    descr: String,
+}
+
+fn main() {}
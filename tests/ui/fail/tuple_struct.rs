@@ -0,0 +1,6 @@
+use rpsl::convert::IntoObject;
+
+#[derive(IntoObject)]
+struct Mntner(String);
+
+fn main() {}
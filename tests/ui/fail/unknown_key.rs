@@ -0,0 +1,9 @@
+use rpsl::convert::FromObject;
+
+#[derive(FromObject)]
+struct Mntner {
+    #[rpsl(required, key)]
+    mntner: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use rpsl::convert::{FromObject, IntoObject};
+
+#[derive(FromObject, IntoObject)]
+struct Mntner {
+    #[rpsl(required)]
+    mntner: String,
+    descr: Option<String>,
+    #[rpsl(name = "mnt-by", required, multiple)]
+    maintainers: Vec<String>,
+}
+
+fn main() {}